@@ -1,12 +1,6 @@
 // 导出频谱分析结果为 CSV 文件
 
-mod audio_reader;
-mod fft_processor;
-mod frequency_estimator;
-
-use audio_reader::AudioData;
-use fft_processor::FftResult;
-use frequency_estimator::FrequencyEstimator;
+use amdemod::{AudioData, FftResult, FrequencyEstimator};
 use std::error::Error;
 use std::fs::File;
 use std::io::Write;
@@ -32,10 +26,14 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // 计算 FFT
     println!("计算 FFT...");
+    // 导出按双边布局（0 到 f_s）展开的幅度谱，与先前的 CSV 一致
     let fft_result = FftResult::compute(&samples, sample_rate);
-    let frequencies = &fft_result.frequencies;
-    let magnitude = &fft_result.magnitude;
-    let magnitude_db = fft_result.get_magnitude_db();
+    let (frequencies, magnitude) = fft_result.two_sided_magnitude();
+    let magnitude_db: Vec<f64> = magnitude
+        .iter()
+        .map(|&m| if m > 1e-10 { 20.0 * m.log10() } else { -200.0 })
+        .collect();
+    let (frequencies, magnitude) = (&frequencies, &magnitude);
     
     println!("FFT 点数: {}", num_samples);
     println!("频率分辨率: {:.4} Hz\n", sample_rate / num_samples as f64);
@@ -176,9 +174,9 @@ fn export_waveform_data(
     let num_samples = max_samples.unwrap_or(samples.len()).min(samples.len());
     
     // 写入数据
-    for i in 0..num_samples {
+    for (i, &sample) in samples.iter().take(num_samples).enumerate() {
         let time = i as f64 / sample_rate;
-        writeln!(file, "{:.6},{:.6}", time, samples[i])?;
+        writeln!(file, "{:.6},{:.6}", time, sample)?;
     }
     
     Ok(())
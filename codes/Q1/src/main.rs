@@ -1,15 +1,11 @@
 // Q1 主程序：频谱分析与频率偏差估计
 // 整合四个模块完成完整的分析流程
 
-mod audio_reader;
-mod fft_processor;
-mod spectrum_visualizer;
-mod frequency_estimator;
-
-use audio_reader::AudioData;
-use fft_processor::FftResult;
-use spectrum_visualizer::SpectrumVisualizer;
-use frequency_estimator::FrequencyEstimator;
+use amdemod::stages::{self, DEFAULT_BANDWIDTH};
+use amdemod::{
+    AudioData, FftResult, FrequencyEstimator, FrequencyOffset, PipelineParams, SignResolution,
+    SpectrumVisualizer,
+};
 use std::error::Error;
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -97,78 +93,11 @@ fn main() -> Result<(), Box<dyn Error>> {
     // ===== 步骤 4: 频率偏差估计 =====
     println!("步骤 4: 估计频率偏差 (First 0.1s)f_d...\n");
     
-    // 基本频率估计（排除直流，搜索 10 Hz 到 10 kHz）
-    let (f_d, peak_mag, peak_idx) = FrequencyEstimator::estimate_frequency_offset(
-        frequencies,
-        magnitude,
-        (10.0, 10000.0),
-        true, // 排除直流分量
-    );
-
-    // 精确频率估计（使用抛物线插值）
-    let f_d_refined = FrequencyEstimator::refined_frequency_estimate(
-        frequencies,
-        magnitude,
-        peak_idx,
-    );
-
-    // 寻找多个峰值
-    println!();
-    let threshold = magnitude[peak_idx] * 0.1; // 设置阈值为主峰的 10%
-    let peaks = FrequencyEstimator::find_multiple_peaks(
-        frequencies,
-        magnitude,
-        5, // 最多找 5 个峰值
-        20, // 最小间隔 20 个采样点
-        threshold,
-    );
-    
-    // 通过对称峰值分析确定真实的频率偏差
-    println!("\n=== 对称峰值分析 ===");
-    println!("检测到的峰值：");
-    for (i, (freq, mag, _)) in peaks.iter().enumerate() {
-        println!("  峰值 {}: {:.2} Hz (幅度: {:.6})", i + 1, freq, mag);
-    }
-    
-    // 寻找对称峰值对（幅度相近的峰值）
-    // 仅在低频区域 (0-5000 Hz) 内搜索，因为频率偏差应该在这个范围内
-    let mut symmetric_pairs = Vec::new();
-    for i in 0..peaks.len() {
-        for j in (i+1)..peaks.len() {
-            let (f1, mag1, _) = peaks[i];
-            let (f2, mag2, _) = peaks[j];
-            
-            // 只考虑低频区域的峰值
-            if f1 > 5000.0 || f2 > 5000.0 {
-                continue;
-            }
-            
-            let mag_ratio = mag1.min(mag2) / mag1.max(mag2);
-            // 如果幅度相差小于10%，认为是对称峰值对
-            if mag_ratio > 0.9 {
-                let axis = (f1 + f2) / 2.0;
-                let baseband = (f2 - f1).abs() / 2.0;
-                symmetric_pairs.push((f1, f2, axis, mag1, mag2, baseband));
-            }
-        }
-    }
-    
-    // 选择最佳的对称轴（幅度最大的对称峰值对）
-    let f_d_symmetric = if let Some(&(f1, f2, axis, mag1, mag2, baseband)) = symmetric_pairs
-        .iter()
-        .max_by(|a, b| a.3.partial_cmp(&b.3).unwrap()) {
-        println!("\n找到对称峰值对：");
-        let (lower_freq, lower_mag) = if f1 < f2 { (f1, mag1) } else { (f2, mag2) };
-        let (upper_freq, upper_mag) = if f1 > f2 { (f1, mag1) } else { (f2, mag2) };
-        println!("  下边带峰值: {:.2} Hz (幅度: {:.6})", lower_freq, lower_mag);
-        println!("  上边带峰值: {:.2} Hz (幅度: {:.6})", upper_freq, upper_mag);
-        println!("  频谱对称轴: {:.2} Hz ← 真实的频率偏差 f_d", axis);
-        println!("  基带频率成分: {:.2} Hz", baseband);
-        axis
-    } else {
-        println!("警告：未找到明显的对称峰值对，使用峰值搜索结果");
-        f_d_refined
-    };
+    let analysis = stages::estimate_offset(&fft_result);
+    let f_d = analysis.peak_frequency;
+    let f_d_refined = analysis.refined_frequency;
+    let offset = analysis.offset;
+    let f_d_symmetric = offset.f_d;
 
     // 计算能量分布
     let energy_bands = vec![
@@ -177,17 +106,19 @@ fn main() -> Result<(), Box<dyn Error>> {
         (4000.0, 8000.0),
         (8000.0, sample_rate / 2.0),
     ];
+    // 能量占比按双边频谱（0 到 f_s）计算，与先前的输出一致
+    let (two_sided_frequencies, two_sided_magnitude) = fft_result.two_sided_magnitude();
     FrequencyEstimator::compute_energy_distribution(
-        magnitude,
-        frequencies,
+        &two_sided_magnitude,
+        &two_sided_frequencies,
         &energy_bands,
     );
 
-    // 分析频率关系
-    FrequencyEstimator::analyze_frequency_relationship(
-        frequencies,
-        magnitude,
-        f_d_refined,
+    // 分析频率关系（f̃_c 与 f_c 的大小）
+    let sign = FrequencyEstimator::analyze_frequency_relationship(
+        &fft_result,
+        f_d_symmetric,
+        DEFAULT_BANDWIDTH,
     );
 
     // ===== 结果总结 =====
@@ -197,16 +128,20 @@ fn main() -> Result<(), Box<dyn Error>> {
     println!("1. 频率偏差估计:");
     println!("   峰值搜索法: {:.2} Hz (单个峰值)", f_d);
     println!("   抛物线插值: {:.4} Hz (精确峰值)", f_d_refined);
-    println!("   对称峰值法: {:.2} Hz (频谱对称轴) ← 推荐使用", f_d_symmetric);
+    println!("   对称轴法:   {:.2} Hz (频谱对称轴，置信度 {:.2}) ← 推荐使用", f_d_symmetric, offset.confidence);
     println!();
     println!("   说明：");
     println!("   - 单个峰值 ({:.2} Hz) 反映的是原始信号的能量分布", f_d_refined);
     println!("   - 频谱对称轴 ({:.2} Hz) 才是真实的频率偏差 f_d = f_c - f̃_c", f_d_symmetric);
     println!();
     println!("2. 关于 f̃_c 与 f_c 的大小关系:");
-    println!("   - 仅从幅度谱无法唯一确定 f̃_c > f_c 还是 f̃_c < f_c");
-    println!("   - 原因: AM 信号的频谱具有共轭对称性");
-    println!("   - 无论符号如何，错误解调后的幅度谱都相同");
+    println!(
+        "   - 镜像相干度 ρ = {:.4}，边带不对称度 a = {:+.4}，谐波不对称度 h = {:+.4}",
+        sign.mirror_coherence, sign.sideband_asymmetry, sign.harmonic_asymmetry
+    );
+    println!("   - 判别结果: {:?}（置信度 {:.2}）", sign.sign, sign.confidence);
+    println!("   - 依据: 没有载波初相或边带先验，h 与 a 只说明两侧边带强弱，不能说明哪一侧来自上边带，结论为无法确定");
+    println!("   - 标准双边带信号 m(t)cos(2πf_d t + φ) 与 m(t)cos(-2πf_d t - φ) 是同一个实信号，只有已知载波初相或边带特征时才能判别");
     println!();
     println!("3. 对解调结果的影响:");
     println!("   - 频率偏差的符号不影响二次解调的效果");
@@ -220,25 +155,38 @@ fn main() -> Result<(), Box<dyn Error>> {
     println!("   - Q1_waveform.png: 时域波形");
     println!("========================================\n");
 
-    // 保存关键数据供后续使用（使用对称峰值法确定的频率偏差）
-    save_results_for_q2(f_d_symmetric, sample_rate)?;
+    // 保存关键数据供后续使用（使用对称轴法确定的频率偏差）
+    save_results_for_q2(&offset, &sign, sample_rate, num_samples)?;
 
     Ok(())
 }
 
 /// 保存结果供 Q2 使用
-fn save_results_for_q2(f_d: f64, sample_rate: f64) -> Result<(), Box<dyn Error>> {
+///
+/// `Q1_params.json` 是后续阶段读取的参数文件；`Q1_results.txt` 仅供人工查看
+fn save_results_for_q2(
+    offset: &FrequencyOffset,
+    sign: &SignResolution,
+    sample_rate: f64,
+    num_samples: usize,
+) -> Result<(), Box<dyn Error>> {
     use std::fs;
     use std::io::Write;
 
+    let params = PipelineParams::new(sample_rate, num_samples, *offset, DEFAULT_BANDWIDTH);
+    params.save("output/Q1_params.json")?;
+
     fs::create_dir_all("output")?;
     let mut file = fs::File::create("output/Q1_results.txt")?;
     
     writeln!(file, "Q1 分析结果")?;
     writeln!(file, "===========")?;
-    writeln!(file, "频率偏差 f_d = {:.4} Hz", f_d)?;
+    writeln!(file, "频率偏差 f_d = {:.4} Hz", offset.f_d)?;
     writeln!(file, "采样率 f_s = {:.2} Hz", sample_rate)?;
-    writeln!(file, "基带带宽 f_B = 4000 Hz")?;
+    writeln!(file, "基带带宽 f_B = {} Hz", DEFAULT_BANDWIDTH)?;
+    writeln!(file)?;
+    writeln!(file, "f̃_c 与 f_c 的大小关系:")?;
+    write!(file, "{}", sign.report)?;
     
     println!("结果已保存到 output/Q1_results.txt");
     
@@ -1,34 +1,35 @@
-mod butterworth_filter;
-mod filter_response;
-mod response_visualizer;
-
+use amdemod::{
+    butterworth_filter, filter_response, response_visualizer, stages, AnalogStability, DelayResponse, Discretization,
+    FilterDesigns, FilterFamily, FilterType, IirDesign, PipelineParams, StabilityReport, TransitionSpec,
+};
 use std::fs;
-use std::path::Path;
 
 fn main() {
     println!("=== Q2: Butterworth Filter Design ===\n");
 
     // Read parameters from Q1 results
-    let q1_results_path = "../Q1/output/Q1_results.txt";
-    let (sample_rate, f_d, f_b) = read_q1_results(q1_results_path);
+    let q1_params_path = "../Q1/output/Q1_params.json";
+    let mut params = match PipelineParams::load(q1_params_path) {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("Error reading Q1 parameters: {}", e);
+            return;
+        }
+    };
+    let (sample_rate, f_d, f_b) = (params.sample_rate, params.f_d(), params.bandwidth);
 
     println!("Parameters from Q1:");
     println!("  Sample Rate: {} Hz", sample_rate);
-    println!("  Frequency Offset (f_d): {:.4} Hz", f_d);
+    println!("  Frequency Offset (f_d): {:.4} Hz ({:?}, confidence {:.2})", f_d, params.offset.method, params.offset.confidence);
     println!("  Signal Bandwidth (f_B): {} Hz", f_b);
     println!();
 
     // Design 8th-order Butterworth filters
-    let order = 8;
-    println!("Designing 8th-order Butterworth filters...");
-
-    // High-pass filter with cutoff frequency f_d
+    let order = stages::DEFAULT_FILTER_ORDER;
+    println!("Designing {}th-order Butterworth filters...", order);
     println!("  - High-pass filter (cutoff = {:.4} Hz)", f_d);
-    let highpass = butterworth_filter::ButterworthFilter::highpass(order, f_d, sample_rate);
-
-    // Low-pass filter with cutoff frequency f_B
     println!("  - Low-pass filter (cutoff = {} Hz)", f_b);
-    let lowpass = butterworth_filter::ButterworthFilter::lowpass(order, f_b, sample_rate);
+    let FilterDesigns { highpass, lowpass } = stages::design_filters(&params, order);
 
     println!("\nHigh-pass filter coefficients:");
     println!("  b (numerator): {:?}", &highpass.b[..5.min(highpass.b.len())]);
@@ -38,8 +39,8 @@ fn main() {
     println!("  b (numerator): {:?}", &lowpass.b[..5.min(lowpass.b.len())]);
     println!("  a (denominator): {:?}", &lowpass.a[..5.min(lowpass.a.len())]);
 
-    // Calculate frequency response at the same frequency points as Q1
-    let num_points = 31265; // Same as Q1 audio samples
+    // Calculate frequency response on the DFT grid of the Q1 recording (N = its length)
+    let num_points = params.num_samples;
     println!("\nCalculating frequency responses ({} points)...", num_points);
 
     let hp_response = filter_response::FilterResponse::compute(&highpass, sample_rate, num_points);
@@ -116,56 +117,258 @@ fn main() {
         Some(10000.0),
     ).expect("Failed to plot combined magnitude");
 
+    // Overlay the other filter families at the same order and cutoff
+    let families = [
+        FilterFamily::Butterworth,
+        FilterFamily::Chebyshev1 { ripple_db: 1.0 },
+        FilterFamily::Chebyshev2 { attenuation_db: 60.0 },
+        FilterFamily::Elliptic { ripple_db: 1.0, attenuation_db: 60.0 },
+        FilterFamily::Bessel,
+    ];
+    println!("\nComparing filter families at order {}...", order);
+    for (filter_type, cutoff, name, title) in [
+        (FilterType::Highpass, f_d, "highpass", "High-pass"),
+        (FilterType::Lowpass, f_b, "lowpass", "Low-pass"),
+    ] {
+        let responses: Vec<(String, Vec<f64>)> = families
+            .iter()
+            .map(|&family| {
+                let filter = IirDesign::with_family(family, order, cutoff, sample_rate, filter_type)
+                    .expect("Failed to design filter");
+                let response = filter_response::FilterResponse::compute(&filter, sample_rate, num_points);
+                (family.to_string(), response.magnitude)
+            })
+            .collect();
+        response_visualizer::plot_overlaid_magnitude_db(
+            &hp_response.frequencies,
+            &responses,
+            &format!("{}/Q2_{}_families_db.png", output_dir, name),
+            &format!("{} Filter Families (order {}, cutoff {:.1} Hz)", title, order, cutoff),
+            Some(10000.0),
+        ).expect("Failed to plot filter families");
+    }
+
+    // Minimum orders when the filters are specified by a transition band instead of a fixed order
+    let transition = TransitionSpec { width: 200.0, ripple_db: 1.0, attenuation_db: 60.0 };
+    let mut orders = format!(
+        "=== Q2: Minimum Filter Orders ===\n\nTransition band: {} Hz, passband loss <= {} dB, stopband attenuation >= {} dB\n\n",
+        transition.width, transition.ripple_db, transition.attenuation_db
+    );
+    for family in &families {
+        let order_at = |filter_type, cutoff| match transition.order_at(family, filter_type, cutoff, sample_rate) {
+            Ok((order, _)) => order.to_string(),
+            Err(_) => "n/a".to_string(),
+        };
+        orders.push_str(&format!(
+            "{:<45} high-pass (f_d) {:>4}   low-pass (f_B) {:>4}\n",
+            family.to_string(),
+            order_at(FilterType::Highpass, f_d),
+            order_at(FilterType::Lowpass, f_b)
+        ));
+    }
+    println!("\n{}", orders.trim_end());
+    fs::write(format!("{}/Q2_minimum_orders.txt", output_dir), &orders).expect("Failed to write minimum orders");
+
+    // Exact responses on the full two-sided DFT grid f = k·f_s/N, k = 0..N-1 of the recording:
+    // the analog prototype H(j2πf) against the bilinear-transformed digital filter H(e^jω)
+    println!("\nComparing analog and digital responses on the {}-point DFT grid...", num_points);
+    let mut warping_report = format!(
+        "=== Q2: Analog vs. Digital Response on the DFT Grid ===\n\nN = {} (audio length), f_k = k * {} / N Hz, k = 0..{}\n",
+        num_points, sample_rate, num_points - 1
+    );
+    for (filter, name, title) in [(&highpass, "highpass", "High-pass"), (&lowpass, "lowpass", "Low-pass")] {
+        let analog = filter.analog().expect("Failed to build analog filter");
+        let digital = filter_response::FilterResponse::compute_dft_grid(filter, num_points);
+        let continuous = filter_response::FilterResponse::compute_analog_dft_grid(&analog, sample_rate, num_points);
+        response_visualizer::plot_analog_digital_overlay(
+            &digital.frequencies,
+            &continuous.magnitude,
+            &digital.magnitude,
+            &format!("{}/Q2_{}_analog_vs_digital.png", output_dir, name),
+            &format!("{} Filter: Analog vs. Digital ({} DFT bins)", title, num_points),
+            None,
+        ).expect("Failed to plot analog vs. digital response");
+
+        // Summarize up to f_s/2, where both describe the same physical frequencies
+        let worst = filter_response::max_warping_error(&continuous, &digital);
+        let db = filter_response::magnitude_to_db;
+        let cutoff_bin = ((filter.cutoff * num_points as f64 / sample_rate).round() as usize).min(num_points / 2);
+        warping_report.push_str(&format!("\n{} (cutoff {:.4} Hz):\n", title, filter.cutoff));
+        for (label, k) in [("nearest bin to cutoff", cutoff_bin), ("f_s/2", num_points / 2)] {
+            warping_report.push_str(&format!(
+                "  {:<22} f = {:>10.3} Hz   analog {:>9.3} dB   digital {:>9.3} dB\n",
+                label, digital.frequencies[k], db(continuous.magnitude[k]), db(digital.magnitude[k])
+            ));
+        }
+        warping_report.push_str(&format!(
+            "  max |warping error| where analog > {} dB: {:.3} dB at {:.3} Hz\n",
+            filter_response::WARPING_FLOOR_DB, worst.error_db, worst.frequency
+        ));
+    }
+    println!("\n{}", warping_report.trim_end());
+    fs::write(format!("{}/Q2_warping_error.txt", output_dir), &warping_report).expect("Failed to write warping error summary");
+
+    // Unwrapped phase, group delay and phase delay from the poles and zeros, with the delay spread
+    // over the band each filter passes the speech through: [f_d, f_d + f_B] for the high-pass, [0, f_B] for the low-pass
+    println!("\nAnalyzing phase and delay...");
+    let mut delay_report = String::from("=== Q2: Group Delay and Phase Delay ===\n");
+    for (filter, response, name, title, band) in [
+        (&highpass, &hp_response, "highpass", "High-pass", (f_d, f_d + f_b)),
+        (&lowpass, &lp_response, "lowpass", "Low-pass", (0.0, f_b)),
+    ] {
+        let delay = DelayResponse::digital(filter, num_points).expect("Failed to compute delays");
+        response_visualizer::plot_delay_response(
+            response,
+            &delay,
+            &format!("{}/Q2_{}_delay.png", output_dir, name),
+            &format!("{} Filter: Magnitude, Unwrapped Phase and Delay", title),
+            Some(10000.0),
+            Some(band),
+        ).expect("Failed to plot delay response");
+
+        let summary = delay.passband(band.0, band.1).expect("Failed to summarize passband delay");
+        let ms = |seconds: f64| seconds * 1000.0;
+        delay_report.push_str(&format!(
+            "\n{} (cutoff {:.4} Hz), band {:.1} - {:.1} Hz:\n",
+            title, filter.cutoff, summary.low, summary.high
+        ));
+        delay_report.push_str(&format!(
+            "  group delay   min {:>8.4} ms   max {:>8.4} ms   mean {:>8.4} ms\n",
+            ms(summary.min_group_delay), ms(summary.max_group_delay), ms(summary.mean_group_delay)
+        ));
+        delay_report.push_str(&format!(
+            "  group delay variation {:.4} ms ({:.1} samples)\n",
+            ms(summary.group_delay_variation), summary.group_delay_variation * sample_rate
+        ));
+        delay_report.push_str(&format!(
+            "  phase delay   min {:>8.4} ms   max {:>8.4} ms\n",
+            ms(summary.min_phase_delay), ms(summary.max_phase_delay)
+        ));
+    }
+    println!("\n{}", delay_report.trim_end());
+    fs::write(format!("{}/Q2_delay_summary.txt", output_dir), &delay_report).expect("Failed to write delay summary");
+
+    // Pole-zero diagrams (z-plane, and s-plane for the analog prototype) and the numerical stability
+    // of the direct-form b/a polynomials compared with the second-order sections
+    println!("\nChecking pole locations and numerical stability...");
+    let mut stability_report = String::from("=== Q2: Poles, Zeros and Numerical Stability ===\n");
+    for (filter, name, title) in [(&highpass, "highpass", "High-pass"), (&lowpass, "lowpass", "Low-pass")] {
+        let zpk = filter.zpk().expect("Failed to get poles and zeros");
+        let analog = filter.analog().expect("Failed to build analog filter");
+        response_visualizer::plot_pole_zero_z(
+            &zpk,
+            &format!("{}/Q2_{}_pole_zero.png", output_dir, name),
+            &format!("{} Filter Poles and Zeros", title),
+        ).expect("Failed to plot z-plane poles and zeros");
+        response_visualizer::plot_pole_zero_s(
+            analog.zpk(),
+            &format!("{}/Q2_{}_pole_zero_s.png", output_dir, name),
+            &format!("{} Analog Prototype", title),
+        ).expect("Failed to plot s-plane poles and zeros");
+
+        let report = StabilityReport::digital(filter).expect("Failed to analyze stability");
+        let margin = AnalogStability::of(&analog);
+        stability_report.push_str(&format!("\n{} (cutoff {:.4} Hz):\n", title, filter.cutoff));
+        stability_report.push_str(&format!(
+            "  max pole radius {:.9}   stability margin {:.3e}   {}\n",
+            report.max_pole_radius,
+            report.stability_margin,
+            if report.stable { "stable" } else { "UNSTABLE" }
+        ));
+        stability_report.push_str(&format!("  {:<28} {:>14} {:>14}\n", "", "b/a", "SOS"));
+        stability_report.push_str(&format!(
+            "  {:<28} {:>14.3e} {:>14.3e}\n",
+            "pole sensitivity", report.tf_sensitivity, report.sos_sensitivity
+        ));
+        stability_report.push_str(&format!(
+            "  {:<28} {:>14.3e} {:>14.3e}\n",
+            "pole condition number", report.tf_condition_number, report.sos_condition_number
+        ));
+        stability_report.push_str(&format!(
+            "  {:<28} {:>14.1} {:>14.1}\n",
+            "coefficient bits needed", report.tf_required_bits, report.sos_required_bits
+        ));
+        stability_report.push_str(&format!("  poles recovered from b/a: max error {:.3e}\n", report.tf_pole_error));
+        stability_report.push_str(&format!(
+            "  analog prototype: max Re(p) {:.3} rad/s, min damping ratio {:.4}\n",
+            margin.max_real_part, margin.min_damping_ratio
+        ));
+        for warning in &report.warnings {
+            println!("Warning: {}", warning);
+            stability_report.push_str(&format!("  warning: {}\n", warning));
+        }
+    }
+
+    // The high-pass poles approach z = 1 as f_d decreases, and the b/a form degrades much faster than the SOS form
+    stability_report.push_str(&format!(
+        "\n{}th-order high-pass vs. cutoff:\n  {:>10} {:>14} {:>14} {:>14} {:>10} {:>10}\n",
+        order, "cutoff", "max |p|", "cond (b/a)", "cond (SOS)", "bits b/a", "bits SOS"
+    ));
+    for cutoff in [3000.0, 1000.0, 300.0, 100.0, 30.0] {
+        let filter = IirDesign::highpass(order, cutoff, sample_rate);
+        let report = StabilityReport::digital(&filter).expect("Failed to analyze stability");
+        stability_report.push_str(&format!(
+            "  {:>8.0} Hz {:>14.9} {:>14.3e} {:>14.3e} {:>10.1} {:>10.1}\n",
+            cutoff,
+            report.max_pole_radius,
+            report.tf_condition_number,
+            report.sos_condition_number,
+            report.tf_required_bits,
+            report.sos_required_bits
+        ));
+    }
+    println!("\n{}", stability_report.trim_end());
+    fs::write(format!("{}/Q2_stability.txt", output_dir), &stability_report).expect("Failed to write stability report");
+
+    // Continuous-time H(s) behind each digital filter, simulated with lsim on a unit step
+    let methods = [
+        Discretization::Zoh,
+        Discretization::Foh,
+        Discretization::Tustin,
+        Discretization::Matched,
+        Discretization::Impulse,
+    ];
+    let step = vec![1.0; 2048];
+    let mut analog_report = String::from("=== Q2: Continuous-Time Transfer Functions ===\n");
+    for (filter, title) in [(&highpass, "High-pass"), (&lowpass, "Low-pass")] {
+        let analog = filter.analog().expect("Failed to build analog filter");
+        analog_report.push_str(&format!("\n{} (cutoff {:.4} Hz, unwarped):\n{}\n", title, filter.cutoff, analog));
+        // ZOH is exact for a step input, so it serves as the continuous-time reference
+        analog_report.push_str("\nlsim step response, max |difference| over 2048 samples:\n");
+        analog_report.push_str(&format!("  {:<18} {:>12} {:>16}\n", "method", "vs. exact", "vs. digital"));
+        let exact = analog.lsim(&step, sample_rate, Discretization::Zoh).expect("Failed to simulate analog filter");
+        let digital = filter.to_iir_filter().expect("Failed to build digital filter").process_block(&step);
+        let max_difference = |x: &[f64], y: &[f64]| x.iter().zip(y).fold(0.0f64, |m, (a, b)| m.max((a - b).abs()));
+        for method in methods {
+            let line = match analog.lsim(&step, sample_rate, method) {
+                Ok(y) => format!("{:>12.6} {:>16.6}", max_difference(&y, &exact), max_difference(&y, &digital)),
+                Err(_) => format!("{:>12} {:>16}", "n/a", "n/a"),
+            };
+            analog_report.push_str(&format!("  {:<18} {}\n", method.to_string(), line));
+        }
+    }
+    println!("\n{}", analog_report.trim_end());
+    fs::write(format!("{}/Q2_analog_tf.txt", output_dir), &analog_report).expect("Failed to write analog transfer functions");
+
     // Save filter coefficients
     save_filter_coefficients(&highpass, &lowpass, &format!("{}/Q2_filter_coefficients.txt", output_dir));
 
     // Save frequency response data
     save_frequency_response(&hp_response, &lp_response, &format!("{}/Q2_frequency_response.txt", output_dir));
 
-    println!("\nAll results saved to '{}/' directory", output_dir);
-    println!("\nQ2 completed successfully!");
-}
-
-fn read_q1_results(path: &str) -> (f64, f64, f64) {
-    let content = fs::read_to_string(path)
-        .expect("Failed to read Q1 results file");
-
-    let mut sample_rate = 22050.0;
-    let mut f_d = 3225.0;
-    let mut f_b = 4000.0;
-
-    for line in content.lines() {
-        // Parse: "频率偏差 f_d = 3225.1032 Hz"
-        if line.contains("频率偏差") && line.contains("f_d") {
-            if let Some(value_str) = line.split('=').nth(1) {
-                if let Some(num_str) = value_str.trim().split_whitespace().next() {
-                    f_d = num_str.parse().unwrap_or(3225.0);
-                }
-            }
-        }
-        // Parse: "采样率 f_s = 22050.00 Hz"
-        else if line.contains("采样率") && line.contains("f_s") {
-            if let Some(value_str) = line.split('=').nth(1) {
-                if let Some(num_str) = value_str.trim().split_whitespace().next() {
-                    sample_rate = num_str.parse().unwrap_or(22050.0);
-                }
-            }
-        }
-        // Parse: "基带带宽 f_B = 4000 Hz"
-        else if line.contains("基带带宽") && line.contains("f_B") {
-            if let Some(value_str) = line.split('=').nth(1) {
-                if let Some(num_str) = value_str.trim().split_whitespace().next() {
-                    f_b = num_str.parse().unwrap_or(4000.0);
-                }
-            }
-        }
+    // Save parameters (with full filter designs) for Q3
+    params.filters = Some(FilterDesigns { highpass, lowpass });
+    if let Err(e) = params.save(format!("{}/Q2_params.json", output_dir)) {
+        eprintln!("Error saving Q2 parameters: {}", e);
+        return;
     }
 
-    (sample_rate, f_d, f_b)
+    println!("\nAll results saved to '{}/' directory", output_dir);
+    println!("\nQ2 completed successfully!");
 }
 
-fn save_filter_coefficients(highpass: &butterworth_filter::ButterworthFilter, 
-                            lowpass: &butterworth_filter::ButterworthFilter,
+fn save_filter_coefficients(highpass: &butterworth_filter::IirDesign, 
+                            lowpass: &butterworth_filter::IirDesign,
                             path: &str) {
     let mut content = String::new();
     content.push_str("=== Q2: Filter Coefficients ===\n\n");
@@ -206,13 +409,13 @@ fn save_frequency_response(hp_response: &filter_response::FilterResponse,
     content.push_str("High-pass Filter:\n");
     content.push_str(&format!("Number of frequency points: {}\n", hp_response.frequencies.len()));
     content.push_str(&format!("Frequency range: 0 - {:.2} Hz\n", hp_response.frequencies.last().unwrap_or(&0.0)));
-    content.push_str(&format!("Maximum magnitude: {:.6}\n", hp_response.magnitude.iter().cloned().fold(0./0., f64::max)));
+    content.push_str(&format!("Maximum magnitude: {:.6}\n", hp_response.magnitude.iter().cloned().fold(f64::NAN, f64::max)));
     content.push_str(&format!("Minimum magnitude: {:.6}\n", hp_response.magnitude.iter().cloned().fold(f64::INFINITY, f64::min)));
 
     content.push_str("\nLow-pass Filter:\n");
     content.push_str(&format!("Number of frequency points: {}\n", lp_response.frequencies.len()));
     content.push_str(&format!("Frequency range: 0 - {:.2} Hz\n", lp_response.frequencies.last().unwrap_or(&0.0)));
-    content.push_str(&format!("Maximum magnitude: {:.6}\n", lp_response.magnitude.iter().cloned().fold(0./0., f64::max)));
+    content.push_str(&format!("Maximum magnitude: {:.6}\n", lp_response.magnitude.iter().cloned().fold(f64::NAN, f64::max)));
     content.push_str(&format!("Minimum magnitude: {:.6}\n", lp_response.magnitude.iter().cloned().fold(f64::INFINITY, f64::min)));
 
     fs::write(path, content).expect("Failed to write frequency response data");
@@ -1,10 +1,8 @@
 // 测试滤波器的实际截止频率
 use std::f64::consts::PI;
 
-mod butterworth_filter;
-mod filter_response;
-
-use butterworth_filter::ButterworthFilter;
+use amdemod::filter_response::frequency_response_at_omega;
+use amdemod::IirDesign;
 
 fn main() {
     let sample_rate = 22050.0;
@@ -15,7 +13,7 @@ fn main() {
     
     // Test high-pass filter
     println!("High-pass filter (designed cutoff = {:.4} Hz)", designed_cutoff_hp);
-    let hp_filter = ButterworthFilter::highpass(8, designed_cutoff_hp, sample_rate);
+    let hp_filter = IirDesign::highpass(8, designed_cutoff_hp, sample_rate);
     let actual_cutoff_hp = find_3db_cutoff(&hp_filter, sample_rate, true);
     println!("  Actual -3dB cutoff: {:.4} Hz", actual_cutoff_hp);
     println!("  Error: {:.4} Hz ({:.2}%)\n", 
@@ -24,7 +22,7 @@ fn main() {
     
     // Test low-pass filter
     println!("Low-pass filter (designed cutoff = {:.4} Hz)", designed_cutoff_lp);
-    let lp_filter = ButterworthFilter::lowpass(8, designed_cutoff_lp, sample_rate);
+    let lp_filter = IirDesign::lowpass(8, designed_cutoff_lp, sample_rate);
     let actual_cutoff_lp = find_3db_cutoff(&lp_filter, sample_rate, false);
     println!("  Actual -3dB cutoff: {:.4} Hz", actual_cutoff_lp);
     println!("  Error: {:.4} Hz ({:.2}%)\n", 
@@ -32,7 +30,7 @@ fn main() {
         (actual_cutoff_lp - designed_cutoff_lp) / designed_cutoff_lp * 100.0);
 }
 
-fn find_3db_cutoff(filter: &ButterworthFilter, sample_rate: f64, is_highpass: bool) -> f64 {
+fn find_3db_cutoff(filter: &IirDesign, sample_rate: f64, _is_highpass: bool) -> f64 {
     // 寻找-3dB点 (幅度为1/sqrt(2) ≈ 0.7071)
     let target_magnitude = 1.0 / 2.0_f64.sqrt();
     
@@ -77,28 +75,3 @@ fn find_3db_cutoff(filter: &ButterworthFilter, sample_rate: f64, is_highpass: bo
     
     best_freq
 }
-
-fn frequency_response_at_omega(filter: &ButterworthFilter, omega: f64) -> num_complex::Complex<f64> {
-    use num_complex::Complex;
-    
-    let mut numerator = Complex::new(0.0, 0.0);
-    let mut denominator = Complex::new(0.0, 0.0);
-    
-    for (k, &b_k) in filter.b.iter().enumerate() {
-        let exp_term = Complex::new(
-            (-(k as f64) * omega).cos(),
-            (-(k as f64) * omega).sin(),
-        );
-        numerator += b_k * exp_term;
-    }
-    
-    for (k, &a_k) in filter.a.iter().enumerate() {
-        let exp_term = Complex::new(
-            (-(k as f64) * omega).cos(),
-            (-(k as f64) * omega).sin(),
-        );
-        denominator += a_k * exp_term;
-    }
-    
-    numerator / denominator
-}
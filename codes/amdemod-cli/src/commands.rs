@@ -0,0 +1,1251 @@
+// 各子命令的实现
+// 每个阶段把结果写入输出目录：参数文件、音频、图像和一份报告，文件名以阶段名为前缀
+
+use crate::report::write_report;
+use crate::{
+    AnalyzeArgs, BenchArgs, CompareArgs, DemodPath, OutputArgs, ParamArgs, PsdArgs, RealizationArg, RecoverArgs,
+    RunAllArgs, SpectrogramArgs, StageArgs, StateSpaceArgs, StreamArgs, TrackArgs, WindowsArgs, ZoomArgs,
+};
+use amdemod::carrier_recovery;
+use amdemod::comparator::{self, ComparisonResult};
+use amdemod::offset_tracker::{self, OffsetTrack};
+use amdemod::psd::{self, PowerSpectralDensity};
+use amdemod::stages::{self, OffsetAnalysis, SpectrumWindow, DEFAULT_BANDWIDTH};
+use amdemod::stft::{self, StftConfig};
+use amdemod::streaming::{StreamingDemodulator, StreamingFrequencyDemod, StreamingTimeDemod};
+use amdemod::sub_bin::{self, SubBinMethod};
+use amdemod::window::{Window, WindowGains};
+use amdemod::{
+    filter_response, response_visualizer, AnalogStability, AudioData, DelayResponse, FftResult, FrequencyEstimator,
+    FrequencyOffset, IirDesign, LoopConfig, Pipeline, PipelineConfig, PipelineParams, PsdMethod, SignPrior,
+    SpectrumVisualizer, StabilityReport, StateSpace, TrackerConfig, WavBlockReader, WavBlockWriter,
+};
+use clap::ValueEnum;
+use serde_json::json;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// 频谱图显示的最高频率 (Hz)
+const PLOT_MAX_FREQ: f64 = 10000.0;
+
+/// analyze 细化频谱的点数
+const ZOOM_POINTS: usize = 2001;
+
+/// 读入的单声道录音
+struct Recording {
+    path: PathBuf,
+    samples: Vec<f64>,
+    sample_rate: f64,
+}
+
+impl Recording {
+    fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let audio = AudioData::from_wav(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            samples: audio.to_mono(),
+            sample_rate: audio.sample_rate as f64,
+        })
+    }
+}
+
+pub fn analyze(args: &AnalyzeArgs) -> Result<(), Box<dyn Error>> {
+    let recording = Recording::load(&args.input)?;
+    let params = analyze_stage(
+        &recording,
+        args.f_b,
+        args.sign.prior(),
+        &args.spectrum.spectrum_window(),
+        args.psd_method(),
+        &args.output,
+    )?;
+    let f_d = params.f_d();
+    let zoom = FftResult::compute_zoom(
+        &recording.samples,
+        recording.sample_rate,
+        f_d - args.zoom_span,
+        f_d + args.zoom_span,
+        ZOOM_POINTS,
+    )?;
+    let fft = FftResult::compute(&recording.samples, recording.sample_rate);
+    SpectrumVisualizer::plot_zoom_spectrum(
+        &zoom,
+        Some(&fft),
+        &[(f_d, "f_d")],
+        args.output.output_dir.join("analyze_zoom.png"),
+        "Zoomed Spectrum around f_d",
+    )?;
+    Ok(())
+}
+
+pub fn zoom(args: &ZoomArgs) -> Result<(), Box<dyn Error>> {
+    let recording = Recording::load(&args.input)?;
+    println!("[zoom] {:.2}–{:.2} Hz", args.from, args.to);
+    let zoom = FftResult::compute_zoom(&recording.samples, recording.sample_rate, args.from, args.to, args.points)?;
+    let fft = FftResult::compute(&recording.samples, recording.sample_rate);
+
+    let output = &args.output;
+    fs::create_dir_all(&output.output_dir)?;
+    let markers: Vec<(f64, &str)> = args.mark.iter().map(|&f| (f, "mark")).collect();
+    SpectrumVisualizer::plot_zoom_spectrum(
+        &zoom,
+        Some(&fft),
+        &markers,
+        output.output_dir.join("zoom_spectrum.png"),
+        &format!("Zoomed Spectrum {:.2}-{:.2} Hz", args.from, args.to),
+    )?;
+
+    let (peak_frequency, peak_magnitude) = zoom.peak();
+    let (bin_frequency, bin_magnitude) = fft
+        .get_single_sided_pairs()
+        .into_iter()
+        .filter(|&(f, _)| f >= args.from && f <= args.to)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((f64::NAN, 0.0));
+    let report = json!({
+        "input": recording.path.display().to_string(),
+        "sample_rate": recording.sample_rate,
+        "from": args.from,
+        "to": args.to,
+        "points": args.points,
+        "resolution": zoom.resolution(),
+        "dft_resolution": recording.sample_rate / recording.samples.len() as f64,
+        "peak": {
+            "frequency": peak_frequency,
+            "magnitude": peak_magnitude,
+        },
+        "dft_peak": {
+            "frequency": bin_frequency,
+            "magnitude": bin_magnitude,
+        },
+    });
+    write_report(output, "zoom", "Zoomed Spectrum", &report)?;
+    Ok(())
+}
+
+pub fn psd(args: &PsdArgs) -> Result<(), Box<dyn Error>> {
+    let recording = Recording::load(&args.input)?;
+    let method = args.options.method(args.method);
+    println!("[psd] {}", method);
+    let psd = psd::compute_psd(&recording.samples, recording.sample_rate, &method)?;
+    let analysis = stages::estimate_offset_psd(&psd);
+
+    let bands = [
+        (0.0, 1000.0),
+        (1000.0, 4000.0),
+        (4000.0, 8000.0),
+        (8000.0, recording.sample_rate / 2.0),
+    ];
+    let distribution = FrequencyEstimator::compute_energy_distribution(&psd.amplitude(), &psd.frequencies, &bands);
+
+    let output = &args.output;
+    fs::create_dir_all(&output.output_dir)?;
+    SpectrumVisualizer::plot_psd(
+        &psd,
+        Some(args.confidence),
+        output.output_dir.join("psd.png"),
+        &format!("Power Spectral Density ({})", method),
+        Some(args.max_freq),
+    )?;
+    write_psd_csv(&output.output_dir.join("psd.csv"), &psd, args.confidence)?;
+
+    let report = json!({
+        "input": recording.path.display().to_string(),
+        "sample_rate": recording.sample_rate,
+        "method": method,
+        "segments": psd.segments,
+        "dof": psd.dof,
+        "enbw": psd.enbw,
+        "confidence": args.confidence,
+        "total_power": psd.total_power(),
+        "f_d": analysis.offset.f_d,
+        "f_d_method": analysis.offset.method,
+        "f_d_confidence": analysis.offset.confidence,
+        "peak_frequency": analysis.peak_frequency,
+        "energy_distribution": distribution
+            .iter()
+            .zip(&bands)
+            .map(|((band, percent), &(low, high))| json!({
+                "band": band,
+                "percent": percent,
+                "power": psd.band_power(low, high),
+            }))
+            .collect::<Vec<_>>(),
+    });
+    write_report(output, "psd", "Power Spectral Density", &report)?;
+    Ok(())
+}
+
+pub fn windows(args: &WindowsArgs) -> Result<(), Box<dyn Error>> {
+    let (symmetry, length) = (args.symmetry(), args.length);
+    if length < 2 {
+        return Err(format!("--length must be at least 2, got {}", length).into());
+    }
+    println!("[windows] N = {}, {:?}", length, symmetry);
+    let mut shapes = Vec::new();
+    let mut rows = Vec::new();
+    for window in args.windows() {
+        window.validate_generated(length, symmetry)?;
+        let coefficients = window.generate(length, symmetry);
+        let gains = WindowGains::from_coefficients(&coefficients);
+        println!(
+            "  {:<18} CG {:.4}  ENBW {:.4} bins ({:.3} Hz)  scalloping {:.3} dB",
+            window.to_string(),
+            gains.coherent_gain,
+            gains.enbw_bins,
+            gains.enbw(args.sample_rate),
+            gains.scalloping_loss_db
+        );
+        rows.push(json!({
+            "window": window.to_string(),
+            "coherent_gain": gains.coherent_gain,
+            "power_gain": gains.power_gain,
+            "enbw_bins": gains.enbw_bins,
+            "enbw_hz": gains.enbw(args.sample_rate),
+            "scalloping_loss_db": gains.scalloping_loss_db,
+            "amplitude_correction": gains.amplitude_correction,
+            "energy_correction": gains.energy_correction,
+        }));
+        shapes.push((window, coefficients));
+    }
+
+    let output = &args.output;
+    fs::create_dir_all(&output.output_dir)?;
+    SpectrumVisualizer::plot_windows(
+        &shapes,
+        output.output_dir.join("windows.png"),
+        &format!("Window Functions (N = {}, {:?})", length, symmetry),
+    )?;
+    let report = json!({
+        "length": length,
+        "symmetry": symmetry,
+        "sample_rate": args.sample_rate,
+        "windows": rows,
+    });
+    write_report(output, "windows", "Window Functions", &report)?;
+    Ok(())
+}
+
+pub fn design_filters(args: &StageArgs) -> Result<(), Box<dyn Error>> {
+    let recording = args.input.as_deref().map(Recording::load).transpose()?;
+    let params = resolve_params(&args.params, recording.as_ref())?;
+    let filters = params.require_filters()?;
+    println!(
+        "[design-filters] {}: high-pass order {}, low-pass order {}",
+        filters.highpass.family, filters.highpass.order, filters.lowpass.order
+    );
+    write_design(&params, &args.output)
+}
+
+pub fn state_space(args: &StateSpaceArgs) -> Result<(), Box<dyn Error>> {
+    let recording = args.stage.input.as_deref().map(Recording::load).transpose()?;
+    let params = resolve_params(&args.stage.params, recording.as_ref())?;
+    state_space_stage(&params, args.form, args.analog, &args.stage.output)
+}
+
+pub fn demod_time(args: &StageArgs) -> Result<(), Box<dyn Error>> {
+    let recording = Recording::load(require_input(args)?)?;
+    let params = resolve_params(&args.params, Some(&recording))?;
+    demod_time_stage(&recording, &params, &args.output)?;
+    Ok(())
+}
+
+pub fn demod_freq(args: &StageArgs) -> Result<(), Box<dyn Error>> {
+    let recording = Recording::load(require_input(args)?)?;
+    let params = resolve_params(&args.params, Some(&recording))?;
+    demod_freq_stage(&recording, &params, &args.output)?;
+    Ok(())
+}
+
+pub fn stream(args: &StreamArgs) -> Result<(), Box<dyn Error>> {
+    if args.block_size == 0 {
+        return Err("--block-size must be positive".into());
+    }
+    let mut reader = WavBlockReader::open(&args.input, args.block_size)
+        .map_err(|e| format!("Failed to read {}: {}", args.input.display(), e))?;
+    let params = stream_params(&args.params, &reader)?;
+    stream_stage(args, &mut reader, &params)
+}
+
+pub fn track(args: &TrackArgs) -> Result<(), Box<dyn Error>> {
+    let recording = Recording::load(require_input(&args.stage)?)?;
+    let params = resolve_params(&args.stage.params, Some(&recording))?;
+    track_stage(&recording, &params, &args.tracker_config(), &args.stage.output)
+}
+
+pub fn recover(args: &RecoverArgs) -> Result<(), Box<dyn Error>> {
+    let recording = Recording::load(require_input(&args.stage)?)?;
+    let params = resolve_params(&args.stage.params, Some(&recording))?;
+    let config = args.loop_config(params.f_d(), params.bandwidth);
+    recover_stage(&recording, &params, &config, &args.stage.output)
+}
+
+pub fn spectrogram(args: &SpectrogramArgs) -> Result<(), Box<dyn Error>> {
+    let recording = Recording::load(require_input(&args.stage)?)?;
+    let params = resolve_params(&args.stage.params, Some(&recording))?;
+    spectrogram_stage(&recording, &params, &args.stft_config(), args.max_freq, args.dynamic_range, &args.stage.output)
+}
+
+pub fn compare(args: &CompareArgs) -> Result<(), Box<dyn Error>> {
+    let input = Recording::load(&args.input)?;
+    let reference = Recording::load(&args.reference)?;
+    compare_stage(&input.samples, &reference.samples, &args.output)
+}
+
+pub fn run_all(args: &RunAllArgs) -> Result<(), Box<dyn Error>> {
+    let stage = &args.stage;
+    let output = &stage.output;
+    let recording = Recording::load(require_input(stage)?)?;
+
+    let cache_dir = (!args.no_cache)
+        .then(|| args.cache_dir.clone().unwrap_or_else(|| output.output_dir.join("cache")));
+    let spectrum = args.spectrum.spectrum_window();
+    let mut config = PipelineConfig {
+        spectrum,
+        order: stage.params.order,
+        family: stage.params.family(),
+        transition: stage.params.transition(),
+        cache_dir: cache_dir.clone(),
+        ..PipelineConfig::default()
+    };
+    if let Some(path) = &stage.params.params {
+        let params = PipelineParams::load(path)?;
+        check_sample_rate(&params, &recording)?;
+        config.offset = Some(params.offset);
+        config.bandwidth = params.bandwidth;
+    }
+    if let Some(f_d) = stage.params.f_d {
+        config.offset = Some(FrequencyOffset::manual(f_d));
+    }
+    if let Some(f_b) = stage.params.f_b {
+        config.bandwidth = f_b;
+    }
+
+    let result = Pipeline::new(config).run(&recording.samples, recording.sample_rate)?;
+
+    if let Some(analysis) = &result.analysis {
+        let fft = spectrum.compute(&recording.samples, recording.sample_rate)?;
+        let mut params = result.params.clone();
+        params.filters = None;
+        let label = spectrum_label(&spectrum, None);
+        write_analysis(&recording, &fft, &label, analysis, &params, args.sign.prior(), output)?;
+    }
+    write_design(&result.params, output)?;
+    save_demodulated(output, "demod_time", "Time-Domain Demodulation", &result.params, &result.time_domain)?;
+    save_demodulated(output, "demod_freq", "Frequency-Domain Demodulation", &result.params, &result.frequency_domain)?;
+    write_comparison(&result.frequency_domain, &result.time_domain, &result.comparison, output)?;
+
+    let stages: serde_json::Map<_, _> = result
+        .stages
+        .iter()
+        .map(|run| {
+            let status = if run.cached { "cached" } else { "computed" };
+            (run.stage.to_string(), json!(format!("{} ({:016x})", status, run.key)))
+        })
+        .collect();
+    let report = json!({
+        "input": recording.path.display().to_string(),
+        "cache_dir": cache_dir.map_or("disabled".to_string(), |dir| dir.display().to_string()),
+        "stages": stages,
+    });
+    write_report(output, "pipeline", "Pipeline Run", &report)?;
+    Ok(())
+}
+
+pub fn bench_estimators(args: &BenchArgs) -> Result<(), Box<dyn Error>> {
+    let config = args.config();
+    if config.length < 4 || config.trials == 0 || config.snr_db.is_empty() {
+        return Err("--length must be at least 4, --trials and --snr must not be empty".into());
+    }
+    println!(
+        "[bench-estimators] N = {}, {} trials x {} SNR values",
+        config.length,
+        config.trials,
+        config.snr_db.len()
+    );
+    let rows = sub_bin::benchmark(&SubBinMethod::all(), &config);
+
+    let output = &args.output;
+    fs::create_dir_all(&output.output_dir)?;
+    SpectrumVisualizer::plot_estimator_benchmark(
+        &rows,
+        output.output_dir.join("bench_estimators.png"),
+        "Sub-bin Frequency Estimators: RMS Error vs SNR",
+    )?;
+
+    let bin_width = config.sample_rate / config.length as f64;
+    let results: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            json!({
+                "method": row.method.to_string(),
+                "snr_db": row.snr_db,
+                "bias_hz": row.bias,
+                "rms_hz": row.rms,
+                "rms_bins": row.rms / bin_width,
+                "crb_hz": row.crb,
+            })
+        })
+        .collect();
+    let report = json!({
+        "sample_rate": config.sample_rate,
+        "length": config.length,
+        "bin_width": bin_width,
+        "frequency": config.center_frequency,
+        "trials": config.trials,
+        "seed": config.seed,
+        "results": results,
+    });
+    write_report(output, "bench_estimators", "Sub-bin Frequency Estimator Benchmark", &report)?;
+    Ok(())
+}
+
+fn require_input(args: &StageArgs) -> Result<&Path, Box<dyn Error>> {
+    args.input
+        .as_deref()
+        .ok_or_else(|| "--input is required for this command".into())
+}
+
+/// 读取参数文件（或由录音估计），再应用命令行覆盖
+fn resolve_params(
+    args: &ParamArgs,
+    recording: Option<&Recording>,
+) -> Result<PipelineParams, Box<dyn Error>> {
+    let params = match (&args.params, recording) {
+        (Some(path), _) => PipelineParams::load(path)?,
+        (None, Some(recording)) => {
+            let fft = FftResult::compute(&recording.samples, recording.sample_rate);
+            let offset = stages::estimate_offset(&fft).offset;
+            PipelineParams::new(
+                recording.sample_rate,
+                recording.samples.len(),
+                offset,
+                args.f_b.unwrap_or(DEFAULT_BANDWIDTH),
+            )
+        }
+        (None, None) => return Err("either --params or --input is required".into()),
+    };
+    apply_overrides(params, args, recording)
+}
+
+/// stream 的参数：不读入整段录音就无法估计 f_d，因此须由参数文件或 --f-d 给出
+fn stream_params(args: &ParamArgs, reader: &WavBlockReader) -> Result<PipelineParams, Box<dyn Error>> {
+    let sample_rate = reader.sample_rate() as f64;
+    let params = match (&args.params, args.f_d) {
+        (Some(path), _) => PipelineParams::load(path)?,
+        (None, Some(f_d)) => PipelineParams::new(
+            sample_rate,
+            reader.num_samples(),
+            FrequencyOffset::manual(f_d),
+            args.f_b.unwrap_or(DEFAULT_BANDWIDTH),
+        ),
+        (None, None) => {
+            return Err("stream needs --params or --f-d: f_d cannot be estimated without reading the whole recording".into())
+        }
+    };
+    if (params.sample_rate - sample_rate).abs() > 1e-6 {
+        return Err(format!(
+            "parameter sample_rate = {} Hz does not match the input ({} Hz)",
+            params.sample_rate, sample_rate
+        )
+        .into());
+    }
+    let mut params = apply_overrides(params, args, None)?;
+    params.num_samples = reader.num_samples();
+    Ok(params)
+}
+
+/// 参数文件必须与输入录音的采样率一致
+fn check_sample_rate(params: &PipelineParams, recording: &Recording) -> Result<(), Box<dyn Error>> {
+    if (params.sample_rate - recording.sample_rate).abs() > 1e-6 {
+        return Err(format!(
+            "parameter sample_rate = {} Hz does not match {} ({} Hz)",
+            params.sample_rate,
+            recording.path.display(),
+            recording.sample_rate
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// 应用 --f-d / --f-b / --order / --family / --transition，参数变化或缺少滤波器时重新设计滤波器
+fn apply_overrides(
+    mut params: PipelineParams,
+    args: &ParamArgs,
+    recording: Option<&Recording>,
+) -> Result<PipelineParams, Box<dyn Error>> {
+    if let Some(recording) = recording {
+        check_sample_rate(&params, recording)?;
+        params.num_samples = recording.samples.len();
+    }
+
+    let family = args.family();
+    let transition = args.transition();
+    // 给出过渡带指标时总是重新设计，阶数由指标决定
+    let mut redesign = transition.is_some()
+        || params
+            .filters
+            .as_ref()
+            .is_none_or(|filters| filters.highpass.order != args.order || filters.highpass.family != family);
+    if let Some(f_d) = args.f_d {
+        if f_d != params.f_d() {
+            params.offset = FrequencyOffset::manual(f_d);
+            redesign = true;
+        }
+    }
+    if let Some(f_b) = args.f_b {
+        if f_b != params.bandwidth {
+            params.bandwidth = f_b;
+            redesign = true;
+        }
+    }
+    if redesign {
+        // 先去掉旧滤波器，保证设计前的参数检查针对新的 f_d / f_B
+        params.filters = None;
+        params.validate()?;
+        params.filters = Some(match &transition {
+            Some(transition) => stages::design_filters_for_transition(&params, family, transition)?,
+            None => stages::design_filters_with(&params, args.order, family)?,
+        });
+    }
+    params.validate()?;
+    Ok(params)
+}
+
+/// analyze：估计 f_d，写出频谱图、参数文件和报告
+fn analyze_stage(
+    recording: &Recording,
+    f_b: Option<f64>,
+    prior: SignPrior,
+    spectrum: &SpectrumWindow,
+    psd_method: Option<PsdMethod>,
+    output: &OutputArgs,
+) -> Result<PipelineParams, Box<dyn Error>> {
+    println!("[analyze] {}", recording.path.display());
+
+    let fft = spectrum.compute(&recording.samples, recording.sample_rate)?;
+    let psd = psd_method
+        .map(|method| psd::compute_psd(&recording.samples, recording.sample_rate, &method))
+        .transpose()?;
+    let analysis = match &psd {
+        Some(psd) => stages::estimate_offset_psd(psd),
+        None => stages::estimate_offset(&fft),
+    };
+    let params = PipelineParams::new(
+        recording.sample_rate,
+        recording.samples.len(),
+        analysis.offset,
+        f_b.unwrap_or(DEFAULT_BANDWIDTH),
+    );
+    write_analysis(recording, &fft, &spectrum_label(spectrum, psd.as_ref()), &analysis, &params, prior, output)?;
+    Ok(params)
+}
+
+/// f_d 估计所用频谱的说明：功率谱密度的方法，或周期图及其窗（矩形窗时省略）
+fn spectrum_label(spectrum: &SpectrumWindow, psd: Option<&PowerSpectralDensity>) -> String {
+    match psd {
+        Some(psd) => psd.method.to_string(),
+        None if spectrum.window == Window::Rectangular => "periodogram".to_string(),
+        None => format!("periodogram ({} window, {:?} correction)", spectrum.window, spectrum.correction),
+    }
+}
+
+/// 写出输入频谱图、参数文件和 analyze 报告；spectrum 描述 f_d 估计所用的频谱（见 [`spectrum_label`]）
+fn write_analysis(
+    recording: &Recording,
+    fft: &FftResult,
+    spectrum: &str,
+    analysis: &OffsetAnalysis,
+    params: &PipelineParams,
+    prior: SignPrior,
+    output: &OutputArgs,
+) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(&output.output_dir)?;
+    let sign = FrequencyEstimator::resolve_offset_sign(fft, params.f_d(), params.bandwidth, prior);
+    print!("{}", sign.report);
+    SpectrumVisualizer::plot_spectrum(
+        &fft.frequencies,
+        &fft.magnitude,
+        output.output_dir.join("analyze_spectrum.png"),
+        "Spectrum of Input Signal",
+        Some(PLOT_MAX_FREQ),
+    )?;
+    params.save(output.output_dir.join("analyze_params.json"))?;
+
+    let report = json!({
+        "input": recording.path.display().to_string(),
+        "sample_rate": params.sample_rate,
+        "num_samples": params.num_samples,
+        "spectrum": spectrum,
+        "peak_frequency": analysis.peak_frequency,
+        "refined_frequency": analysis.refined_frequency,
+        "symmetric_axis": {
+            "confidence": analysis.symmetric_axis.confidence,
+            "reason": analysis.symmetric_axis.reason,
+            "candidates": analysis.symmetric_axis.candidates,
+        },
+        "f_d": params.f_d(),
+        "method": params.offset.method,
+        "confidence": params.offset.confidence,
+        "bandwidth": params.bandwidth,
+        "sign": {
+            "prior": sign.prior,
+            "result": sign.sign,
+            "signed_f_d": sign.f_d,
+            "confidence": sign.confidence,
+            "sideband_asymmetry": sign.sideband_asymmetry,
+            "harmonic_asymmetry": sign.harmonic_asymmetry,
+            "mirror_coherence": sign.mirror_coherence,
+            "carrier_phase": sign.carrier_phase,
+        },
+    });
+    write_report(output, "analyze", "Frequency Offset Analysis", &report)?;
+    Ok(())
+}
+
+/// design-filters：写出带滤波器的参数文件、幅频响应图和报告
+fn write_design(params: &PipelineParams, output: &OutputArgs) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(&output.output_dir)?;
+    let filters = params.require_filters()?;
+
+    let hp_response =
+        filter_response::FilterResponse::compute(&filters.highpass, params.sample_rate, params.num_samples);
+    let lp_response =
+        filter_response::FilterResponse::compute(&filters.lowpass, params.sample_rate, params.num_samples);
+    response_visualizer::plot_combined_magnitude(
+        &hp_response.frequencies,
+        &hp_response.magnitude,
+        &lp_response.magnitude,
+        &output.output_dir.join("filters_magnitude.png").to_string_lossy(),
+        "Filter Magnitude Responses",
+        Some(PLOT_MAX_FREQ),
+    )?;
+    params.save(output.output_dir.join("filters_params.json"))?;
+
+    // 解析的展开相位与群时延 / 相位时延，时延起伏统计在信号实际通过的频段上：高通 [f_d, f_d + f_B]，低通 [0, f_B]；
+    // 零极点图与 b/a、二阶节两种实现的数值稳定性；
+    // 以及模拟原型与数字滤波器在全部 N 个 DFT 频点上的响应，显示双线性变换的频率畸变
+    let mut warping = json!({});
+    let mut delay = json!({});
+    let mut stability = json!({});
+    for (name, title, filter, response, band) in [
+        ("highpass", "High-pass", &filters.highpass, &hp_response, (params.f_d(), params.f_d() + params.bandwidth)),
+        ("lowpass", "Low-pass", &filters.lowpass, &lp_response, (0.0, params.bandwidth)),
+    ] {
+        let delays = DelayResponse::digital(filter, params.num_samples)?;
+        response_visualizer::plot_delay_response(
+            response,
+            &delays,
+            &output.output_dir.join(format!("filters_{}_delay.png", name)).to_string_lossy(),
+            &format!("{} Filter: Magnitude, Unwrapped Phase and Delay", title),
+            Some(PLOT_MAX_FREQ),
+            Some(band),
+        )?;
+        delay[name] = serde_json::to_value(delays.passband(band.0, band.1)?)?;
+
+        let analog_system = filter.analog()?;
+        response_visualizer::plot_pole_zero_z(
+            &filter.zpk()?,
+            &output.output_dir.join(format!("filters_{}_pole_zero.png", name)).to_string_lossy(),
+            &format!("{} Filter Poles and Zeros", title),
+        )?;
+        response_visualizer::plot_pole_zero_s(
+            analog_system.zpk(),
+            &output.output_dir.join(format!("filters_{}_pole_zero_s.png", name)).to_string_lossy(),
+            &format!("{} Analog Prototype", title),
+        )?;
+        let report = StabilityReport::digital(filter)?;
+        for warning in &report.warnings {
+            eprintln!("Warning: {}", warning);
+        }
+        stability[name] = json!({
+            "digital": report,
+            "analog": AnalogStability::of(&analog_system),
+        });
+
+        let digital = filter_response::FilterResponse::compute_dft_grid(filter, params.num_samples);
+        let analog = filter_response::FilterResponse::compute_analog_dft_grid(
+            &analog_system,
+            params.sample_rate,
+            params.num_samples,
+        );
+        response_visualizer::plot_analog_digital_overlay(
+            &digital.frequencies,
+            &analog.magnitude,
+            &digital.magnitude,
+            &output.output_dir.join(format!("filters_{}_analog_vs_digital.png", name)).to_string_lossy(),
+            &format!("{} Filter: Analog vs. Digital ({} DFT bins)", title, params.num_samples),
+            None,
+        )?;
+        warping[name] = warping_report(&analog, &digital);
+    }
+
+    // 同阶同族的 [f_d, f_d + f_B] 带通，只写入报告供比较，不参与解调
+    let sideband =
+        stages::design_sideband_bandpass(params, filters.highpass.order, filters.highpass.family)?;
+    let report = json!({
+        "sample_rate": params.sample_rate,
+        "f_d": params.f_d(),
+        "bandwidth": params.bandwidth,
+        "highpass": filter_report(&filters.highpass)?,
+        "lowpass": filter_report(&filters.lowpass)?,
+        "sideband_bandpass": filter_report(&sideband)?,
+        "warping": warping,
+        "delay": delay,
+        "stability": stability,
+    });
+    write_report(output, "filters", "IIR Filter Design", &report)?;
+    Ok(())
+}
+
+/// 0 到 f_s/2 之间（模拟响应高于 `WARPING_FLOOR_DB` 处）数字与模拟幅度之差的最大值及其频率
+fn warping_report(
+    analog: &filter_response::FilterResponse,
+    digital: &filter_response::FilterResponse,
+) -> serde_json::Value {
+    let worst = filter_response::max_warping_error(analog, digital);
+    json!({
+        "dft_bins": digital.frequencies.len(),
+        "max_error_db": worst.error_db,
+        "max_error_frequency": worst.frequency,
+    })
+}
+
+/// 单个滤波器的系数报告：b/a、零极点增益（复数写成 [re, im]）与二阶节
+fn filter_report(filter: &IirDesign) -> Result<serde_json::Value, Box<dyn Error>> {
+    let zpk = filter.zpk()?;
+    let zeros: Vec<[f64; 2]> = zpk.zeros.iter().map(|z| [z.re, z.im]).collect();
+    let poles: Vec<[f64; 2]> = zpk.poles.iter().map(|p| [p.re, p.im]).collect();
+    let mut report = json!({
+        "family": filter.family.to_string(),
+        "order": filter.order,
+        "cutoff": filter.cutoff,
+        "b": filter.b,
+        "a": filter.a,
+        "zeros": zeros,
+        "poles": poles,
+        "gain": zpk.gain,
+        "sos": filter.sos,
+    });
+    if let Some(upper) = filter.upper_cutoff {
+        report["upper_cutoff"] = json!(upper);
+    }
+    Ok(report)
+}
+
+/// state-space：把两个滤波器的状态空间实现写成 MATLAB 脚本，矩阵和 Hankel 奇异值写入报告
+fn state_space_stage(
+    params: &PipelineParams,
+    form: RealizationArg,
+    analog: bool,
+    output: &OutputArgs,
+) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(&output.output_dir)?;
+    let filters = params.require_filters()?;
+    let mut report = json!({
+        "sample_rate": params.sample_rate,
+        "form": form.to_possible_value().map(|value| value.get_name().to_string()),
+        "domain": if analog { "continuous" } else { "discrete" },
+    });
+    for (name, filter) in [("highpass", &filters.highpass), ("lowpass", &filters.lowpass)] {
+        let system = if analog { filter.analog()?.state_space()? } else { filter.state_space()? };
+        let (system, hankel) = match form {
+            RealizationArg::Sections => (system, None),
+            RealizationArg::Controllable => (system.controllable_form()?, None),
+            RealizationArg::Observable => (system.observable_form()?, None),
+            RealizationArg::Balanced => {
+                let balanced = system.balanced()?;
+                (balanced.system, Some(balanced.hankel_singular_values))
+            }
+        };
+        let path = output.output_dir.join(format!("state_space_{}.m", name));
+        fs::write(&path, system.to_matlab(name))?;
+        println!("[state-space] {}: {} states -> {}", name, system.order(), path.display());
+        report[name] = state_space_report(&system, hankel.as_deref())?;
+    }
+    write_report(output, "state_space", "State-Space Realizations", &report)?;
+    Ok(())
+}
+
+/// 单个实现的报告：矩阵按行写出，平衡实现另附 Hankel 奇异值
+fn state_space_report(system: &StateSpace, hankel: Option<&[f64]>) -> Result<serde_json::Value, Box<dyn Error>> {
+    let mut report = serde_json::to_value(system)?;
+    report["order"] = json!(system.order());
+    if let Some(hankel) = hankel {
+        report["hankel_singular_values"] = json!(hankel);
+    }
+    Ok(report)
+}
+
+/// demod-time：IIR 高通 → 乘载波 → IIR 低通
+fn demod_time_stage(
+    recording: &Recording,
+    params: &PipelineParams,
+    output: &OutputArgs,
+) -> Result<Vec<f64>, Box<dyn Error>> {
+    println!("[demod-time] f_d = {:.4} Hz", params.f_d());
+    let demod = stages::demodulate_time_domain(&recording.samples, params)?;
+    save_demodulated(output, "demod_time", "Time-Domain Demodulation", params, &demod.x_l)?;
+    Ok(demod.x_l)
+}
+
+/// demod-freq：理想高通 → 频谱搬移 → 理想低通
+fn demod_freq_stage(
+    recording: &Recording,
+    params: &PipelineParams,
+    output: &OutputArgs,
+) -> Result<Vec<f64>, Box<dyn Error>> {
+    println!("[demod-freq] f_d = {:.4} Hz", params.f_d());
+    let demod = stages::demodulate_frequency_domain(&recording.samples, params);
+    save_demodulated(output, "demod_freq", "Frequency-Domain Demodulation", params, &demod.output)?;
+    Ok(demod.output)
+}
+
+/// stream：逐块读入、解调、写出，内存占用只取决于块长与 FIR 长度
+///
+/// --verify 时另外把整段录音读入内存做批量解调，报告两者的差别；相对均方根误差超过 --tolerance 时报错
+fn stream_stage(
+    args: &StreamArgs,
+    reader: &mut WavBlockReader,
+    params: &PipelineParams,
+) -> Result<(), Box<dyn Error>> {
+    let (name, title) = match args.demod_path {
+        DemodPath::Time => ("stream_time", "Streaming Time-Domain Demodulation"),
+        DemodPath::Freq => ("stream_freq", "Streaming Frequency-Domain Demodulation"),
+    };
+    println!(
+        "[stream] {} path, f_d = {:.4} Hz, {} samples per block",
+        name.trim_start_matches("stream_"),
+        params.f_d(),
+        args.block_size
+    );
+    let config = args.overlap_save_config();
+    let mut demod: Box<dyn StreamingDemodulator> = match args.demod_path {
+        DemodPath::Time => Box::new(StreamingTimeDemod::new(params)?),
+        DemodPath::Freq => Box::new(StreamingFrequencyDemod::new(params, &config)?),
+    };
+
+    fs::create_dir_all(&args.output.output_dir)?;
+    let wav_path = args.output.output_dir.join(format!("{}.wav", name));
+    let mut writer = WavBlockWriter::create(&wav_path, reader.sample_rate(), args.gain)?;
+    let mut streamed = Vec::new();
+    let (mut blocks, mut read, mut peak) = (0usize, 0usize, 0.0f64);
+    let start = Instant::now();
+    while let Some(block) = reader.next_block()? {
+        let output = demod.process_block(&block);
+        writer.write_block(&output)?;
+        peak = output.iter().fold(peak, |max, &x| max.max(x.abs()));
+        if args.verify {
+            streamed.extend_from_slice(&output);
+        }
+        blocks += 1;
+        read += block.len();
+    }
+    let tail = demod.finish();
+    writer.write_block(&tail)?;
+    peak = tail.iter().fold(peak, |max, &x| max.max(x.abs()));
+    streamed.extend(tail);
+    let elapsed = start.elapsed().as_secs_f64();
+    let (written, clipped) = (writer.written(), writer.clipped());
+    writer.finalize()?;
+    println!("Demodulated audio saved to {}", wav_path.display());
+    if clipped > 0 {
+        eprintln!("Warning: {} samples clipped; lower --gain (peak amplitude {:.3})", clipped, peak);
+    }
+
+    let mut report = json!({
+        "input": args.input.display().to_string(),
+        "output": wav_path.display().to_string(),
+        "sample_rate": params.sample_rate,
+        "num_samples": written,
+        "f_d": params.f_d(),
+        "bandwidth": params.bandwidth,
+        "block_size": args.block_size,
+        "blocks": blocks,
+        "gain": args.gain,
+        "peak_amplitude": peak,
+        "clipped_samples": clipped,
+        "elapsed_seconds": elapsed,
+        "realtime_factor": read as f64 / params.sample_rate / elapsed.max(1e-9),
+    });
+    if args.demod_path == DemodPath::Freq {
+        report["overlap_save"] = json!({
+            "taps": config.taps,
+            "fft_size": config.fft_size,
+            "window": config.window.to_string(),
+        });
+    }
+    let mut relative_rms_error = 0.0;
+    if args.verify {
+        let recording = Recording::load(&args.input)?;
+        let (batch, margin) = match args.demod_path {
+            DemodPath::Time => (stages::demodulate_time_domain(&recording.samples, params)?.x_l, 0),
+            DemodPath::Freq => (stages::demodulate_frequency_domain(&recording.samples, params).output, config.taps),
+        };
+        let (verification, error) = stream_verification(&streamed, &batch, margin);
+        relative_rms_error = error;
+        report["verification"] = verification;
+        report["verification"]["tolerance"] = json!(args.tolerance);
+    }
+    write_report(&args.output, name, title, &report)?;
+    if relative_rms_error > args.tolerance {
+        return Err(format!(
+            "streamed output differs from batch demodulation: relative RMS error {:.3e} exceeds --tolerance {:.3e}",
+            relative_rms_error, args.tolerance
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// 分块输出与批量输出的差别及相对均方根误差；margin 为两端不计入相对误差的样本数（FIR 首尾暂态）
+fn stream_verification(streamed: &[f64], batch: &[f64], margin: usize) -> (serde_json::Value, f64) {
+    let n = streamed.len().min(batch.len());
+    let max_abs_difference = streamed
+        .iter()
+        .zip(batch)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f64::max);
+    let interior = margin.min(n / 2)..n - margin.min(n / 2);
+    let power: f64 = batch[interior.clone()].iter().map(|x| x * x).sum();
+    let error: f64 = interior.clone().map(|i| (streamed[i] - batch[i]).powi(2)).sum();
+    let relative_rms_error = if power > 0.0 { (error / power).sqrt() } else { 0.0 };
+    println!(
+        "Streaming vs batch: max |difference| = {:.3e}, relative RMS error = {:.3e}",
+        max_abs_difference, relative_rms_error
+    );
+    let report = json!({
+        "streamed_samples": streamed.len(),
+        "batch_samples": batch.len(),
+        "max_abs_difference": max_abs_difference,
+        "excluded_edge_samples": margin,
+        "relative_rms_error": relative_rms_error,
+    });
+    (report, relative_rms_error)
+}
+
+/// spectrogram：两条解调路径各阶段信号的时频谱图
+///
+/// 频域路径的 x_h、x_b、x_l 由各自的频谱做 IFFT 得到；频谱搬移只含 cos 的 1/2，
+/// x_b、x_l 乘以 2 与时域路径的幅度一致。两条路径的 x 相同，只画一次
+fn spectrogram_stage(
+    recording: &Recording,
+    params: &PipelineParams,
+    config: &StftConfig,
+    max_freq: f64,
+    dynamic_range: f64,
+    output: &OutputArgs,
+) -> Result<(), Box<dyn Error>> {
+    println!(
+        "[spectrogram] window {} / hop {} / FFT {}",
+        config.window_length, config.hop, config.fft_size
+    );
+    let time = stages::demodulate_time_domain(&recording.samples, params)?;
+    let freq = stages::demodulate_frequency_domain(&recording.samples, params);
+    let scaled = |fft: &FftResult, gain: f64| -> Vec<f64> {
+        fft.to_signal().into_iter().map(|x| x * gain).collect()
+    };
+
+    let signals = [
+        ("x", "x", recording.samples.clone()),
+        ("time_x_h", "Time-Domain x_h", time.x_h),
+        ("time_x_b", "Time-Domain x_b", time.x_b),
+        ("time_x_l", "Time-Domain x_l", time.x_l),
+        ("freq_x_h", "Frequency-Domain x_h", scaled(&freq.x_h, 1.0)),
+        ("freq_x_b", "Frequency-Domain x_b", scaled(&freq.x_b, 2.0)),
+        ("freq_x_l", "Frequency-Domain x_l", scaled(&freq.x_l, 2.0)),
+    ];
+
+    fs::create_dir_all(&output.output_dir)?;
+    let mut files = Vec::new();
+    for (name, label, samples) in &signals {
+        let spec = stft::compute_stft(samples, recording.sample_rate, config)?;
+        let file = format!("spectrogram_{}.png", name);
+        SpectrumVisualizer::plot_spectrogram(
+            &spec,
+            output.output_dir.join(&file),
+            &format!("Spectrogram of {}", label),
+            Some(max_freq),
+            dynamic_range,
+        )?;
+        files.push(json!({
+            "signal": name,
+            "file": file,
+            "frames": spec.frames.len(),
+        }));
+    }
+
+    let report = json!({
+        "input": recording.path.display().to_string(),
+        "sample_rate": params.sample_rate,
+        "f_d": params.f_d(),
+        "f_b": params.bandwidth,
+        "window": config.window,
+        "window_length": config.window_length,
+        "hop": config.hop,
+        "fft_size": config.fft_size,
+        "time_resolution": config.window_length as f64 / recording.sample_rate,
+        "bin_spacing": recording.sample_rate / config.fft_size as f64,
+        "dynamic_range_db": dynamic_range,
+        "spectrograms": files,
+    });
+    write_report(output, "spectrogram", "Spectrograms", &report)?;
+    Ok(())
+}
+
+/// track：跟踪 f_d(t)，写出轨迹图、逐帧 CSV、NCO 解调音频和报告
+fn track_stage(
+    recording: &Recording,
+    params: &PipelineParams,
+    config: &TrackerConfig,
+    output: &OutputArgs,
+) -> Result<(), Box<dyn Error>> {
+    println!("[track] nominal f_d = {:.4} Hz", params.f_d());
+    let track = offset_tracker::track_frequency_offset(
+        &recording.samples,
+        recording.sample_rate,
+        params.f_d(),
+        config,
+    )?;
+    let demod = stages::demodulate_tracked(&recording.samples, params, &track)?;
+
+    fs::create_dir_all(&output.output_dir)?;
+    SpectrumVisualizer::plot_offset_track(
+        &track,
+        output.output_dir.join("track_offset.png"),
+        "Tracked Frequency Offset",
+    )?;
+    let csv_path = output.output_dir.join("track_offset.csv");
+    write_track_csv(&csv_path, &track)?;
+    save_demodulated(output, "track_demod", "Tracked Demodulation", params, &demod.x_l)?;
+
+    let (min, mean, max) = track.summary();
+    let report = json!({
+        "input": recording.path.display().to_string(),
+        "sample_rate": params.sample_rate,
+        "nominal_f_d": track.nominal,
+        "frame_length": config.frame_length,
+        "hop": config.hop,
+        "max_deviation": config.max_deviation,
+        "median_window": config.median_window,
+        "frames": track.frames.len(),
+        "valid_frames": track.valid_frames(),
+        "f_d": {
+            "min": min,
+            "mean": mean,
+            "max": max,
+            "drift": max - min,
+        },
+        "trajectory": csv_path.display().to_string(),
+    });
+    write_report(output, "track", "Frequency Offset Tracking", &report)?;
+    Ok(())
+}
+
+/// 功率谱密度：frequency,density,lower,upper（lower / upper 为给定置信度的置信区间）
+fn write_psd_csv(path: &Path, psd: &PowerSpectralDensity, confidence: f64) -> Result<(), Box<dyn Error>> {
+    let (lower, upper) = psd.confidence_interval(confidence);
+    let mut csv = String::from("frequency,density,lower,upper\n");
+    for (((f, p), lo), hi) in psd.frequencies.iter().zip(&psd.density).zip(&lower).zip(&upper) {
+        csv.push_str(&format!("{},{},{},{}\n", f, p, lo, hi));
+    }
+    fs::write(path, csv)?;
+    println!("PSD saved to {}", path.display());
+    Ok(())
+}
+
+/// 逐帧轨迹：time,raw,score,f_d（无法估计的帧 raw 留空）
+fn write_track_csv(path: &Path, track: &OffsetTrack) -> Result<(), Box<dyn Error>> {
+    let mut csv = String::from("time,raw,score,f_d\n");
+    for frame in &track.frames {
+        let raw = frame.raw.map_or(String::new(), |raw| raw.to_string());
+        csv.push_str(&format!("{},{},{},{}\n", frame.time, raw, frame.score, frame.f_d));
+    }
+    fs::write(path, csv)?;
+    println!("f_d trajectory saved to {}", path.display());
+    Ok(())
+}
+
+/// recover：闭环载波恢复，写出环路轨迹图、以锁定相位解调的音频和报告
+fn recover_stage(
+    recording: &Recording,
+    params: &PipelineParams,
+    config: &LoopConfig,
+    output: &OutputArgs,
+) -> Result<(), Box<dyn Error>> {
+    println!(
+        "[recover] {:?} loop, B_n = {} Hz, initial f_d = {:.4} Hz",
+        config.kind, config.loop_bandwidth, config.initial_frequency
+    );
+    let recovery = carrier_recovery::recover_carrier(&recording.samples, recording.sample_rate, config)?;
+    if !recovery.is_locked() {
+        eprintln!("Warning: the loop did not stay locked; the output uses the unlocked NCO phase");
+    }
+    let demod = stages::demodulate_recovered(&recording.samples, params, &recovery)?;
+
+    fs::create_dir_all(&output.output_dir)?;
+    SpectrumVisualizer::plot_carrier_recovery(
+        &recovery,
+        output.output_dir.join("recover_loop.png"),
+        "Carrier Recovery Loop",
+    )?;
+    save_demodulated(output, "recover_demod", "Carrier-Recovery Demodulation", params, &demod.x_l)?;
+
+    let report = json!({
+        "input": recording.path.display().to_string(),
+        "sample_rate": recording.sample_rate,
+        "loop": config.kind,
+        "loop_bandwidth": config.loop_bandwidth,
+        "damping": config.damping,
+        "initial_frequency": config.initial_frequency,
+        "arm_bandwidth": config.arm_bandwidth,
+        "locked": recovery.is_locked(),
+        "lock_time": recovery.lock_time,
+        "locked_frequency": recovery.locked_frequency(),
+        "rms_phase_error": recovery.rms_phase_error(),
+        "final_lock_indicator": recovery.lock.last().copied().unwrap_or(0.0),
+    });
+    write_report(output, "recover", "Carrier Recovery", &report)?;
+    Ok(())
+}
+
+/// 写出解调音频、输出频谱图和报告
+fn save_demodulated(
+    output: &OutputArgs,
+    name: &str,
+    title: &str,
+    params: &PipelineParams,
+    samples: &[f64],
+) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(&output.output_dir)?;
+    let spectrum = FftResult::compute(samples, params.sample_rate);
+    let wav_path = output.output_dir.join(format!("{}.wav", name));
+    amdemod::write_wav(&wav_path, samples, params.sample_rate as u32)?;
+    SpectrumVisualizer::plot_fft_result(
+        &spectrum,
+        output.output_dir.join(format!("{}_spectrum.png", name)),
+        &format!("{} Output", title),
+    )?;
+
+    let (peak_frequency, peak_magnitude) = spectrum
+        .get_single_sided_pairs()
+        .into_iter()
+        .filter(|&(f, _)| f > 10.0 && f < params.bandwidth)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0.0, 0.0));
+    let max_amplitude = samples.iter().fold(0.0f64, |max, &x| max.max(x.abs()));
+
+    let report = json!({
+        "output": wav_path.display().to_string(),
+        "sample_rate": params.sample_rate,
+        "num_samples": samples.len(),
+        "f_d": params.f_d(),
+        "bandwidth": params.bandwidth,
+        "max_amplitude": max_amplitude,
+        "baseband_peak": {
+            "frequency": peak_frequency,
+            "magnitude": peak_magnitude,
+        },
+    });
+    write_report(output, name, title, &report)?;
+    Ok(())
+}
+
+/// compare：误差指标、波形对比图和报告
+fn compare_stage(input: &[f64], reference: &[f64], output: &OutputArgs) -> Result<(), Box<dyn Error>> {
+    println!("[compare]");
+    let result = comparator::compare_signals(input, reference);
+    write_comparison(input, reference, &result, output)
+}
+
+/// 写出波形对比图和 compare 报告
+fn write_comparison(
+    input: &[f64],
+    reference: &[f64],
+    result: &ComparisonResult,
+    output: &OutputArgs,
+) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(&output.output_dir)?;
+    comparator::plot_full_comparison(
+        input,
+        reference,
+        &output.output_dir.join("compare_full.png").to_string_lossy(),
+    )?;
+    comparator::plot_comparison(
+        input,
+        reference,
+        &output.output_dir.join("compare_detail.png").to_string_lossy(),
+    )?;
+
+    let mut report = serde_json::to_value(result)?;
+    report["rmse"] = json!(result.mse.sqrt());
+    write_report(output, "compare", "Signal Comparison", &report)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FamilyArg;
+
+    fn param_args(f_d: Option<f64>, f_b: Option<f64>, order: usize) -> ParamArgs {
+        ParamArgs {
+            params: None,
+            f_d,
+            f_b,
+            order,
+            family: FamilyArg::Butterworth,
+            ripple: 1.0,
+            attenuation: 60.0,
+            transition: None,
+        }
+    }
+
+    fn estimated_params() -> PipelineParams {
+        let offset = FrequencyOffset {
+            f_d: 3000.0,
+            method: amdemod::EstimatorMethod::SymmetricAxis,
+            confidence: 0.95,
+        };
+        PipelineParams::new(22050.0, 1024, offset, DEFAULT_BANDWIDTH)
+    }
+
+    #[test]
+    fn test_overrides_redesign_filters() {
+        let params = apply_overrides(estimated_params(), &param_args(None, None, 8), None).unwrap();
+        assert_eq!(params.require_filters().unwrap().highpass.order, 8);
+
+        let params = apply_overrides(params, &param_args(Some(2500.0), Some(3000.0), 6), None).unwrap();
+        let filters = params.require_filters().unwrap();
+        assert_eq!(params.offset, FrequencyOffset::manual(2500.0));
+        assert_eq!(filters.highpass.cutoff, 2500.0);
+        assert_eq!(filters.lowpass.cutoff, 3000.0);
+        assert_eq!(filters.lowpass.order, 6);
+
+        // 只换滤波器族也要重新设计
+        let mut args = param_args(None, None, 6);
+        args.family = FamilyArg::Elliptic;
+        let params = apply_overrides(params, &args, None).unwrap();
+        let filters = params.require_filters().unwrap();
+        assert_eq!(filters.highpass.family, args.family());
+        assert_eq!(filters.highpass.cutoff, 2500.0);
+
+        // 过渡带指标决定阶数：同为 200 Hz，低通（3000 Hz）的相对过渡带比高通（2500 Hz）窄，阶数不低于高通
+        args.transition = Some(200.0);
+        let params = apply_overrides(params, &args, None).unwrap();
+        let filters = params.require_filters().unwrap();
+        assert!(filters.lowpass.order >= filters.highpass.order && filters.highpass.order != 6);
+        assert_eq!(filters.lowpass.cutoff, 3000.0);
+    }
+
+    #[test]
+    fn test_override_out_of_range() {
+        let err = apply_overrides(estimated_params(), &param_args(Some(20000.0), None, 8), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("offset.f_d"));
+    }
+}
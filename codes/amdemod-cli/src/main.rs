@@ -0,0 +1,707 @@
+//! `amdemod` 命令行工具
+//!
+//! 把 Q1–Q4 的各个阶段做成子命令，输入、输出路径和参数都由命令行给出，
+//! 可以在任意录音上运行整条解调流水线而无需修改源码。
+
+mod commands;
+mod report;
+
+use amdemod::stages::{SpectrumWindow, DEFAULT_FILTER_ORDER};
+use amdemod::sub_bin::BenchmarkConfig;
+use amdemod::window::{Correction, Symmetry};
+use amdemod::{
+    FilterFamily, LoopConfig, LoopKind, OverlapSaveConfig, PsdMethod, SignPrior, StftConfig, TrackerConfig,
+    TransitionSpec, Window,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::error::Error;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "amdemod", version, about = "AM signal demodulation pipeline")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Estimate the frequency offset f_d from the spectrum of a recording
+    Analyze(AnalyzeArgs),
+    /// Evaluate the spectrum densely over a frequency band with the chirp-z transform
+    Zoom(ZoomArgs),
+    /// Estimate the power spectral density (Welch, Bartlett or multitaper) with confidence intervals
+    Psd(PsdArgs),
+    /// Report coherent gain, ENBW and scalloping loss of window functions and plot their responses
+    Windows(WindowsArgs),
+    /// Design the high-pass (f_d) and low-pass (f_B) IIR filters (Butterworth by default)
+    DesignFilters(StageArgs),
+    /// Export state-space realizations (A, B, C, D) of the designed filters
+    StateSpace(StateSpaceArgs),
+    /// Demodulate in the time domain with the designed IIR filters
+    DemodTime(StageArgs),
+    /// Demodulate in the frequency domain with ideal filters
+    DemodFreq(StageArgs),
+    /// Demodulate an arbitrarily long recording block by block with bounded memory
+    Stream(StreamArgs),
+    /// Track a drifting frequency offset f_d(t) and demodulate with a phase-continuous NCO
+    Track(TrackArgs),
+    /// Recover the carrier with a PLL or Costas loop and demodulate with the locked phase
+    Recover(RecoverArgs),
+    /// Render STFT spectrograms of x, x_h, x_b and x_l for both demodulation paths
+    Spectrogram(SpectrogramArgs),
+    /// Compare two demodulated recordings
+    Compare(CompareArgs),
+    /// Benchmark the sub-bin frequency estimators on noisy synthetic tones
+    BenchEstimators(BenchArgs),
+    /// Run every stage in-process, recomputing only stages whose inputs changed
+    RunAll(RunAllArgs),
+}
+
+#[derive(Args)]
+struct AnalyzeArgs {
+    /// Input WAV file
+    #[arg(short, long)]
+    input: PathBuf,
+
+    /// Baseband bandwidth f_B (Hz) recorded in the parameter file
+    #[arg(long)]
+    f_b: Option<f64>,
+
+    #[command(flatten)]
+    sign: SignArgs,
+
+    #[command(flatten)]
+    spectrum: SpectrumArgs,
+
+    /// Half-width (Hz) of the zoomed spectrum plotted around the estimated f_d
+    #[arg(long, default_value_t = 20.0)]
+    zoom_span: f64,
+
+    /// Estimate f_d on a power spectral density instead of the raw periodogram
+    #[arg(long, value_enum)]
+    psd: Option<PsdArg>,
+
+    #[command(flatten)]
+    psd_options: PsdOptions,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args)]
+struct ZoomArgs {
+    /// Input WAV file
+    #[arg(short, long)]
+    input: PathBuf,
+
+    /// Lower edge of the band (Hz)
+    #[arg(long)]
+    from: f64,
+
+    /// Upper edge of the band (Hz)
+    #[arg(long)]
+    to: f64,
+
+    /// Number of frequency points in the band
+    #[arg(long, default_value_t = 2001)]
+    points: usize,
+
+    /// Frequencies (Hz) to mark on the plot
+    #[arg(long, value_delimiter = ',')]
+    mark: Vec<f64>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args)]
+struct PsdArgs {
+    /// Input WAV file
+    #[arg(short, long)]
+    input: PathBuf,
+
+    /// PSD estimator
+    #[arg(long, value_enum, default_value_t = PsdArg::Welch)]
+    method: PsdArg,
+
+    #[command(flatten)]
+    options: PsdOptions,
+
+    /// Confidence level of the plotted and exported interval
+    #[arg(long, default_value_t = 0.95)]
+    confidence: f64,
+
+    /// Highest frequency shown (Hz)
+    #[arg(long, default_value_t = 10000.0)]
+    max_freq: f64,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// 功率谱密度估计方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PsdArg {
+    Welch,
+    Bartlett,
+    Multitaper,
+}
+
+/// 功率谱密度估计的参数
+#[derive(Args)]
+struct PsdOptions {
+    /// Segment length in samples (Welch, Bartlett)
+    #[arg(long, default_value_t = 4096)]
+    segment_length: usize,
+
+    /// Overlap between segments in samples (Welch) [default: half a segment]
+    #[arg(long)]
+    overlap: Option<usize>,
+
+    /// Segment window (Welch), e.g. hann, blackman-harris, kaiser:8.6, tukey:0.5
+    #[arg(long, default_value_t = Window::Hann)]
+    window: Window,
+
+    /// Time-half-bandwidth product NW (multitaper)
+    #[arg(long, default_value_t = 4.0)]
+    nw: f64,
+
+    /// Number of DPSS tapers (multitaper) [default: 2·NW - 1]
+    #[arg(long)]
+    tapers: Option<usize>,
+}
+
+impl PsdOptions {
+    fn method(&self, kind: PsdArg) -> PsdMethod {
+        match kind {
+            PsdArg::Welch => PsdMethod::Welch {
+                segment_length: self.segment_length,
+                overlap: self.overlap.unwrap_or(self.segment_length / 2),
+                window: self.window,
+            },
+            PsdArg::Bartlett => PsdMethod::Bartlett {
+                segment_length: self.segment_length,
+            },
+            PsdArg::Multitaper => PsdMethod::Multitaper {
+                nw: self.nw,
+                tapers: self
+                    .tapers
+                    .unwrap_or(((2.0 * self.nw).floor() as usize).saturating_sub(1).max(1)),
+            },
+        }
+    }
+}
+
+/// 边带先验
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SidebandPrior {
+    None,
+    UpperSideband,
+    LowerSideband,
+}
+
+impl AnalyzeArgs {
+    fn psd_method(&self) -> Option<PsdMethod> {
+        self.psd.map(|kind| self.psd_options.method(kind))
+    }
+}
+
+/// f_d 符号判别所用的先验
+#[derive(Args)]
+struct SignArgs {
+    /// Prior knowledge used to decide the sign of f_d = f_c - f̃_c
+    #[arg(long, value_enum, default_value_t = SidebandPrior::None)]
+    sign_prior: SidebandPrior,
+
+    /// Known carrier phase difference θ_c - θ̃_c (rad) used to decide the sign of f_d
+    #[arg(long, allow_negative_numbers = true, conflicts_with = "sign_prior")]
+    carrier_phase: Option<f64>,
+}
+
+impl SignArgs {
+    fn prior(&self) -> SignPrior {
+        match (self.carrier_phase, self.sign_prior) {
+            (Some(theta), _) => SignPrior::CarrierPhase(theta),
+            (None, SidebandPrior::None) => SignPrior::None,
+            (None, SidebandPrior::UpperSideband) => SignPrior::UpperSidebandDominant,
+            (None, SidebandPrior::LowerSideband) => SignPrior::LowerSidebandDominant,
+        }
+    }
+}
+
+/// 估计 f_d 所用输入频谱的窗与修正方式
+#[derive(Args)]
+struct SpectrumArgs {
+    /// Window applied to the recording before the spectrum used to estimate f_d, e.g. hann, flat_top, kaiser:8.6
+    #[arg(long, default_value_t = SpectrumWindow::default().window)]
+    spectrum_window: Window,
+
+    /// Correction of the window's loss: amplitude reads sinusoid amplitudes, energy preserves power
+    #[arg(long, value_enum, default_value_t = CorrectionArg::Amplitude)]
+    correction: CorrectionArg,
+}
+
+/// 窗的修正方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CorrectionArg {
+    Amplitude,
+    Energy,
+}
+
+impl SpectrumArgs {
+    fn spectrum_window(&self) -> SpectrumWindow {
+        SpectrumWindow {
+            window: self.spectrum_window,
+            correction: match self.correction {
+                CorrectionArg::Amplitude => Correction::Amplitude,
+                CorrectionArg::Energy => Correction::Energy,
+            },
+        }
+    }
+}
+
+#[derive(Args)]
+struct StageArgs {
+    /// Input WAV file
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    #[command(flatten)]
+    params: ParamArgs,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args)]
+struct StateSpaceArgs {
+    #[command(flatten)]
+    stage: StageArgs,
+
+    /// Realization written for each filter
+    #[arg(long, value_enum, default_value_t = RealizationArg::Sections)]
+    form: RealizationArg,
+
+    /// Export the continuous-time prototypes (unwarped edges) instead of the digital filters
+    #[arg(long)]
+    analog: bool,
+}
+
+/// 状态空间实现形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RealizationArg {
+    /// Cascade of second-order sections
+    Sections,
+    Controllable,
+    Observable,
+    Balanced,
+}
+
+#[derive(Args)]
+struct StreamArgs {
+    /// Input WAV file, read block by block
+    #[arg(short, long)]
+    input: PathBuf,
+
+    #[command(flatten)]
+    params: ParamArgs,
+
+    /// Demodulation path
+    #[arg(long = "path", value_enum, default_value_t = DemodPath::Time)]
+    demod_path: DemodPath,
+
+    /// Samples read and demodulated per block
+    #[arg(long, default_value_t = 8192)]
+    block_size: usize,
+
+    /// FIR length approximating the ideal filters (frequency-domain path, odd)
+    #[arg(long, default_value_t = OverlapSaveConfig::default().taps)]
+    taps: usize,
+
+    /// FFT size of each overlap-save block (frequency-domain path)
+    #[arg(long, default_value_t = OverlapSaveConfig::default().fft_size)]
+    fft_size: usize,
+
+    /// Window truncating the ideal impulse responses (frequency-domain path)
+    #[arg(long, default_value_t = OverlapSaveConfig::default().window)]
+    fir_window: Window,
+
+    /// Fixed gain applied before writing 16-bit samples (a stream cannot be peak-normalised)
+    #[arg(long, default_value_t = 1.0)]
+    gain: f64,
+
+    /// Also demodulate the whole recording in memory and report the difference
+    #[arg(long)]
+    verify: bool,
+
+    /// Largest relative RMS error between streamed and batch output accepted by --verify
+    #[arg(long, default_value_t = 1e-2)]
+    tolerance: f64,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// 分块解调的路径
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DemodPath {
+    Time,
+    Freq,
+}
+
+impl StreamArgs {
+    fn overlap_save_config(&self) -> OverlapSaveConfig {
+        OverlapSaveConfig {
+            taps: self.taps,
+            fft_size: self.fft_size,
+            window: self.fir_window,
+        }
+    }
+}
+
+#[derive(Args)]
+struct RunAllArgs {
+    #[command(flatten)]
+    stage: StageArgs,
+
+    /// Stage cache directory [default: <OUTPUT_DIR>/cache]
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Recompute every stage without reading or writing the cache
+    #[arg(long, conflicts_with = "cache_dir")]
+    no_cache: bool,
+
+    #[command(flatten)]
+    sign: SignArgs,
+
+    #[command(flatten)]
+    spectrum: SpectrumArgs,
+}
+
+#[derive(Args)]
+struct TrackArgs {
+    #[command(flatten)]
+    stage: StageArgs,
+
+    /// Frame length in samples
+    #[arg(long, default_value_t = TrackerConfig::default().frame_length)]
+    frame_length: usize,
+
+    /// Hop between frames in samples
+    #[arg(long, default_value_t = TrackerConfig::default().hop)]
+    hop: usize,
+
+    /// Largest deviation (Hz) of f_d(t) from the nominal f_d searched in each frame
+    #[arg(long, default_value_t = TrackerConfig::default().max_deviation)]
+    max_deviation: f64,
+
+    /// Median filter length (frames) applied to the per-frame estimates
+    #[arg(long, default_value_t = TrackerConfig::default().median_window)]
+    median_window: usize,
+}
+
+impl TrackArgs {
+    fn tracker_config(&self) -> TrackerConfig {
+        TrackerConfig {
+            frame_length: self.frame_length,
+            hop: self.hop,
+            max_deviation: self.max_deviation,
+            median_window: self.median_window,
+            ..TrackerConfig::default()
+        }
+    }
+}
+
+#[derive(Args)]
+struct RecoverArgs {
+    #[command(flatten)]
+    stage: StageArgs,
+
+    /// Carrier recovery loop
+    #[arg(long = "loop", value_enum, default_value_t = LoopArg::Costas)]
+    loop_kind: LoopArg,
+
+    /// Loop noise bandwidth B_n (Hz)
+    #[arg(long, default_value_t = 20.0)]
+    loop_bandwidth: f64,
+
+    /// Loop damping factor ζ
+    #[arg(long, default_value_t = std::f64::consts::FRAC_1_SQRT_2)]
+    damping: f64,
+
+    /// I/Q arm low-pass cutoff (Hz) [default: min(f_B, 0.8 f_d)]
+    #[arg(long)]
+    arm_bandwidth: Option<f64>,
+}
+
+/// 载波恢复环路类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LoopArg {
+    Pll,
+    Costas,
+}
+
+impl RecoverArgs {
+    /// 以参数中的 f_d 作为 NCO 初始频率
+    fn loop_config(&self, f_d: f64, f_b: f64) -> LoopConfig {
+        let kind = match self.loop_kind {
+            LoopArg::Pll => LoopKind::Pll,
+            LoopArg::Costas => LoopKind::Costas,
+        };
+        let arm_bandwidth = self.arm_bandwidth.unwrap_or(f_b.min(0.8 * f_d));
+        LoopConfig {
+            loop_bandwidth: self.loop_bandwidth,
+            damping: self.damping,
+            ..LoopConfig::new(kind, f_d, arm_bandwidth)
+        }
+    }
+}
+
+#[derive(Args)]
+struct SpectrogramArgs {
+    #[command(flatten)]
+    stage: StageArgs,
+
+    /// Analysis window, e.g. hann, blackman-harris, kaiser:8.6, tukey:0.5
+    #[arg(long, default_value_t = Window::Hann)]
+    window: Window,
+
+    /// Window length in samples
+    #[arg(long, default_value_t = StftConfig::default().window_length)]
+    window_length: usize,
+
+    /// Hop between frames in samples
+    #[arg(long, default_value_t = StftConfig::default().hop)]
+    hop: usize,
+
+    /// FFT size per frame (zero-padded when larger than the window) [default: window length]
+    #[arg(long)]
+    fft_size: Option<usize>,
+
+    /// Highest frequency shown (Hz)
+    #[arg(long, default_value_t = 10000.0)]
+    max_freq: f64,
+
+    /// Colour scale range below the peak (dB)
+    #[arg(long, default_value_t = 80.0)]
+    dynamic_range: f64,
+}
+
+#[derive(Args)]
+struct WindowsArgs {
+    /// Windows to compare [default: all, with typical parameters]
+    #[arg(long, value_delimiter = ',')]
+    window: Vec<Window>,
+
+    /// Window length N
+    #[arg(long, default_value_t = 4096)]
+    length: usize,
+
+    /// Use symmetric instead of periodic windows
+    #[arg(long)]
+    symmetric: bool,
+
+    /// Sample rate (Hz) used to express ENBW in Hz
+    #[arg(long, default_value_t = 22050.0)]
+    sample_rate: f64,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+impl WindowsArgs {
+    fn windows(&self) -> Vec<Window> {
+        if self.window.is_empty() {
+            Window::all()
+        } else {
+            self.window.clone()
+        }
+    }
+
+    fn symmetry(&self) -> Symmetry {
+        if self.symmetric {
+            Symmetry::Symmetric
+        } else {
+            Symmetry::Periodic
+        }
+    }
+}
+
+impl SpectrogramArgs {
+    fn stft_config(&self) -> StftConfig {
+        StftConfig {
+            window: self.window,
+            window_length: self.window_length,
+            hop: self.hop,
+            fft_size: self.fft_size.unwrap_or(self.window_length),
+        }
+    }
+}
+
+#[derive(Args)]
+struct CompareArgs {
+    /// Demodulated WAV file under test
+    #[arg(short, long)]
+    input: PathBuf,
+
+    /// Reference WAV file
+    #[arg(short, long)]
+    reference: PathBuf,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// SNR values A²/(2σ²) in dB
+    #[arg(long, value_delimiter = ',', allow_negative_numbers = true,
+          default_values_t = BenchmarkConfig::default().snr_db)]
+    snr: Vec<f64>,
+
+    /// Trials per SNR value
+    #[arg(long, default_value_t = BenchmarkConfig::default().trials)]
+    trials: usize,
+
+    /// Signal length N in samples
+    #[arg(long, default_value_t = BenchmarkConfig::default().length)]
+    length: usize,
+
+    /// Sample rate f_s (Hz)
+    #[arg(long, default_value_t = BenchmarkConfig::default().sample_rate)]
+    sample_rate: f64,
+
+    /// Tone frequency (Hz), randomized within ± half a bin
+    #[arg(long, default_value_t = BenchmarkConfig::default().center_frequency)]
+    frequency: f64,
+
+    /// Random seed
+    #[arg(long, default_value_t = BenchmarkConfig::default().seed)]
+    seed: u64,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+impl BenchArgs {
+    fn config(&self) -> BenchmarkConfig {
+        BenchmarkConfig {
+            sample_rate: self.sample_rate,
+            length: self.length,
+            center_frequency: self.frequency,
+            snr_db: self.snr.clone(),
+            trials: self.trials,
+            seed: self.seed,
+        }
+    }
+}
+
+/// 参数来源与覆盖
+#[derive(Args)]
+struct ParamArgs {
+    /// Parameter file from a previous stage; estimated from the input when omitted
+    #[arg(short, long)]
+    params: Option<PathBuf>,
+
+    /// Override the frequency offset f_d (Hz)
+    #[arg(long)]
+    f_d: Option<f64>,
+
+    /// Override the baseband bandwidth f_B (Hz)
+    #[arg(long)]
+    f_b: Option<f64>,
+
+    /// IIR filter order
+    #[arg(long, default_value_t = DEFAULT_FILTER_ORDER)]
+    order: usize,
+
+    /// IIR filter family used for both the high-pass and the low-pass filter
+    #[arg(long, value_enum, default_value_t = FamilyArg::Butterworth)]
+    family: FamilyArg,
+
+    /// Passband ripple in dB (chebyshev1, elliptic; also the passband edge loss for --transition)
+    #[arg(long, default_value_t = 1.0)]
+    ripple: f64,
+
+    /// Stopband attenuation in dB (chebyshev2, elliptic; also the stopband edge loss for --transition)
+    #[arg(long, default_value_t = 60.0)]
+    attenuation: f64,
+
+    /// Transition band width in Hz; picks the minimum order meeting --ripple/--attenuation instead of --order
+    #[arg(long)]
+    transition: Option<f64>,
+}
+
+/// 滤波器族
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FamilyArg {
+    Butterworth,
+    Chebyshev1,
+    Chebyshev2,
+    Elliptic,
+    Bessel,
+}
+
+impl ParamArgs {
+    fn transition(&self) -> Option<TransitionSpec> {
+        self.transition.map(|width| TransitionSpec {
+            width,
+            ripple_db: self.ripple,
+            attenuation_db: self.attenuation,
+        })
+    }
+
+    fn family(&self) -> FilterFamily {
+        match self.family {
+            FamilyArg::Butterworth => FilterFamily::Butterworth,
+            FamilyArg::Chebyshev1 => FilterFamily::Chebyshev1 { ripple_db: self.ripple },
+            FamilyArg::Chebyshev2 => FilterFamily::Chebyshev2 { attenuation_db: self.attenuation },
+            FamilyArg::Elliptic => FilterFamily::Elliptic {
+                ripple_db: self.ripple,
+                attenuation_db: self.attenuation,
+            },
+            FamilyArg::Bessel => FilterFamily::Bessel,
+        }
+    }
+}
+
+/// 输出目录与报告格式
+#[derive(Args)]
+struct OutputArgs {
+    /// Directory for generated files
+    #[arg(short, long, default_value = "output")]
+    output_dir: PathBuf,
+
+    /// Report format
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+/// 报告文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    match Cli::parse().command {
+        Command::Analyze(args) => commands::analyze(&args),
+        Command::Zoom(args) => commands::zoom(&args),
+        Command::Psd(args) => commands::psd(&args),
+        Command::Windows(args) => commands::windows(&args),
+        Command::DesignFilters(args) => commands::design_filters(&args),
+        Command::StateSpace(args) => commands::state_space(&args),
+        Command::DemodTime(args) => commands::demod_time(&args),
+        Command::DemodFreq(args) => commands::demod_freq(&args),
+        Command::Stream(args) => commands::stream(&args),
+        Command::Track(args) => commands::track(&args),
+        Command::Recover(args) => commands::recover(&args),
+        Command::Spectrogram(args) => commands::spectrogram(&args),
+        Command::Compare(args) => commands::compare(&args),
+        Command::RunAll(args) => commands::run_all(&args),
+        Command::BenchEstimators(args) => commands::bench_estimators(&args),
+    }
+}
@@ -0,0 +1,95 @@
+// 子命令报告输出
+// 同一份报告内容可写成文本或 JSON 文件
+
+use crate::{Format, OutputArgs};
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// 把报告写入 `<output_dir>/<name>_report.{txt,json}`
+pub fn write_report(
+    output: &OutputArgs,
+    name: &str,
+    title: &str,
+    report: &Value,
+) -> Result<PathBuf, Box<dyn Error>> {
+    fs::create_dir_all(&output.output_dir)?;
+    let (path, content) = match output.format {
+        Format::Text => (
+            output.output_dir.join(format!("{}_report.txt", name)),
+            render_text(title, report),
+        ),
+        Format::Json => (
+            output.output_dir.join(format!("{}_report.json", name)),
+            serde_json::to_string_pretty(report)?,
+        ),
+    };
+    fs::write(&path, content)?;
+    println!("Report saved to {}", path.display());
+    Ok(path)
+}
+
+/// 文本格式：标题 + 每行一个 `键: 值`，嵌套对象缩进两格
+fn render_text(title: &str, report: &Value) -> String {
+    let mut text = format!("{}\n{}\n\n", title, "=".repeat(title.chars().count()));
+    render_fields(&mut text, report, 0);
+    text
+}
+
+fn render_fields(text: &mut String, value: &Value, indent: usize) {
+    let Value::Object(fields) = value else {
+        text.push_str(&format!("{:indent$}{}\n", "", format_scalar(value), indent = indent));
+        return;
+    };
+    for (key, field) in fields {
+        if field.is_object() {
+            text.push_str(&format!("{:indent$}{}:\n", "", key, indent = indent));
+            render_fields(text, field, indent + 2);
+        } else if let Some(items) = field.as_array().filter(|items| items.iter().any(Value::is_object)) {
+            for (i, item) in items.iter().enumerate() {
+                text.push_str(&format!("{:indent$}{}[{}]:\n", "", key, i, indent = indent));
+                render_fields(text, item, indent + 2);
+            }
+        } else {
+            text.push_str(&format!(
+                "{:indent$}{}: {}\n",
+                "",
+                key,
+                format_scalar(field),
+                indent = indent
+            ));
+        }
+    }
+}
+
+fn format_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(format_scalar).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_text() {
+        let report = json!({
+            "f_d": 3000.5,
+            "method": "manual",
+            "highpass": { "order": 8, "b": [1.0, -1.0] },
+            "candidates": [{ "frequency": 3000.0 }, { "frequency": 1500.0 }],
+        });
+        let text = render_text("Report", &report);
+
+        assert!(text.starts_with("Report\n======\n\n"));
+        assert!(text.contains("f_d: 3000.5\n"));
+        assert!(text.contains("method: manual\n"));
+        assert!(text.contains("highpass:\n  order: 8\n  b: 1.0, -1.0\n"));
+        assert!(text.contains("candidates[1]:\n  frequency: 1500.0\n"));
+    }
+}
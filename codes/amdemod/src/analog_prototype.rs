@@ -0,0 +1,412 @@
+// 模拟低通原型模块
+// 截止角频率归一化为 1 rad/s 的各族模拟低通原型（零极点增益），
+// 与 MATLAB buttap / cheb1ap / cheb2ap / ellipap / besselap 对应。
+// 数字滤波器由原型经频率缩放和双线性变换得到，见 `butterworth_filter::design_zpk`
+
+use crate::butterworth_filter::butterworth_analog_poles;
+use crate::zpk::{self, Zpk};
+use num_complex::Complex;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+use std::fmt;
+
+/// 判定 Jacobi 椭圆函数值为零、极点为实数时使用的容差
+const EPSILON: f64 = 2e-16;
+
+/// 滤波器族，决定归一化频率 1 处的含义
+///
+/// - Butterworth、Bessel：截止频率处衰减 3 dB
+/// - Chebyshev I、椭圆：截止频率为通带边缘，衰减恰为通带波纹 `ripple_db`
+/// - Chebyshev II：截止频率为阻带边缘，衰减恰为阻带衰减 `attenuation_db`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum FilterFamily {
+    /// 通带最平
+    #[default]
+    Butterworth,
+    /// 通带等波纹，过渡带比同阶 Butterworth 陡
+    Chebyshev1 { ripple_db: f64 },
+    /// 阻带等波纹，通带单调
+    Chebyshev2 { attenuation_db: f64 },
+    /// 通带与阻带均等波纹（Cauer），同阶下过渡带最窄
+    Elliptic { ripple_db: f64, attenuation_db: f64 },
+    /// 群时延最平，过渡带最缓
+    Bessel,
+}
+
+impl FilterFamily {
+    pub fn is_butterworth(&self) -> bool {
+        matches!(self, FilterFamily::Butterworth)
+    }
+
+    /// 检查波纹与衰减参数
+    pub fn validate(&self) -> Result<(), String> {
+        let positive = |name: &str, value: f64| {
+            if value.is_finite() && value > 0.0 {
+                Ok(())
+            } else {
+                Err(format!("{} must be positive and finite, got {}", name, value))
+            }
+        };
+        match *self {
+            FilterFamily::Butterworth | FilterFamily::Bessel => Ok(()),
+            FilterFamily::Chebyshev1 { ripple_db } => positive("ripple_db", ripple_db),
+            FilterFamily::Chebyshev2 { attenuation_db } => positive("attenuation_db", attenuation_db),
+            FilterFamily::Elliptic { ripple_db, attenuation_db } => {
+                positive("ripple_db", ripple_db)?;
+                positive("attenuation_db", attenuation_db)?;
+                if attenuation_db <= ripple_db {
+                    return Err(format!(
+                        "attenuation_db = {} must exceed ripple_db = {}",
+                        attenuation_db, ripple_db
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 截止频率处的幅度增益（线性）
+    pub fn cutoff_gain(&self) -> f64 {
+        match *self {
+            FilterFamily::Butterworth | FilterFamily::Bessel => FRAC_1_SQRT_2,
+            FilterFamily::Chebyshev1 { ripple_db } | FilterFamily::Elliptic { ripple_db, .. } => {
+                10f64.powf(-ripple_db / 20.0)
+            }
+            FilterFamily::Chebyshev2 { attenuation_db } => 10f64.powf(-attenuation_db / 20.0),
+        }
+    }
+}
+
+impl fmt::Display for FilterFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterFamily::Butterworth => write!(f, "Butterworth"),
+            FilterFamily::Chebyshev1 { ripple_db } => write!(f, "Chebyshev I ({} dB ripple)", ripple_db),
+            FilterFamily::Chebyshev2 { attenuation_db } => {
+                write!(f, "Chebyshev II ({} dB stopband)", attenuation_db)
+            }
+            FilterFamily::Elliptic { ripple_db, attenuation_db } => {
+                write!(f, "Elliptic ({} dB ripple, {} dB stopband)", ripple_db, attenuation_db)
+            }
+            FilterFamily::Bessel => write!(f, "Bessel"),
+        }
+    }
+}
+
+/// 归一化模拟低通原型 H(s) = k·Π(s - z_i) / Π(s - p_i)
+pub fn prototype(family: &FilterFamily, order: usize) -> Result<Zpk, String> {
+    family.validate()?;
+    if order == 0 {
+        return Err("filter order must be at least 1".to_string());
+    }
+    Ok(match *family {
+        FilterFamily::Butterworth => Zpk::new(Vec::new(), butterworth_analog_poles(order), 1.0),
+        FilterFamily::Chebyshev1 { ripple_db } => chebyshev1(order, ripple_db),
+        FilterFamily::Chebyshev2 { attenuation_db } => chebyshev2(order, attenuation_db),
+        FilterFamily::Elliptic { ripple_db, attenuation_db } => elliptic(order, ripple_db, attenuation_db),
+        FilterFamily::Bessel => bessel(order),
+    })
+}
+
+/// Chebyshev I 型：极点位于椭圆上，偶数阶时直流增益为 -rp dB
+fn chebyshev1(order: usize, ripple_db: f64) -> Zpk {
+    let n = order as f64;
+    let eps = (10f64.powf(0.1 * ripple_db) - 1.0).sqrt();
+    let mu = (1.0 / eps).asinh() / n;
+    let poles: Vec<Complex<f64>> = (0..order)
+        .map(|i| {
+            let theta = PI * (2.0 * i as f64 - n + 1.0) / (2.0 * n);
+            -Complex::new(mu, theta).sinh()
+        })
+        .collect();
+    let mut gain = poles.iter().map(|p| -p).product::<Complex<f64>>().re;
+    if order.is_multiple_of(2) {
+        gain /= (1.0 + eps * eps).sqrt();
+    }
+    Zpk::new(Vec::new(), poles, gain)
+}
+
+/// Chebyshev II 型：Chebyshev I 极点取倒数，零点位于虚轴上
+fn chebyshev2(order: usize, attenuation_db: f64) -> Zpk {
+    let n = order as f64;
+    let de = 1.0 / (10f64.powf(0.1 * attenuation_db) - 1.0).sqrt();
+    let mu = (1.0 / de).asinh() / n;
+
+    // 奇数阶时中间一项对应 s = ∞ 处的零点
+    let zeros: Vec<Complex<f64>> = (0..order)
+        .map(|i| 2.0 * i as f64 - n + 1.0)
+        .filter(|&m| m != 0.0)
+        .map(|m| Complex::new(0.0, 1.0 / (m * PI / (2.0 * n)).sin()))
+        .collect();
+    let poles: Vec<Complex<f64>> = (0..order)
+        .map(|i| {
+            let p = -Complex::from_polar(1.0, PI * (2.0 * i as f64 - n + 1.0) / (2.0 * n));
+            1.0 / Complex::new(mu.sinh() * p.re, mu.cosh() * p.im)
+        })
+        .collect();
+    let gain = (poles.iter().map(|p| -p).product::<Complex<f64>>()
+        / zeros.iter().map(|z| -z).product::<Complex<f64>>())
+    .re;
+    Zpk::new(zeros, poles, gain)
+}
+
+/// 椭圆（Cauer）滤波器：由 Jacobi 椭圆函数给出零极点（Orfanidis 的方法）
+fn elliptic(order: usize, ripple_db: f64, attenuation_db: f64) -> Zpk {
+    let eps_sq = 10f64.powf(0.1 * ripple_db) - 1.0;
+    if order == 1 {
+        let p = -(1.0 / eps_sq).sqrt();
+        return Zpk::new(Vec::new(), vec![Complex::new(p, 0.0)], -p);
+    }
+    let n = order as f64;
+    let eps = eps_sq.sqrt();
+    let ck1_sq = eps_sq / (10f64.powf(0.1 * attenuation_db) - 1.0);
+    let m = elliptic_degree(order, ck1_sq);
+    let capk = ellipk(m);
+
+    let jacobi: Vec<(f64, f64, f64)> = (1 - order % 2..order)
+        .step_by(2)
+        .map(|j| ellipj(j as f64 * capk / n, m))
+        .collect();
+
+    let mut zeros: Vec<Complex<f64>> = jacobi
+        .iter()
+        .filter(|(s, _, _)| s.abs() > EPSILON)
+        .map(|(s, _, _)| Complex::new(0.0, 1.0 / (m.sqrt() * s)))
+        .collect();
+    zeros.extend(zeros.clone().iter().map(|z| z.conj()));
+
+    let r = arc_jac_sc1(1.0 / eps, ck1_sq);
+    let v0 = capk * r / (n * ellipk(ck1_sq));
+    let (sv, cv, dv) = ellipj(v0, 1.0 - m);
+    let mut poles: Vec<Complex<f64>> = jacobi
+        .iter()
+        .map(|&(s, c, d)| -Complex::new(c * d * sv * cv, s * dv) / (1.0 - (d * sv).powi(2)))
+        .collect();
+    let scale = poles.iter().map(|p| p.norm_sqr()).sum::<f64>().sqrt();
+    let conjugates: Vec<Complex<f64>> = poles
+        .iter()
+        .filter(|p| p.im.abs() > EPSILON * scale)
+        .map(|p| p.conj())
+        .collect();
+    poles.extend(conjugates);
+
+    let mut gain = (poles.iter().map(|p| -p).product::<Complex<f64>>()
+        / zeros.iter().map(|z| -z).product::<Complex<f64>>())
+    .re;
+    if order.is_multiple_of(2) {
+        gain /= (1.0 + eps_sq).sqrt();
+    }
+    Zpk::new(zeros, poles, gain)
+}
+
+/// Bessel 滤波器：反向 Bessel 多项式的根，按截止频率处 -3 dB 归一化（SciPy besselap 的 norm='mag'）
+fn bessel(order: usize) -> Zpk {
+    // θ_N(s) = Σ a_k s^k，a_k = (2N-k)! / (2^{N-k} k! (N-k)!)，a_N = 1，按降幂排列
+    let mut coefficients = vec![1.0];
+    for k in (1..=order).rev() {
+        let previous = coefficients[coefficients.len() - 1];
+        coefficients.push(previous * (2 * order - k + 1) as f64 * k as f64 / (2 * (order - k + 1)) as f64);
+    }
+    let mut poles: Vec<Complex<f64>> = Vec::with_capacity(order);
+    for p in zpk::roots(&coefficients) {
+        // 求根结果只是近似共轭，这里显式配对，保证后续分成二阶节
+        let tol = 1e-8 * p.norm();
+        if p.im > tol {
+            poles.extend([p, p.conj()]);
+        } else if p.im.abs() <= tol {
+            poles.push(Complex::new(p.re, 0.0));
+        }
+    }
+
+    // |H(jω)| 单调下降：先倍增找到区间，再二分求 -3 dB 频率
+    let response = |w: f64| {
+        let s = Complex::new(0.0, w);
+        poles.iter().map(|p| -p / (s - p)).product::<Complex<f64>>().norm()
+    };
+    let mut high = 1.0;
+    while response(high) > FRAC_1_SQRT_2 {
+        high *= 2.0;
+    }
+    let mut low = 0.0;
+    for _ in 0..200 {
+        let mid = 0.5 * (low + high);
+        if response(mid) > FRAC_1_SQRT_2 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    let w3db = 0.5 * (low + high);
+
+    let poles: Vec<Complex<f64>> = poles.iter().map(|p| p / w3db).collect();
+    let gain = poles.iter().map(|p| -p).product::<Complex<f64>>().re;
+    Zpk::new(Vec::new(), poles, gain)
+}
+
+/// 算术-几何平均
+fn agm(mut a: f64, mut b: f64) -> f64 {
+    for _ in 0..64 {
+        if (a - b).abs() <= f64::EPSILON * a {
+            break;
+        }
+        (a, b) = (0.5 * (a + b), (a * b).sqrt());
+    }
+    a
+}
+
+/// 第一类完全椭圆积分 K(m)，m = k² 为参数
+pub(crate) fn ellipk(m: f64) -> f64 {
+    PI / (2.0 * agm(1.0, (1.0 - m).sqrt()))
+}
+
+/// K(1 - p)，p 很小时不损失精度
+pub(crate) fn ellipkm1(p: f64) -> f64 {
+    PI / (2.0 * agm(1.0, p.sqrt()))
+}
+
+/// Jacobi 椭圆函数 (sn, cn, dn)(u | m)，降阶 Landen 变换（Abramowitz & Stegun 16.4）
+fn ellipj(u: f64, m: f64) -> (f64, f64, f64) {
+    if m >= 1.0 {
+        let sech = 1.0 / u.cosh();
+        return (u.tanh(), sech, sech);
+    }
+    let mut a = vec![1.0];
+    let mut c = vec![m.sqrt()];
+    let mut b = (1.0 - m).sqrt();
+    while c[c.len() - 1].abs() > f64::EPSILON && a.len() < 32 {
+        let an = a[a.len() - 1];
+        a.push(0.5 * (an + b));
+        c.push(0.5 * (an - b));
+        b = (an * b).sqrt();
+    }
+    let steps = a.len() - 1;
+    let mut phi = 2f64.powi(steps as i32) * a[steps] * u;
+    let mut next = phi;
+    for i in (1..=steps).rev() {
+        next = phi;
+        phi = 0.5 * (phi + (c[i] / a[i] * phi.sin()).asin());
+    }
+    let (sn, cn) = phi.sin_cos();
+    let dn = if steps == 0 { 1.0 } else { cn / (next - phi).cos() };
+    (sn, cn, dn)
+}
+
+/// 由阶数与判别参数 m1 求选择性参数 m（椭圆度方程，nome 级数）
+fn elliptic_degree(order: usize, m1: f64) -> f64 {
+    let q1 = (-PI * ellipkm1(m1) / ellipk(m1)).exp();
+    let q = q1.powf(1.0 / order as f64);
+    let num: f64 = (0..=7).map(|k: i32| q.powi(k * (k + 1))).sum();
+    let den = 1.0 + 2.0 * (1..=8).map(|k: i32| q.powi(k * k)).sum::<f64>();
+    16.0 * q * (num / den).powi(4)
+}
+
+/// 复数域上的反 Jacobi sn 函数，升阶 Landen 变换
+fn arc_jac_sn(w: Complex<f64>, m: f64) -> Complex<f64> {
+    let complement = |x: Complex<f64>| ((1.0 - x) * (1.0 + x)).sqrt();
+    let k = m.sqrt();
+    if k >= 1.0 {
+        return w.atanh();
+    }
+    let mut ks = vec![k];
+    while ks[ks.len() - 1] != 0.0 && ks.len() < 32 {
+        let kn = ks[ks.len() - 1];
+        let kp = ((1.0 - kn) * (1.0 + kn)).sqrt();
+        ks.push((1.0 - kp) / (1.0 + kp));
+    }
+    let capk = ks[1..].iter().map(|kn| 1.0 + kn).product::<f64>() * PI / 2.0;
+    let wn = ks.windows(2).fold(w, |wn, pair| {
+        2.0 * wn / ((1.0 + pair[1]) * (1.0 + complement(wn * pair[0])))
+    });
+    capk * 2.0 / PI * wn.asin()
+}
+
+/// 实数 w 的反 Jacobi sc 函数（补参数）：sc(u | 1 - m) = w 的实数解
+fn arc_jac_sc1(w: f64, m: f64) -> f64 {
+    arc_jac_sn(Complex::new(0.0, w), m).im
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn magnitude(prototype: &Zpk, w: f64) -> f64 {
+        prototype.evaluate(Complex::new(0.0, w)).norm()
+    }
+
+    fn db(x: f64) -> f64 {
+        20.0 * x.log10()
+    }
+
+    #[test]
+    fn test_prototypes_meet_their_specifications() {
+        let grid = |from: f64, to: f64| (0..=4000).map(move |i| from + (to - from) * i as f64 / 4000.0);
+        for order in [3, 4, 5, 8] {
+            // 截止频率处的增益由族决定；所有极点都在左半平面
+            let families = [
+                FilterFamily::Butterworth,
+                FilterFamily::Chebyshev1 { ripple_db: 1.0 },
+                FilterFamily::Chebyshev2 { attenuation_db: 40.0 },
+                FilterFamily::Elliptic { ripple_db: 0.5, attenuation_db: 60.0 },
+                FilterFamily::Bessel,
+            ];
+            for family in families {
+                let h = prototype(&family, order).unwrap();
+                assert_eq!(h.poles.len(), order);
+                assert!(h.poles.iter().all(|p| p.re < 0.0), "{} order {}", family, order);
+                assert!((magnitude(&h, 1.0) - family.cutoff_gain()).abs() < 1e-9, "{} order {}", family, order);
+            }
+
+            // Chebyshev I：通带在 [-rp, 0] dB 之间等波纹
+            let h = prototype(&FilterFamily::Chebyshev1 { ripple_db: 1.0 }, order).unwrap();
+            let passband: Vec<f64> = grid(0.0, 1.0).map(|w| db(magnitude(&h, w))).collect();
+            assert!(passband.iter().all(|&g| (-1.0 - 1e-9..=1e-9).contains(&g)));
+            assert!(passband.iter().cloned().fold(f64::MIN, f64::max) > -1e-4);
+
+            // Chebyshev II：阻带不高于 -rs dB 且达到该值
+            let h = prototype(&FilterFamily::Chebyshev2 { attenuation_db: 40.0 }, order).unwrap();
+            let stopband: Vec<f64> = grid(1.0, 50.0).map(|w| db(magnitude(&h, w))).collect();
+            assert!(stopband.iter().all(|&g| g <= -40.0 + 1e-6));
+            assert!(stopband.iter().cloned().fold(f64::MIN, f64::max) > -40.1);
+            assert!((magnitude(&h, 0.0) - 1.0).abs() < 1e-9);
+
+            // 椭圆：通带 [-rp, 0] dB，阻带从某一频率起不高于 -rs dB 且等波纹
+            let h = prototype(&FilterFamily::Elliptic { ripple_db: 0.5, attenuation_db: 60.0 }, order).unwrap();
+            assert!(grid(0.0, 1.0).all(|w| (-0.5 - 1e-9..=1e-9).contains(&db(magnitude(&h, w)))));
+            let edge = grid(1.0, 50.0).find(|&w| db(magnitude(&h, w)) <= -60.0).unwrap();
+            let stopband: Vec<f64> = grid(edge, 500.0).map(|w| db(magnitude(&h, w))).collect();
+            assert!(stopband.iter().all(|&g| g <= -60.0 + 1e-6), "order {}", order);
+            assert!(stopband.iter().cloned().fold(f64::MIN, f64::max) > -60.1);
+        }
+    }
+
+    #[test]
+    fn test_bessel_matches_known_polynomial() {
+        // θ_3(s) = s³ + 6s² + 15s + 15：原型极点乘以 -3 dB 频率后应是其根，根之积的模为 15
+        let h = prototype(&FilterFamily::Bessel, 3).unwrap();
+        let w3db = (15.0 / h.poles.iter().map(|p| p.norm()).product::<f64>()).cbrt();
+        for p in &h.poles {
+            let s = p * w3db;
+            let value = s * s * s + 6.0 * s * s + 15.0 * s + 15.0;
+            assert!(value.norm() < 1e-9, "{}", value);
+        }
+        assert!((magnitude(&h, 0.0) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_elliptic_functions() {
+        // m = 0 与 m = 1 的退化情形，以及 sn² + cn² = 1、dn² + m·sn² = 1
+        let (sn, cn, dn) = ellipj(0.7, 0.0);
+        assert!((sn - 0.7f64.sin()).abs() < 1e-15 && (cn - 0.7f64.cos()).abs() < 1e-15 && dn == 1.0);
+        assert!((ellipk(0.0) - PI / 2.0).abs() < 1e-15);
+        // K(0.5) = 1.854074677301372
+        assert!((ellipk(0.5) - 1.854074677301372).abs() < 1e-13);
+        for (u, m) in [(0.3, 0.2), (1.1, 0.9), (1.0, 0.999999)] {
+            let (sn, cn, dn) = ellipj(u, m);
+            assert!((sn * sn + cn * cn - 1.0).abs() < 1e-13);
+            assert!((dn * dn + m * sn * sn - 1.0).abs() < 1e-13);
+            // sc = sn / cn 的反函数，以补参数 1 - m 给出
+            assert!((arc_jac_sc1(sn / cn, 1.0 - m) - u).abs() < 1e-9, "u = {}, m = {}", u, m);
+        }
+    }
+}
@@ -0,0 +1,307 @@
+// 连续时间 LTI 模型模块
+// 模拟滤波器 H(s) 的零极点增益、传递函数（s 的降幂系数，MATLAB tf）与状态空间三种表示，
+// H(s) 的分式打印，以及在采样输入上仿真连续系统的 lsim（可选 ZOH、FOH、Tustin、零极点匹配、冲激响应不变）
+
+use crate::state_space::{Discretization, StateSpace};
+use crate::zpk::{poly, roots, Zpk};
+use num_complex::Complex;
+use std::f64::consts::PI;
+use std::fmt;
+
+/// 连续时间单输入单输出系统 H(s) = k·Π(s - z_i) / Π(s - p_i)
+///
+/// 以零极点为基本表示：滤波器设计直接给出零极点，由传递函数系数求根在高阶时是病态的。
+/// 增益另存 ln|k|：高阶滤波器的 k ≈ wc^N 超出 f64 范围，求值和实现时按极点或节分摊
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalogSystem {
+    zpk: Zpk,
+    log_gain: f64,
+}
+
+impl AnalogSystem {
+    /// 检查零点不多于极点（真有理函数）、复根成对共轭、增益有限
+    pub fn from_zpk(zpk: Zpk) -> Result<Self, String> {
+        if !zpk.gain.is_finite() {
+            return Err(format!(
+                "增益 {} 不是有限值（{} 阶模拟滤波器的增益约为 wc^N，阶数过高时溢出，改用 with_gain_at）",
+                zpk.gain,
+                zpk.poles.len()
+            ));
+        }
+        if zpk.zeros.iter().chain(&zpk.poles).any(|r| !r.is_finite()) {
+            return Err("零极点必须是有限值".to_string());
+        }
+        zpk.to_analog_sos()?;
+        Ok(Self { log_gain: zpk.gain.abs().ln(), zpk })
+    }
+
+    /// 保持零极点不变，确定增益使 H(s) 在 s 处取给定的实数值（同 `Zpk::with_gain_at`）
+    ///
+    /// 增益在对数域求得，k 超出 f64 范围时 `zpk().gain` 为 ±∞，但求值、状态空间实现和仿真不受影响
+    pub fn with_gain_at(zpk: Zpk, s: Complex<f64>, value: f64) -> Result<Self, String> {
+        if zpk.zeros.iter().chain(&zpk.poles).any(|r| !r.is_finite()) {
+            return Err("零极点必须是有限值".to_string());
+        }
+        let unit = Zpk { gain: 1.0, ..zpk };
+        let log_unit = unit.zeros.iter().map(|z| (s - z).norm().ln()).sum::<f64>()
+            - unit.poles.iter().map(|p| (s - p).norm().ln()).sum::<f64>();
+        let phase = unit.zeros.iter().map(|z| (s - z).arg()).sum::<f64>()
+            - unit.poles.iter().map(|p| (s - p).arg()).sum::<f64>();
+        let log_gain = value.abs().ln() - log_unit;
+        if !(log_gain.is_finite() && value != 0.0) {
+            return Err(format!("无法在 s = {} 处把响应归一化到 {}", s, value));
+        }
+        let sign = (value * phase.cos()).signum();
+        let zpk = Zpk { gain: log_gain.exp() * sign, ..unit };
+        zpk.to_analog_sos_log_gain(log_gain)?;
+        Ok(Self { zpk, log_gain })
+    }
+
+    /// 由 s 的降幂系数 num、den 构造（MATLAB `tf(num, den)`）
+    pub fn from_tf(num: &[f64], den: &[f64]) -> Result<Self, String> {
+        let den_lead = den.iter().copied().find(|&v| v != 0.0).ok_or("分母 den 全为零")?;
+        let num_lead = num.iter().copied().find(|&v| v != 0.0).unwrap_or(0.0);
+        let zeros = if num_lead == 0.0 { Vec::new() } else { roots(num) };
+        Self::from_zpk(Zpk::new(zeros, roots(den), num_lead / den_lead))
+    }
+
+    /// 由连续状态空间模型构造（`ss2zp`）
+    pub fn from_state_space(system: &StateSpace) -> Result<Self, String> {
+        if system.is_discrete() {
+            return Err("离散状态空间模型不是模拟系统".to_string());
+        }
+        Self::from_zpk(system.to_zpk()?)
+    }
+
+    /// 零极点增益；k 超出 f64 范围时 gain 为 ±∞
+    pub fn zpk(&self) -> &Zpk {
+        &self.zpk
+    }
+
+    /// 阶数（极点数）
+    pub fn order(&self) -> usize {
+        self.zpk.poles.len()
+    }
+
+    /// s 的降幂系数 (num, den)，den 首一，num 补零到与 den 等长（MATLAB `tfdata`）
+    pub fn tf(&self) -> (Vec<f64>, Vec<f64>) {
+        let den: Vec<f64> = poly(&self.zpk.poles).iter().map(|c| c.re).collect();
+        let mut num = vec![0.0; self.zpk.poles.len() - self.zpk.zeros.len()];
+        num.extend(poly(&self.zpk.zeros).iter().map(|c| c.re * self.zpk.gain));
+        (num, den)
+    }
+
+    /// 状态空间实现：s 域二阶节（`Zpk::to_analog_sos`）逐节串联
+    pub fn state_space(&self) -> Result<StateSpace, String> {
+        StateSpace::from_sections(&self.zpk.to_analog_sos_log_gain(self.log_gain)?, None)
+    }
+
+    /// 在复频率 s 处求 H(s)
+    pub fn evaluate(&self, s: Complex<f64>) -> Complex<f64> {
+        self.zpk.evaluate_log_gain(s, self.log_gain)
+    }
+
+    /// 频率 f (Hz) 处的响应 H(j2πf)
+    pub fn frequency_response(&self, frequency: f64) -> Complex<f64> {
+        self.evaluate(Complex::new(0.0, 2.0 * PI * frequency))
+    }
+
+    /// 按指定方法离散化为采样率 sample_rate 的离散状态空间模型（MATLAB `c2d`）
+    pub fn discretize(&self, sample_rate: f64, method: Discretization) -> Result<StateSpace, String> {
+        match method {
+            Discretization::Matched => {
+                StateSpace::from_sections(&self.matched_zpk(sample_rate)?.to_sos()?, Some(sample_rate))
+            }
+            _ => self.state_space()?.discretize(sample_rate, method),
+        }
+    }
+
+    /// 在采样率为 sample_rate 的输入序列上仿真连续系统，初始状态为零（MATLAB `lsim(sys, u, t)`）
+    ///
+    /// input[k] 是 t = k/f_s 时刻的输入，method 决定样本之间的输入如何重建
+    pub fn lsim(&self, input: &[f64], sample_rate: f64, method: Discretization) -> Result<Vec<f64>, String> {
+        self.discretize(sample_rate, method)?.simulate(input)
+    }
+
+    /// 零极点匹配：z = e^{sT}，s = ∞ 处的零点放在 z = -1（Nyquist 频率）
+    ///
+    /// 直流增益非零时在直流处匹配增益；高通、带通等直流为零的系统在模拟幅度最大的频率处匹配幅度
+    fn matched_zpk(&self, sample_rate: f64) -> Result<Zpk, String> {
+        if !(sample_rate.is_finite() && sample_rate > 0.0) {
+            return Err(format!("采样率必须为正数，得到 {}", sample_rate));
+        }
+        let map = |s: &Complex<f64>| (s / sample_rate).exp();
+        let mut zeros: Vec<Complex<f64>> = self.zpk.zeros.iter().map(map).collect();
+        zeros.resize(self.zpk.poles.len(), Complex::new(-1.0, 0.0));
+        let unit = Zpk::new(zeros, self.zpk.poles.iter().map(map).collect(), 1.0);
+
+        // 对数频率网格 (1e-4, 1)·f_s/2 上的幅度最大点
+        let peak = (0..=512)
+            .map(|i| 0.499 * sample_rate * 10f64.powf(-4.0 + 4.0 * i as f64 / 512.0))
+            .max_by(|&a, &b| self.frequency_response(a).norm().total_cmp(&self.frequency_response(b).norm()))
+            .expect("网格非空");
+        let dc = self.frequency_response(0.0).norm();
+        let frequency = if dc.is_finite() && dc > 1e-6 * self.frequency_response(peak).norm() {
+            0.0
+        } else {
+            peak
+        };
+        let analog = self.frequency_response(frequency);
+        let digital = unit.evaluate(Complex::from_polar(1.0, 2.0 * PI * frequency / sample_rate));
+        let ratio = analog / digital;
+        if !(ratio.is_finite() && ratio.norm() > 0.0) {
+            return Err("零极点匹配无法确定增益".to_string());
+        }
+        Ok(Zpk { gain: ratio.norm().copysign(ratio.re), ..unit })
+    }
+}
+
+impl fmt::Display for AnalogSystem {
+    /// 按分式打印 H(s)，系数保留 5 位有效数字
+    ///
+    /// ```text
+    ///                    1.5585e17
+    /// H(s) = ---------------------------------
+    ///        s^4 + 65328 s^3 + ... + 1.5585e17
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (num, den) = self.tf();
+        let numerator = format_polynomial(&num);
+        let denominator = format_polynomial(&den);
+        let width = numerator.chars().count().max(denominator.chars().count());
+        let center = |text: &str| format!("{}{}", " ".repeat((width - text.chars().count()) / 2), text);
+        writeln!(f, "       {}", center(&numerator))?;
+        writeln!(f, "H(s) = {}", "-".repeat(width))?;
+        write!(f, "       {}", center(&denominator))
+    }
+}
+
+/// s 的降幂系数打印为 "s^2 + 1.4142 s + 1"，零系数的项省略
+///
+/// 模拟滤波器各项系数的量级可以相差 wc^N 倍，不能按相对大小舍弃小系数
+fn format_polynomial(coefficients: &[f64]) -> String {
+    let degree = coefficients.len().saturating_sub(1);
+    let mut text = String::new();
+    for (i, &c) in coefficients.iter().enumerate() {
+        if c == 0.0 {
+            continue;
+        }
+        let power = degree - i;
+        let magnitude = c.abs();
+        let sign = if c < 0.0 { "-" } else { "+" };
+        if text.is_empty() {
+            if c < 0.0 {
+                text.push('-');
+            }
+        } else {
+            text.push_str(&format!(" {} ", sign));
+        }
+        let variable = match power {
+            0 => String::new(),
+            1 => "s".to_string(),
+            _ => format!("s^{}", power),
+        };
+        if power > 0 && (magnitude - 1.0).abs() < 1e-12 {
+            text.push_str(&variable);
+        } else if power > 0 {
+            text.push_str(&format!("{} {}", format_coefficient(magnitude), variable));
+        } else {
+            text.push_str(&format_coefficient(magnitude));
+        }
+    }
+    if text.is_empty() {
+        "0".to_string()
+    } else {
+        text
+    }
+}
+
+/// 5 位有效数字；量级在 [1e-3, 1e5) 之外时用科学计数法
+fn format_coefficient(value: f64) -> String {
+    let magnitude = value.abs();
+    if (1e-3..1e5).contains(&magnitude) {
+        let decimals = (4 - magnitude.log10().floor() as i32).max(0) as usize;
+        let text = format!("{:.*}", decimals, value);
+        if text.contains('.') {
+            text.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            text
+        }
+    } else {
+        format!("{:.4e}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analog_prototype::FilterFamily;
+    use crate::butterworth_filter::{analog_zpk, FilterType, IirDesign};
+
+    #[test]
+    fn test_tf_round_trip_and_display() {
+        // 二阶 Butterworth：1 / (s² + √2 s + 1)
+        let system = AnalogSystem::from_tf(&[1.0], &[1.0, std::f64::consts::SQRT_2, 1.0]).unwrap();
+        assert_eq!(system.order(), 2);
+        assert!((system.frequency_response(1.0 / (2.0 * PI)).norm() - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+        let (num, den) = system.tf();
+        assert_eq!(num.len(), 3);
+        assert!((num[2] - 1.0).abs() < 1e-12 && (den[1] - std::f64::consts::SQRT_2).abs() < 1e-12);
+
+        let text = system.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].trim(), "1");
+        assert!(lines[1].starts_with("H(s) = ---"));
+        assert_eq!(lines[2].trim(), "s^2 + 1.4142 s + 1");
+        assert_eq!(format_polynomial(&[-2.0, 0.0, 1.5e7]), "-2 s^2 + 1.5000e7");
+        assert_eq!(format_polynomial(&[1.0, 2e-4, 0.0]), "s^2 + 2.0000e-4 s");
+
+        // 零点多于极点不是真有理函数
+        assert!(AnalogSystem::from_tf(&[1.0, 0.0], &[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_tustin_lsim_matches_digital_design() {
+        // 以预畸变后的截止角频率构造模拟高通（lp2hp），Tustin 离散化即双线性变换，
+        // 应与镜像法设计的数字高通给出相同的输出
+        let (fs, fc) = (22050.0, 3000.0);
+        let family = FilterFamily::Chebyshev1 { ripple_db: 1.0 };
+        let prewarped = 2.0 * fs * (PI * fc / fs).tan();
+        let analog = AnalogSystem::from_zpk(analog_zpk(&family, 6, &[prewarped], FilterType::Highpass).unwrap()).unwrap();
+        let digital = IirDesign::with_family(family, 6, fc, fs, FilterType::Highpass).unwrap();
+
+        let input: Vec<f64> = (0..3000).map(|i| (i as f64 * 0.37).sin() + ((i * 7) % 11) as f64 * 0.05).collect();
+        let simulated = analog.lsim(&input, fs, Discretization::Tustin).unwrap();
+        let expected = digital.to_iir_filter().unwrap().process_block(&input);
+        let peak = expected.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+        let error = simulated.iter().zip(&expected).fold(0.0f64, |m, (a, b)| m.max((a - b).abs()));
+        assert!(error < 1e-9 * peak, "{}", error);
+
+        // 高通有直通项，冲激响应不变法不适用
+        assert!(analog.lsim(&input, fs, Discretization::Impulse).is_err());
+    }
+
+    #[test]
+    fn test_discretizations_of_designed_lowpass() {
+        // 未预畸变的 8 阶模拟低通：各方法的阶跃响应都收敛到直流增益 1，
+        // 采样率远高于截止频率时各方法彼此接近
+        let fs = 22050.0;
+        let analog = IirDesign::lowpass(8, 400.0, fs).analog().unwrap();
+        assert!((analog.frequency_response(400.0).norm() - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+        let step = vec![1.0; 4000];
+        let reference = analog.lsim(&step, fs, Discretization::Zoh).unwrap();
+        for method in [Discretization::Foh, Discretization::Tustin, Discretization::Matched, Discretization::Impulse] {
+            let y = analog.lsim(&step, fs, method).unwrap();
+            assert!((y[3999] - 1.0).abs() < 1e-3, "{}: {}", method, y[3999]);
+            let error = y.iter().zip(&reference).fold(0.0f64, |m, (a, b)| m.max((a - b).abs()));
+            assert!(error < 0.05, "{}: {}", method, error);
+        }
+
+        // 零极点匹配：极点 e^{pT}
+        let matched = analog.matched_zpk(fs).unwrap();
+        for (pd, pa) in matched.poles.iter().zip(&analog.zpk().poles) {
+            assert!((pd - (pa / fs).exp()).norm() < 1e-12);
+        }
+    }
+}
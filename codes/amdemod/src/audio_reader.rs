@@ -0,0 +1,197 @@
+// 1. 音频文件读取模块
+// 负责读取 WAV 文件并提取采样数据、采样率和样本数
+
+use hound::{WavReader, WavSpec};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// 音频数据结构
+#[derive(Debug, Clone)]
+pub struct AudioData {
+    /// 采样数据（归一化为浮点数）
+    pub samples: Vec<f64>,
+    /// 采样率 (Hz)
+    pub sample_rate: u32,
+    /// 样本数
+    pub num_samples: usize,
+    /// WAV 文件规格
+    pub spec: WavSpec,
+}
+
+impl AudioData {
+    /// 从 WAV 文件读取音频数据
+    pub fn from_wav<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut reader = WavReader::open(path)?;
+        let spec = reader.spec();
+        let sample_rate = spec.sample_rate;
+
+        // 读取所有采样点并归一化
+        let samples: Vec<f64> = match spec.sample_format {
+            hound::SampleFormat::Float => {
+                reader
+                    .samples::<f32>()
+                    .map(|s| s.map(|v| v as f64))
+                    .collect::<Result<_, _>>()?
+            }
+            hound::SampleFormat::Int => {
+                let max_value = (1 << (spec.bits_per_sample - 1)) as f64;
+                reader
+                    .samples::<i32>()
+                    .map(|s| s.map(|v| v as f64 / max_value))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+
+        let num_samples = samples.len();
+
+        println!("音频文件读取成功:");
+        println!("  采样率: {} Hz", sample_rate);
+        println!("  样本数: {}", num_samples);
+        println!("  位深度: {} bits", spec.bits_per_sample);
+        println!("  声道数: {}", spec.channels);
+        println!("  时长: {:.2} 秒", num_samples as f64 / sample_rate as f64);
+
+        Ok(AudioData {
+            samples,
+            sample_rate,
+            num_samples,
+            spec,
+        })
+    }
+
+    /// 获取信号时长（秒）
+    pub fn duration(&self) -> f64 {
+        self.num_samples as f64 / self.sample_rate as f64
+    }
+
+    /// 获取单声道数据（如果是立体声则转换为单声道）
+    pub fn to_mono(&self) -> Vec<f64> {
+        if self.spec.channels == 1 {
+            self.samples.clone()
+        } else {
+            // 立体声转单声道：取平均
+            self.samples
+                .chunks(self.spec.channels as usize)
+                .map(|chunk| chunk.iter().sum::<f64>() / chunk.len() as f64)
+                .collect()
+        }
+    }
+
+    /// 保存为 WAV 文件（单声道 16 位，采样率与原文件相同）
+    pub fn save_wav<P: AsRef<Path>>(
+        &self,
+        path: P,
+        samples: &[f64],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::audio_writer::write_wav(path, samples, self.sample_rate)?;
+        println!("音频文件保存成功");
+        Ok(())
+    }
+}
+
+/// 读取 WAV 文件并返回单声道采样数据
+pub fn read_wav<P: AsRef<Path>>(path: P) -> Result<Vec<f64>, Box<dyn std::error::Error>> {
+    Ok(AudioData::from_wav(path)?.to_mono())
+}
+
+/// 分块读取 WAV 文件：每次返回至多 block_size 个单声道样本，不把整段录音读入内存
+///
+/// 归一化与多声道平均的方式与 [`AudioData::from_wav`] + [`AudioData::to_mono`] 相同
+pub struct WavBlockReader {
+    reader: WavReader<BufReader<File>>,
+    spec: WavSpec,
+    block_size: usize,
+}
+
+impl WavBlockReader {
+    pub fn open<P: AsRef<Path>>(path: P, block_size: usize) -> Result<Self, Box<dyn std::error::Error>> {
+        if block_size == 0 {
+            return Err("block_size 必须为正".into());
+        }
+        let reader = WavReader::open(path)?;
+        let spec = reader.spec();
+        Ok(Self { reader, spec, block_size })
+    }
+
+    pub fn spec(&self) -> WavSpec {
+        self.spec
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.spec.sample_rate
+    }
+
+    /// 每个声道的样本数（由文件头给出）
+    pub fn num_samples(&self) -> usize {
+        self.reader.duration() as usize
+    }
+
+    /// 读取下一块单声道样本，文件结束时返回 None
+    pub fn next_block(&mut self) -> Result<Option<Vec<f64>>, Box<dyn std::error::Error>> {
+        let channels = self.spec.channels as usize;
+        let count = self.block_size * channels;
+        let interleaved: Vec<f64> = match self.spec.sample_format {
+            hound::SampleFormat::Float => self
+                .reader
+                .samples::<f32>()
+                .take(count)
+                .map(|s| s.map(|v| v as f64))
+                .collect::<Result<_, _>>()?,
+            hound::SampleFormat::Int => {
+                let max_value = (1 << (self.spec.bits_per_sample - 1)) as f64;
+                self.reader
+                    .samples::<i32>()
+                    .take(count)
+                    .map(|s| s.map(|v| v as f64 / max_value))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+        if interleaved.is_empty() {
+            return Ok(None);
+        }
+        let block = if channels == 1 {
+            interleaved
+        } else {
+            interleaved
+                .chunks(channels)
+                .map(|chunk| chunk.iter().sum::<f64>() / chunk.len() as f64)
+                .collect()
+        };
+        Ok(Some(block))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_reader() {
+        // 测试读取音频文件
+        let result = AudioData::from_wav("../project.wav");
+        assert!(result.is_ok());
+
+        if let Ok(audio) = result {
+            assert!(audio.sample_rate > 0);
+            assert!(audio.num_samples > 0);
+            assert_eq!(audio.samples.len(), audio.num_samples);
+        }
+    }
+
+    #[test]
+    fn test_block_reader_matches_whole_file() {
+        let path = "../project.wav";
+        let Ok(audio) = AudioData::from_wav(path) else {
+            return;
+        };
+        let mut reader = WavBlockReader::open(path, 4000).unwrap();
+        assert_eq!(reader.num_samples(), audio.num_samples / audio.spec.channels as usize);
+        let mut samples = Vec::new();
+        while let Some(block) = reader.next_block().unwrap() {
+            assert!(block.len() <= 4000);
+            samples.extend(block);
+        }
+        assert_eq!(samples, audio.to_mono());
+    }
+}
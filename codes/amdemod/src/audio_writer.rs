@@ -0,0 +1,93 @@
+// 音频文件写入模块
+// 将处理后的信号归一化并保存为 16 位单声道 WAV 文件
+
+use std::path::Path;
+
+/// 保存为 WAV 文件（自动归一化，保留少量余量防止削波）
+pub fn write_wav<P: AsRef<Path>>(
+    path: P,
+    samples: &[f64],
+    sample_rate: u32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = hound::WavWriter::create(path, spec)?;
+
+    // Normalize samples to prevent clipping
+    let max_val = samples.iter().fold(0.0f64, |max, &x| max.max(x.abs()));
+    let scale = if max_val > 0.0 {
+        0.95 / max_val // Leave some headroom
+    } else {
+        1.0
+    };
+
+    for &sample in samples {
+        let normalized = sample * scale;
+        let sample_i16 = (normalized * 32767.0).clamp(-32768.0, 32767.0) as i16;
+        writer.write_sample(sample_i16)?;
+    }
+
+    writer.finalize()?;
+
+    Ok(())
+}
+
+/// 分块写入 16 位单声道 WAV 文件
+///
+/// 分块写入时无法预知整段信号的峰值，改用固定增益：样本乘以 gain 后超出 [-1, 1] 的部分被削波并计数
+pub struct WavBlockWriter {
+    writer: hound::WavWriter<std::io::BufWriter<std::fs::File>>,
+    gain: f64,
+    clipped: usize,
+    written: usize,
+}
+
+impl WavBlockWriter {
+    pub fn create<P: AsRef<Path>>(path: P, sample_rate: u32, gain: f64) -> Result<Self, Box<dyn std::error::Error>> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        Ok(Self {
+            writer: hound::WavWriter::create(path, spec)?,
+            gain,
+            clipped: 0,
+            written: 0,
+        })
+    }
+
+    pub fn write_block(&mut self, samples: &[f64]) -> Result<(), Box<dyn std::error::Error>> {
+        for &sample in samples {
+            let scaled = sample * self.gain;
+            if scaled.abs() > 1.0 {
+                self.clipped += 1;
+            }
+            let sample_i16 = (scaled * 32767.0).clamp(-32768.0, 32767.0) as i16;
+            self.writer.write_sample(sample_i16)?;
+        }
+        self.written += samples.len();
+        Ok(())
+    }
+
+    /// 已写入的样本数
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// 被削波的样本数
+    pub fn clipped(&self) -> usize {
+        self.clipped
+    }
+
+    pub fn finalize(self) -> Result<(), Box<dyn std::error::Error>> {
+        self.writer.finalize()?;
+        Ok(())
+    }
+}
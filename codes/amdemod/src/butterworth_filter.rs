@@ -0,0 +1,475 @@
+use crate::analog_prototype::{self, FilterFamily};
+use crate::analog_system::AnalogSystem;
+use crate::iir_filter::{IirFilter, SosSection};
+use crate::state_space::StateSpace;
+use crate::zpk::Zpk;
+use num_complex::Complex;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// 由模拟原型经双线性变换设计的数字 IIR 滤波器
+///
+/// 族（Butterworth、Chebyshev I/II、椭圆、Bessel）与类型（低通、高通、带通、带阻）均可选，
+/// 同时保存 b/a 与二阶节两种形式
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IirDesign {
+    pub b: Vec<f64>,
+    pub a: Vec<f64>,
+    /// 二阶节 [b0, b1, b2, a0, a1, a2]，由设计时的零极点直接分组；旧参数文件中没有此项
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sos: Vec<SosSection>,
+    pub order: usize,
+    /// 截止频率；带通、带阻时为下边缘
+    pub cutoff: f64,
+    /// 带通、带阻的上边缘，低通、高通没有此项
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upper_cutoff: Option<f64>,
+    pub sample_rate: f64,
+    pub filter_type: FilterType,
+    /// 模拟原型所属的族；旧参数文件中没有此项，即 Butterworth
+    #[serde(default, skip_serializing_if = "FilterFamily::is_butterworth")]
+    pub family: FilterFamily,
+}
+
+/// 旧名称：该类型早已不限于 Butterworth 族
+#[deprecated(note = "renamed to `IirDesign`")]
+pub type ButterworthFilter = IirDesign;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterType {
+    Lowpass,
+    Highpass,
+    /// 通带为 [cutoff, upper_cutoff]
+    Bandpass,
+    /// 阻带为 [cutoff, upper_cutoff]
+    Bandstop,
+}
+
+impl FilterType {
+    /// 带通、带阻由两个边缘描述，极点数为原型阶数的两倍
+    pub fn is_band(&self) -> bool {
+        matches!(self, FilterType::Bandpass | FilterType::Bandstop)
+    }
+}
+
+impl IirDesign {
+    pub fn lowpass(order: usize, cutoff: f64, sample_rate: f64) -> Self {
+        Self::with_family(FilterFamily::Butterworth, order, cutoff, sample_rate, FilterType::Lowpass)
+            .unwrap_or_else(|e| panic!("invalid Butterworth design: {}", e))
+    }
+
+    pub fn highpass(order: usize, cutoff: f64, sample_rate: f64) -> Self {
+        Self::with_family(FilterFamily::Butterworth, order, cutoff, sample_rate, FilterType::Highpass)
+            .unwrap_or_else(|e| panic!("invalid Butterworth design: {}", e))
+    }
+
+    /// 带通 Butterworth 滤波器，通带边缘 low、high 处衰减 3 dB，共 2·order 个极点
+    pub fn bandpass(order: usize, low: f64, high: f64, sample_rate: f64) -> Self {
+        Self::band_with_family(FilterFamily::Butterworth, order, low, high, sample_rate, FilterType::Bandpass)
+            .unwrap_or_else(|e| panic!("invalid Butterworth design: {}", e))
+    }
+
+    /// 带阻 Butterworth 滤波器，阻带边缘 low、high 处衰减 3 dB，几何中心处为零点
+    pub fn bandstop(order: usize, low: f64, high: f64, sample_rate: f64) -> Self {
+        Self::band_with_family(FilterFamily::Butterworth, order, low, high, sample_rate, FilterType::Bandstop)
+            .unwrap_or_else(|e| panic!("invalid Butterworth design: {}", e))
+    }
+
+    /// 按指定的族设计低通或高通，cutoff 的含义见 `FilterFamily`
+    ///
+    /// 由零极点同时得到传递函数和二阶节，二者描述同一个滤波器
+    pub fn with_family(
+        family: FilterFamily,
+        order: usize,
+        cutoff: f64,
+        sample_rate: f64,
+        filter_type: FilterType,
+    ) -> Result<Self, String> {
+        Self::design(family, order, &[cutoff], sample_rate, filter_type)
+    }
+
+    /// 按指定的族设计带通或带阻，low、high 两个边缘的含义与低通的 cutoff 相同
+    pub fn band_with_family(
+        family: FilterFamily,
+        order: usize,
+        low: f64,
+        high: f64,
+        sample_rate: f64,
+        filter_type: FilterType,
+    ) -> Result<Self, String> {
+        Self::design(family, order, &[low, high], sample_rate, filter_type)
+    }
+
+    fn design(
+        family: FilterFamily,
+        order: usize,
+        cutoffs: &[f64],
+        sample_rate: f64,
+        filter_type: FilterType,
+    ) -> Result<Self, String> {
+        let zpk = design_zpk(&family, order, cutoffs, sample_rate, filter_type)?;
+        let (b, a) = zpk.to_tf();
+        let sos = zpk.to_sos()?;
+        Ok(Self {
+            b,
+            a,
+            sos,
+            order,
+            cutoff: cutoffs[0],
+            upper_cutoff: cutoffs.get(1).copied(),
+            sample_rate,
+            filter_type,
+            family,
+        })
+    }
+
+    /// 传递函数的阶数（极点数）：带通、带阻为原型阶数的两倍
+    pub fn degree(&self) -> usize {
+        if self.filter_type.is_band() {
+            2 * self.order
+        } else {
+            self.order
+        }
+    }
+
+    /// 零极点增益表示：有二阶节时由各节求得（每节只解二次方程，数值稳定），否则由 b/a 求根
+    pub fn zpk(&self) -> Result<Zpk, String> {
+        if self.sos.is_empty() {
+            Zpk::from_tf(&self.b, &self.a)
+        } else {
+            Zpk::from_sos(&self.sos)
+        }
+    }
+
+    /// 对应的连续时间滤波器 H(s)（MATLAB `butter(n, Wn, 's')`）：同一原型按未预畸变的角频率 2π·f_c 变换，
+    /// 与数字滤波器只在截止频率处一致，双线性变换的频率压缩使二者在高频处逐渐偏离。
+    /// 与 [`design_zpk`] 一样，增益取自原型在 s = 0 的响应（低通、带阻的直流，带通的中心 j·wo），
+    /// 在对数域确定，高阶时 wc^N 溢出也不影响求值和仿真
+    pub fn analog(&self) -> Result<AnalogSystem, String> {
+        let edges: Vec<f64> = std::iter::once(self.cutoff)
+            .chain(self.upper_cutoff)
+            .map(|f| 2.0 * PI * f)
+            .collect();
+        let zpk = analog_zpk(&self.family, self.order, &edges, self.filter_type)?;
+        let dc = analog_prototype::prototype(&self.family, self.order)?.evaluate(Complex::new(0.0, 0.0)).re;
+        match self.filter_type {
+            // 高通增益为原型零极点之比，不含 wc^N
+            FilterType::Highpass => AnalogSystem::from_zpk(zpk),
+            FilterType::Lowpass | FilterType::Bandstop => AnalogSystem::with_gain_at(zpk, Complex::new(0.0, 0.0), dc),
+            FilterType::Bandpass => {
+                AnalogSystem::with_gain_at(zpk, Complex::new(0.0, (edges[0] * edges[1]).sqrt()), dc)
+            }
+        }
+    }
+
+    /// 离散状态空间模型：有二阶节时逐节串联（数值稳定），否则为 b/a 的能控标准型
+    pub fn state_space(&self) -> Result<StateSpace, String> {
+        self.to_iir_filter()?.state_space(self.sample_rate)
+    }
+
+    /// 构造滤波器对象：有二阶节时用二阶节级联，否则退回直接 I 型
+    pub fn to_iir_filter(&self) -> Result<IirFilter, String> {
+        if self.sos.is_empty() {
+            IirFilter::direct_form_1(&self.b, &self.a)
+        } else {
+            IirFilter::from_sos(&self.sos)
+        }
+    }
+}
+
+/// 数字 IIR 滤波器的零极点增益（MATLAB `[z, p, k] = butter(n, Wn)`、`cheby1`、`cheby2`、`ellip`）
+///
+/// cutoffs 对低通、高通为一个截止频率，对带通、带阻为 [下边缘, 上边缘]。
+/// 低通：归一化模拟原型的零极点按预畸变后的截止角频率缩放，再经双线性变换，s = ∞ 处的零点映射到 z = -1。
+/// 高通：设计截止频率关于 f_s/4 镜像（f_s/2 - f_c）的低通，再令 H_HP(z) = H_LP(-z)，
+/// 零极点取反、增益不变，频率轴 ω → π - ω 精确翻转，各族的通带、阻带边缘随之镜像。
+/// 带通、带阻：两个边缘分别预畸变，原型经 `Zpk::lp2bp` / `Zpk::lp2bs` 变换后再做双线性变换，
+/// 数字滤波器的两个边缘因此精确落在给定频率上
+pub fn design_zpk(
+    family: &FilterFamily,
+    order: usize,
+    cutoffs: &[f64],
+    sample_rate: f64,
+    filter_type: FilterType,
+) -> Result<Zpk, String> {
+    let expected = if filter_type.is_band() { 2 } else { 1 };
+    if cutoffs.len() != expected {
+        return Err(format!(
+            "{:?} filter needs {} cutoff frequencies, got {}",
+            filter_type,
+            expected,
+            cutoffs.len()
+        ));
+    }
+    for &cutoff in cutoffs {
+        if !(cutoff > 0.0 && cutoff < sample_rate / 2.0) {
+            return Err(format!(
+                "cutoff {} Hz must lie strictly between 0 and f_s/2 = {} Hz",
+                cutoff,
+                sample_rate / 2.0
+            ));
+        }
+    }
+    // Pre-warp the cutoff frequency to compensate for bilinear transform distortion
+    let prewarp = |cutoff: f64| 2.0 * sample_rate * (PI * cutoff / sample_rate).tan();
+    // 增益取自原型在 s = 0 的响应，对应数字滤波器的直流（低通、带阻）或通带中心（带通）。
+    // 高阶时模拟增益 wc^N 会溢出，不能直接经双线性变换求得
+    let one = Complex::new(1.0, 0.0);
+    match filter_type {
+        FilterType::Lowpass => {
+            let prototype = analog_prototype::prototype(family, order)?;
+            let dc = prototype.evaluate(Complex::new(0.0, 0.0)).re;
+            Ok(prototype.lp2lp(prewarp(cutoffs[0])).bilinear(sample_rate).with_gain_at(one, dc))
+        }
+        FilterType::Highpass => {
+            // Spectral inversion method: H_HP(z) = H_LP(-z)
+            let mirrored = [sample_rate / 2.0 - cutoffs[0]];
+            let lowpass = design_zpk(family, order, &mirrored, sample_rate, FilterType::Lowpass)?;
+            Ok(Zpk::new(
+                lowpass.zeros.iter().map(|z| -z).collect(),
+                lowpass.poles.iter().map(|p| -p).collect(),
+                lowpass.gain,
+            ))
+        }
+        FilterType::Bandpass | FilterType::Bandstop => {
+            if cutoffs[0] >= cutoffs[1] {
+                return Err(format!(
+                    "lower band edge {} Hz must be below the upper edge {} Hz",
+                    cutoffs[0], cutoffs[1]
+                ));
+            }
+            let (w1, w2) = (prewarp(cutoffs[0]), prewarp(cutoffs[1]));
+            let (wo, bw) = ((w1 * w2).sqrt(), w2 - w1);
+            let prototype = analog_prototype::prototype(family, order)?;
+            let dc = prototype.evaluate(Complex::new(0.0, 0.0)).re;
+            if filter_type == FilterType::Bandpass {
+                // 中心角频率 wo 预畸变前对应的数字频率
+                let center = Complex::from_polar(1.0, 2.0 * (wo / (2.0 * sample_rate)).atan());
+                Ok(prototype.lp2bp(wo, bw).bilinear(sample_rate).with_gain_at(center, dc))
+            } else {
+                Ok(prototype.lp2bs(wo, bw).bilinear(sample_rate).with_gain_at(one, dc))
+            }
+        }
+    }
+}
+
+/// 模拟滤波器的零极点增益（MATLAB `[z, p, k] = butter(n, Wn, 's')`）
+///
+/// edges 为角频率 (rad/s)：低通、高通一个，带通、带阻为 [下边缘, 上边缘]。
+/// 归一化原型经 `Zpk::lp2lp` / `lp2hp` / `lp2bp` / `lp2bs` 变换，边缘含义与数字设计相同
+pub fn analog_zpk(
+    family: &FilterFamily,
+    order: usize,
+    edges: &[f64],
+    filter_type: FilterType,
+) -> Result<Zpk, String> {
+    let expected = if filter_type.is_band() { 2 } else { 1 };
+    if edges.len() != expected {
+        return Err(format!(
+            "{:?} filter needs {} edge frequencies, got {}",
+            filter_type,
+            expected,
+            edges.len()
+        ));
+    }
+    if edges.iter().any(|&w| !(w.is_finite() && w > 0.0)) {
+        return Err(format!("edge frequencies must be positive, got {:?}", edges));
+    }
+    let prototype = analog_prototype::prototype(family, order)?;
+    match filter_type {
+        FilterType::Lowpass => Ok(prototype.lp2lp(edges[0])),
+        FilterType::Highpass => Ok(prototype.lp2hp(edges[0])),
+        FilterType::Bandpass | FilterType::Bandstop => {
+            if edges[0] >= edges[1] {
+                return Err(format!(
+                    "lower band edge {} rad/s must be below the upper edge {} rad/s",
+                    edges[0], edges[1]
+                ));
+            }
+            let (wo, bw) = ((edges[0] * edges[1]).sqrt(), edges[1] - edges[0]);
+            if filter_type == FilterType::Bandpass {
+                Ok(prototype.lp2bp(wo, bw))
+            } else {
+                Ok(prototype.lp2bs(wo, bw))
+            }
+        }
+    }
+}
+
+/// 归一化（截止角频率 1 rad/s）模拟 Butterworth 原型的极点，均匀分布在左半单位圆上
+pub fn butterworth_analog_poles(order: usize) -> Vec<Complex<f64>> {
+    (0..order).map(|k| {
+        let theta = PI * (2.0 * k as f64 + order as f64 + 1.0) / (2.0 * order as f64);
+        Complex::from_polar(1.0, theta)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::Discretization;
+
+    #[test]
+    fn test_matches_reference_design() {
+        // SciPy butter(4, 0.2)：截止频率为 0.2 倍 Nyquist
+        let filter = IirDesign::lowpass(4, 0.1 * 1000.0, 1000.0);
+        let b = [0.00482434, 0.01929737, 0.02894606, 0.01929737, 0.00482434];
+        let a = [1.0, -2.36951301, 2.31398841, -1.05466541, 0.18737949];
+        for (x, y) in filter.b.iter().zip(&b).chain(filter.a.iter().zip(&a)) {
+            assert!((x - y).abs() < 1e-8, "{} vs {}", x, y);
+        }
+        assert_eq!(filter.sos.len(), 2);
+    }
+
+    #[test]
+    fn test_zpk_and_sos_describe_the_same_filter() {
+        for filter in [IirDesign::highpass(8, 3000.0, 22050.0), IirDesign::lowpass(5, 4000.0, 22050.0)] {
+            let zpk = filter.zpk().unwrap();
+            assert_eq!(zpk.poles.len(), filter.order);
+            // 极点都在单位圆内；零点全部在 z = ±1
+            assert!(zpk.poles.iter().all(|p| p.norm() < 1.0));
+            let zero = if filter.filter_type == FilterType::Lowpass { -1.0 } else { 1.0 };
+            assert!(zpk.zeros.iter().all(|z| (z - zero).norm() < 1e-6));
+
+            // 截止频率处 -3 dB，二阶节与传递函数的响应一致
+            let z = Complex::from_polar(1.0, 2.0 * PI * filter.cutoff / filter.sample_rate);
+            assert!((zpk.evaluate(z).norm() - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-9);
+            let tf = crate::filter_response::frequency_response_at_omega(&filter, z.arg());
+            assert!((tf - zpk.evaluate(z)).norm() < 1e-9);
+
+            let input: Vec<f64> = (0..2000).map(|i| (i as f64 * 0.37).sin() + ((i * 7) % 11) as f64 * 0.05).collect();
+            let cascade = filter.to_iir_filter().unwrap().process_block(&input);
+            let direct = crate::iir_filter::apply_filter(&input, &filter.b, &filter.a);
+            assert!(cascade.iter().zip(&direct).all(|(x, y)| (x - y).abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_other_families_keep_cutoff_semantics() {
+        // 高通经 H_LP(-z) 镜像后，截止频率处的增益仍由族决定；偶数阶 Chebyshev I 的通带最大值为 1
+        let families = [
+            FilterFamily::Chebyshev1 { ripple_db: 0.5 },
+            FilterFamily::Chebyshev2 { attenuation_db: 50.0 },
+            FilterFamily::Elliptic { ripple_db: 0.5, attenuation_db: 60.0 },
+            FilterFamily::Bessel,
+        ];
+        for family in families {
+            for filter_type in [FilterType::Highpass, FilterType::Lowpass] {
+                let filter = IirDesign::with_family(family, 8, 3000.0, 22050.0, filter_type).unwrap();
+                assert_eq!(filter.sos.len(), 4);
+                let zpk = filter.zpk().unwrap();
+                assert!(zpk.poles.iter().all(|p| p.norm() < 1.0), "{}", family);
+                let omega = 2.0 * PI * 3000.0 / 22050.0;
+                let h = zpk.evaluate(Complex::from_polar(1.0, omega)).norm();
+                assert!((h - family.cutoff_gain()).abs() < 1e-8, "{} {:?}: {}", family, filter_type, h);
+                let tf = crate::filter_response::frequency_response_at_omega(&filter, omega).norm();
+                assert!((tf - h).abs() < 1e-6, "{} {:?}", family, filter_type);
+            }
+        }
+
+        // 同阶同截止频率下，椭圆高通在 0.9 f_d 处的衰减远大于 Butterworth
+        let stopband = |family| {
+            let filter = IirDesign::with_family(family, 8, 3000.0, 22050.0, FilterType::Highpass).unwrap();
+            let z = Complex::from_polar(1.0, 2.0 * PI * 2700.0 / 22050.0);
+            filter.zpk().unwrap().evaluate(z).norm()
+        };
+        let elliptic = stopband(FilterFamily::Elliptic { ripple_db: 0.5, attenuation_db: 60.0 });
+        assert!(elliptic < 0.1 * stopband(FilterFamily::Butterworth));
+
+        let err = IirDesign::with_family(
+            FilterFamily::Elliptic { ripple_db: 3.0, attenuation_db: 2.0 }, 4, 3000.0, 22050.0, FilterType::Lowpass,
+        )
+        .unwrap_err();
+        assert!(err.contains("must exceed"));
+    }
+
+    #[test]
+    fn test_band_edges_are_exact() {
+        let fs = 22050.0;
+        let gain_at = |filter: &IirDesign, f: f64| {
+            filter.zpk().unwrap().evaluate(Complex::from_polar(1.0, 2.0 * PI * f / fs)).norm()
+        };
+        for family in [FilterFamily::Butterworth, FilterFamily::Chebyshev1 { ripple_db: 0.5 }] {
+            // 上边带区域 [f_d, f_d + f_B]：两个边缘的增益由族决定，中心处通带
+            let bandpass = IirDesign::band_with_family(family, 4, 3000.0, 7000.0, fs, FilterType::Bandpass).unwrap();
+            assert_eq!((bandpass.degree(), bandpass.sos.len(), bandpass.b.len()), (8, 4, 9));
+            for edge in [3000.0, 7000.0] {
+                assert!((gain_at(&bandpass, edge) - family.cutoff_gain()).abs() < 1e-9, "{} {}", family, edge);
+            }
+            assert!(gain_at(&bandpass, (3000.0f64 * 7000.0).sqrt()) > family.cutoff_gain());
+            assert!(gain_at(&bandpass, 1000.0) < 1e-2 && gain_at(&bandpass, 10000.0) < 1e-2);
+
+            // 50 Hz 工频陷波
+            let bandstop = IirDesign::band_with_family(family, 2, 45.0, 55.0, fs, FilterType::Bandstop).unwrap();
+            for edge in [45.0, 55.0] {
+                assert!((gain_at(&bandstop, edge) - family.cutoff_gain()).abs() < 1e-7, "{} {}", family, edge);
+            }
+            assert!(gain_at(&bandstop, 50.0) < 1e-2);
+            // 偶数阶 Chebyshev I 远离阻带处的增益为 -rp dB
+            assert!((family.cutoff_gain() - 1e-9..=1.0 + 1e-9).contains(&gain_at(&bandstop, 1000.0)));
+        }
+
+        // 二阶节与传递函数描述同一个滤波器
+        let filter = IirDesign::bandpass(3, 3000.0, 7000.0, fs);
+        let input: Vec<f64> = (0..2000).map(|i| (i as f64 * 0.37).sin() + ((i * 7) % 11) as f64 * 0.05).collect();
+        let cascade = filter.to_iir_filter().unwrap().process_block(&input);
+        let direct = crate::iir_filter::apply_filter(&input, &filter.b, &filter.a);
+        assert!(cascade.iter().zip(&direct).all(|(x, y)| (x - y).abs() < 1e-9));
+
+        assert!(IirDesign::band_with_family(FilterFamily::Butterworth, 4, 7000.0, 3000.0, fs, FilterType::Bandpass).is_err());
+        assert!(IirDesign::with_family(FilterFamily::Butterworth, 4, 3000.0, fs, FilterType::Bandstop).is_err());
+    }
+
+    #[test]
+    fn test_high_order_gain_does_not_overflow() {
+        // f_s = 22050 Hz 时 Π(2f_s - p) 在 64 阶左右超出 f64 范围，原型增益 wc^N 在 70 阶左右超出
+        let fs = 22050.0;
+        let order = 80;
+        let at = |f: f64| Complex::from_polar(1.0, 2.0 * PI * f / fs);
+        let designs = [
+            (FilterType::Lowpass, vec![4000.0], at(0.0)),
+            (FilterType::Highpass, vec![3000.0], at(fs / 2.0)),
+            (FilterType::Bandpass, vec![3000.0, 7000.0], at((3000.0f64 * 7000.0).sqrt())),
+            (FilterType::Bandstop, vec![3000.0, 7000.0], at(0.0)),
+        ];
+        for (filter_type, cutoffs, passband) in designs {
+            let zpk = design_zpk(&FilterFamily::Butterworth, order, &cutoffs, fs, filter_type).unwrap();
+            assert!(zpk.gain.is_finite() && zpk.gain > 0.0, "{:?}: k = {}", filter_type, zpk.gain);
+            let h = zpk.evaluate(passband).norm();
+            assert!((h - 1.0).abs() < 1e-6, "{:?}: |H| = {}", filter_type, h);
+            for &cutoff in &cutoffs {
+                let h = zpk.evaluate(at(cutoff)).norm();
+                assert!((h - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-6, "{:?} {}: |H| = {}", filter_type, cutoff, h);
+            }
+        }
+    }
+
+    #[test]
+    fn test_high_order_analog_gain_does_not_overflow() {
+        // 80 阶模拟低通的 k = wc^80 ≈ 1e352，带通的 bw^80 同样超出 f64 范围
+        let fs = 22050.0;
+        let designs = [
+            (IirDesign::lowpass(80, 4000.0, fs), vec![4000.0], 0.0),
+            (IirDesign::highpass(80, 3000.0, fs), vec![3000.0], 1e6),
+            (IirDesign::bandpass(80, 3000.0, 7000.0, fs), vec![3000.0, 7000.0], (3000.0f64 * 7000.0).sqrt()),
+            (IirDesign::bandstop(80, 3000.0, 7000.0, fs), vec![3000.0, 7000.0], 0.0),
+        ];
+        for (filter, edges, passband) in designs {
+            let analog = filter.analog().unwrap();
+            let h = analog.frequency_response(passband).norm();
+            assert!((h - 1.0).abs() < 1e-6, "{:?}: |H| = {}", filter.filter_type, h);
+            for &edge in &edges {
+                let h = analog.frequency_response(edge).norm();
+                assert!((h - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-6, "{:?} {}: |H| = {}", filter.filter_type, edge, h);
+            }
+            assert!(analog.state_space().unwrap().a.to_rows().iter().flatten().all(|v| v.is_finite()));
+        }
+
+        // 逐节实现的状态空间模型仿真阶跃，稳态为直流增益 1
+        let analog = IirDesign::lowpass(80, 4000.0, fs).analog().unwrap();
+        let step = analog.lsim(&vec![1.0; 8192], fs, Discretization::Tustin).unwrap();
+        assert!((step[step.len() - 1] - 1.0).abs() < 1e-3, "step -> {}", step[step.len() - 1]);
+    }
+}
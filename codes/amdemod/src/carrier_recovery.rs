@@ -0,0 +1,338 @@
+// 载波恢复模块
+// 用闭环（PLL / Costas 环）直接从错误解调的信号中锁定残余载波 cos(2πf_d t + φ)，
+// 不依赖开环估计的 f_d 和零初相假设
+
+use crate::analog_prototype::FilterFamily;
+use crate::butterworth_filter::{FilterType, IirDesign};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// 环路类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoopKind {
+    /// 锁相环：鉴相器 Q / |w|，要求信号中含有残余载波（标准调幅）
+    Pll,
+    /// Costas 环：鉴相器 I·Q / |w|²，适用于抑制载波的双边带信号，输出有 180° 相位模糊
+    Costas,
+}
+
+/// 载波恢复参数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LoopConfig {
+    pub kind: LoopKind,
+    /// 环路噪声带宽 B_n (Hz)，越大捕获越快、抖动越大
+    pub loop_bandwidth: f64,
+    /// 阻尼系数 ζ
+    pub damping: f64,
+    /// NCO 的初始频率 (Hz)，环路只能捕获与之相差约几个 B_n 以内的载波
+    pub initial_frequency: f64,
+    /// I/Q 支路低通滤波器的截止频率 (Hz)
+    ///
+    /// 须低于载波频率，否则乘积中 2f_d 附近的分量会落入支路通带；
+    /// f_d < f_B 时只能用基带中低于 f_d 的部分锁定载波
+    pub arm_bandwidth: f64,
+    /// I/Q 支路 Butterworth 低通的阶数
+    pub arm_order: usize,
+    /// 锁定指示器高于该值才认为已锁定，取值 (0, 1)
+    pub lock_threshold: f64,
+    /// 信号功率与锁定指示器的平滑时间常数 (s)
+    pub smoothing: f64,
+}
+
+impl LoopConfig {
+    /// 以初始频率和支路带宽构造，其余参数取默认值
+    pub fn new(kind: LoopKind, initial_frequency: f64, arm_bandwidth: f64) -> Self {
+        Self {
+            kind,
+            loop_bandwidth: 20.0,
+            damping: std::f64::consts::FRAC_1_SQRT_2,
+            initial_frequency,
+            arm_bandwidth,
+            arm_order: 6,
+            lock_threshold: 0.8,
+            smoothing: 0.02,
+        }
+    }
+
+    /// 二阶环路 PI 滤波器的比例、积分增益（鉴相器与 NCO 增益归一化为 1）
+    fn gains(&self, sample_rate: f64) -> (f64, f64) {
+        let zeta = self.damping;
+        let theta = self.loop_bandwidth / sample_rate / (zeta + 0.25 / zeta);
+        let denom = 1.0 + 2.0 * zeta * theta + theta * theta;
+        (4.0 * zeta * theta / denom, 4.0 * theta * theta / denom)
+    }
+
+    fn validate(&self, sample_rate: f64) -> Result<(), String> {
+        let nyquist = sample_rate / 2.0;
+        if !(self.loop_bandwidth > 0.0 && self.loop_bandwidth < nyquist / 10.0) {
+            return Err(format!(
+                "loop_bandwidth = {} Hz 超出范围 (0, {} Hz)",
+                self.loop_bandwidth,
+                nyquist / 10.0
+            ));
+        }
+        if self.damping <= 0.0 {
+            return Err(format!("damping = {} 必须为正", self.damping));
+        }
+        if !(self.arm_bandwidth > 0.0 && self.arm_bandwidth < self.initial_frequency.min(nyquist)) {
+            return Err(format!(
+                "arm_bandwidth = {} Hz 超出范围 (0, {} Hz)，支路带宽须低于载波频率",
+                self.arm_bandwidth,
+                self.initial_frequency.min(nyquist)
+            ));
+        }
+        if self.arm_order == 0 {
+            return Err("arm_order 至少为 1".to_string());
+        }
+        if !(self.lock_threshold > 0.0 && self.lock_threshold < 1.0) {
+            return Err(format!("lock_threshold = {} 超出范围 (0, 1)", self.lock_threshold));
+        }
+        if !(self.smoothing > 0.0 && self.smoothing.is_finite()) {
+            return Err(format!("smoothing = {} s 必须为正", self.smoothing));
+        }
+        Ok(())
+    }
+}
+
+/// 载波恢复结果，各轨迹与输入逐样本对应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarrierRecovery {
+    pub config: LoopConfig,
+    pub sample_rate: f64,
+    /// 同相支路 I[n]，锁定后约为限带到 arm_bandwidth 的 m(t)（Costas 环可能整体反相）
+    pub baseband: Vec<f64>,
+    /// 正交支路 Q[n]，锁定后应接近 0
+    pub quadrature: Vec<f64>,
+    /// NCO 相位 θ[n] (rad)，锁定后即恢复出的载波相位 2πf_d t + φ（Costas 环模 π）
+    pub phase: Vec<f64>,
+    /// NCO 瞬时频率 (Hz)，即环路跟踪到的 f_d
+    pub frequency: Vec<f64>,
+    /// 相位误差估计 (rad)：PLL 为 atan2(Q, I)，Costas 环为 ½·atan2(2IQ, I² - Q²)
+    pub phase_error: Vec<f64>,
+    /// 平滑后的锁定指示器：PLL 为 cos φ_e，Costas 环为 cos 2φ_e，1 表示完全锁定
+    pub lock: Vec<f64>,
+    /// 锁定指示器此后一直高于阈值的起始时刻 (s)；结束时仍未锁定为 `None`
+    pub lock_time: Option<f64>,
+}
+
+impl CarrierRecovery {
+    pub fn is_locked(&self) -> bool {
+        self.lock_time.is_some()
+    }
+
+    /// 锁定后 NCO 频率的平均值 (Hz)；未锁定时取最后一个样本的频率
+    pub fn locked_frequency(&self) -> f64 {
+        let start = self
+            .lock_time
+            .map_or(self.frequency.len().saturating_sub(1), |t| (t * self.sample_rate) as usize);
+        let tail = &self.frequency[start.min(self.frequency.len().saturating_sub(1))..];
+        tail.iter().sum::<f64>() / tail.len().max(1) as f64
+    }
+
+    /// 锁定后相位误差的均方根 (rad)
+    pub fn rms_phase_error(&self) -> Option<f64> {
+        let start = (self.lock_time? * self.sample_rate) as usize;
+        let tail = &self.phase_error[start..];
+        Some((tail.iter().map(|e| e * e).sum::<f64>() / tail.len().max(1) as f64).sqrt())
+    }
+}
+
+/// 闭环载波恢复
+///
+/// NCO 输出 cos θ、-sin θ 与输入相乘，经支路低通（二阶节级联）得到 I = m·cos φ_e、Q = m·sin φ_e。
+/// 鉴相器输出以平滑后的支路功率归一化，使环路增益与信号幅度无关；
+/// 二阶 PI 环路滤波器的增益由 B_n 和 ζ 给出。
+pub fn recover_carrier(
+    samples: &[f64],
+    sample_rate: f64,
+    config: &LoopConfig,
+) -> Result<CarrierRecovery, String> {
+    config.validate(sample_rate)?;
+
+    let arm = IirDesign::with_family(
+        FilterFamily::Butterworth,
+        config.arm_order,
+        config.arm_bandwidth,
+        sample_rate,
+        FilterType::Lowpass,
+    )?;
+    let (mut i_arm, mut q_arm) = (arm.to_iir_filter()?, arm.to_iir_filter()?);
+    let (kp, ki) = config.gains(sample_rate);
+    let alpha = 1.0 - (-1.0 / (config.smoothing * sample_rate)).exp();
+
+    let n = samples.len();
+    let mut baseband = Vec::with_capacity(n);
+    let mut quadrature = Vec::with_capacity(n);
+    let mut phase = Vec::with_capacity(n);
+    let mut frequency = Vec::with_capacity(n);
+    let mut phase_error = Vec::with_capacity(n);
+    let mut lock = Vec::with_capacity(n);
+
+    let omega0 = 2.0 * PI * config.initial_frequency / sample_rate;
+    let (mut theta, mut integrator) = (0.0f64, 0.0f64);
+    let (mut power, mut lock_level) = (0.0f64, 0.0f64);
+    let mut lock_start: Option<usize> = None;
+
+    for (idx, &x) in samples.iter().enumerate() {
+        let i = i_arm.process_sample(2.0 * x * theta.cos());
+        let q = q_arm.process_sample(-2.0 * x * theta.sin());
+
+        power += alpha * (i * i + q * q - power);
+        let norm = power.max(f64::MIN_POSITIVE);
+        let (error, estimate, indicator) = match config.kind {
+            LoopKind::Pll => (
+                q / norm.sqrt(),
+                q.atan2(i),
+                i / (i * i + q * q).sqrt().max(f64::MIN_POSITIVE),
+            ),
+            LoopKind::Costas => (
+                i * q / norm,
+                0.5 * (2.0 * i * q).atan2(i * i - q * q),
+                (i * i - q * q) / (i * i + q * q).max(f64::MIN_POSITIVE),
+            ),
+        };
+        lock_level += alpha * (indicator - lock_level);
+        match (lock_level >= config.lock_threshold, lock_start) {
+            (true, None) => lock_start = Some(idx),
+            (false, Some(_)) => lock_start = None,
+            _ => {}
+        }
+
+        // PI 环路滤波器，输出为 NCO 的频率修正量 (rad/sample)
+        integrator += ki * error;
+        let omega = omega0 + kp * error + integrator;
+
+        baseband.push(i);
+        quadrature.push(q);
+        phase.push(theta);
+        frequency.push(omega * sample_rate / (2.0 * PI));
+        phase_error.push(estimate);
+        lock.push(lock_level);
+
+        theta = (theta + omega).rem_euclid(2.0 * PI);
+    }
+
+    Ok(CarrierRecovery {
+        config: *config,
+        sample_rate,
+        baseband,
+        quadrature,
+        phase,
+        frequency,
+        phase_error,
+        lock,
+        lock_time: lock_start.map(|idx| idx as f64 / sample_rate),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FS: f64 = 22050.0;
+
+    /// 基带 m(t)：三个正弦分量
+    fn baseband(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64 / FS;
+                (2.0 * PI * 300.0 * t).sin() + 0.6 * (2.0 * PI * 800.0 * t + 1.0).sin()
+                    + 0.4 * (2.0 * PI * 1500.0 * t + 2.0).sin()
+            })
+            .collect()
+    }
+
+    /// 锁定后的输出与 s·m(t) 的相对误差，s = ±1 取较小者
+    fn relative_error(output: &[f64], m: &[f64], start: usize) -> f64 {
+        // 支路低通的群时延约为若干个样本，在 0–40 个样本内搜索
+        let energy: f64 = m[start..].iter().map(|v| v * v).sum();
+        (0..40)
+            .flat_map(|d| [1.0, -1.0].map(|s| (d, s)))
+            .map(|(d, s)| {
+                (start..output.len())
+                    .map(|i| (output[i] - s * m[i - d]).powi(2))
+                    .sum::<f64>()
+                    / energy
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[test]
+    fn test_costas_locks_on_suppressed_carrier() {
+        let n = 2 * 22050;
+        let m = baseband(n);
+        let samples: Vec<f64> = m
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| v * (2.0 * PI * 3000.0 * i as f64 / FS + 0.9).cos())
+            .collect();
+
+        let config = LoopConfig::new(LoopKind::Costas, 2996.0, 2000.0);
+        let result = recover_carrier(&samples, FS, &config).unwrap();
+        assert!(result.is_locked());
+        assert!(result.lock_time.unwrap() < 0.5);
+        assert!((result.locked_frequency() - 3000.0).abs() < 0.05);
+        assert!(result.rms_phase_error().unwrap() < 0.1, "{:?}", result.rms_phase_error());
+        assert!(relative_error(&result.baseband, &m, n / 2) < 0.01);
+    }
+
+    #[test]
+    fn test_pll_locks_on_residual_carrier() {
+        let n = 2 * 22050;
+        let m = baseband(n);
+        // 标准调幅 (1 + 0.3·m(t))·cos(2πf_d t + φ)
+        let samples: Vec<f64> = m
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| (1.0 + 0.3 * v) * (2.0 * PI * 2500.0 * i as f64 / FS - 2.0).cos())
+            .collect();
+
+        let config = LoopConfig::new(LoopKind::Pll, 2492.0, 2000.0);
+        let result = recover_carrier(&samples, FS, &config).unwrap();
+        assert!(result.is_locked());
+        assert!((result.locked_frequency() - 2500.0).abs() < 0.05);
+
+        let envelope: Vec<f64> = m.iter().map(|v| 1.0 + 0.3 * v).collect();
+        let e = relative_error(&result.baseband, &envelope, n / 2);
+        assert!(e < 0.01, "{}", e);
+    }
+
+    #[test]
+    fn test_unlocked_without_signal_structure() {
+        // 单频信号偏离初始频率太远，Costas 环无法捕获
+        let samples: Vec<f64> = (0..22050)
+            .map(|i| (2.0 * PI * 3400.0 * i as f64 / FS).cos())
+            .collect();
+        let config = LoopConfig {
+            loop_bandwidth: 5.0,
+            ..LoopConfig::new(LoopKind::Costas, 3000.0, 200.0)
+        };
+        let result = recover_carrier(&samples, FS, &config).unwrap();
+        assert!(!result.is_locked());
+        assert_eq!(result.baseband.len(), samples.len());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = LoopConfig {
+            loop_bandwidth: 0.0,
+            ..LoopConfig::new(LoopKind::Pll, 3000.0, 4000.0)
+        };
+        assert!(recover_carrier(&[0.0; 16], FS, &config).is_err());
+
+        // 支路阶数为 0、平滑时间常数非正时返回错误而不是 panic 或产生 NaN
+        let config = LoopConfig {
+            arm_order: 0,
+            ..LoopConfig::new(LoopKind::Pll, 3000.0, 2000.0)
+        };
+        assert!(recover_carrier(&[0.0; 16], FS, &config).is_err());
+        for smoothing in [0.0, -0.02, f64::NAN] {
+            let config = LoopConfig {
+                smoothing,
+                ..LoopConfig::new(LoopKind::Costas, 3000.0, 2000.0)
+            };
+            assert!(recover_carrier(&[0.0; 16], FS, &config).is_err());
+        }
+    }
+}
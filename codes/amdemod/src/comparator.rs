@@ -1,5 +1,7 @@
 use plotters::prelude::*;
+use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComparisonResult {
     pub mse: f64,
     pub max_diff: f64,
@@ -100,7 +102,7 @@ pub fn compare_signals(signal1: &[f64], signal2: &[f64]) -> ComparisonResult {
 }
 
 /// Save comparison results to file
-pub fn save_comparison(result: &ComparisonResult, filename: &str) {
+pub fn save_comparison(result: &ComparisonResult, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
     let mut content = String::new();
     content.push_str("Q4 vs Q3 Comparison Results\n");
     content.push_str("============================\n\n");
@@ -151,15 +153,20 @@ pub fn save_comparison(result: &ComparisonResult, filename: &str) {
     content.push_str("  - No phase distortion from filters\n");
     content.push_str("  - Sharp cutoff\n");
     
-    std::fs::write(filename, content).expect("Failed to save comparison");
+    std::fs::write(filename, content)?;
+    Ok(())
 }
 
 /// Plot full-time comparison of two signals (all samples)
-pub fn plot_full_comparison(signal1: &[f64], signal2: &[f64], filename: &str) {
+pub fn plot_full_comparison(
+    signal1: &[f64],
+    signal2: &[f64],
+    filename: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
     let n = signal1.len().min(signal2.len());
     
     let root = BitMapBackend::new(filename, (1600, 600)).into_drawing_area();
-    root.fill(&WHITE).unwrap();
+    root.fill(&WHITE)?;
     
     let max_val = signal1[..n].iter()
         .chain(signal2[..n].iter())
@@ -171,14 +178,14 @@ pub fn plot_full_comparison(signal1: &[f64], signal2: &[f64], filename: &str) {
         .x_label_area_size(50)
         .y_label_area_size(60)
         .build_cartesian_2d(0..n, -max_val*1.1..max_val*1.1)
-        .unwrap();
+        ?;
     
     chart
         .configure_mesh()
         .x_desc("Sample")
         .y_desc("Amplitude")
         .draw()
-        .unwrap();
+        ?;
     
     // Plot Q4 signal
     chart
@@ -186,9 +193,9 @@ pub fn plot_full_comparison(signal1: &[f64], signal2: &[f64], filename: &str) {
             (0..n).map(|i| (i, signal1[i])),
             &BLUE,
         ))
-        .unwrap()
+        ?
         .label("Q4 (Frequency-domain)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
     
     // Plot Q3 signal  
     chart
@@ -196,27 +203,32 @@ pub fn plot_full_comparison(signal1: &[f64], signal2: &[f64], filename: &str) {
             (0..n).map(|i| (i, signal2[i])),
             &RED,
         ))
-        .unwrap()
+        ?
         .label("Q3 (Time-domain)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
     
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()
-        .unwrap();
+        ?;
     
-    root.present().unwrap();
+    root.present()?;
     println!("  Saved: {}", filename);
+    Ok(())
 }
 
 /// Plot comparison of two signals (first 2000 samples for detail)
-pub fn plot_comparison(signal1: &[f64], signal2: &[f64], filename: &str) {
+pub fn plot_comparison(
+    signal1: &[f64],
+    signal2: &[f64],
+    filename: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
     let n = signal1.len().min(signal2.len()).min(2000); // Plot first 2000 samples
     
     let root = BitMapBackend::new(filename, (1200, 800)).into_drawing_area();
-    root.fill(&WHITE).unwrap();
+    root.fill(&WHITE)?;
     
     let max_val = signal1[..n].iter()
         .chain(signal2[..n].iter())
@@ -228,14 +240,14 @@ pub fn plot_comparison(signal1: &[f64], signal2: &[f64], filename: &str) {
         .x_label_area_size(50)
         .y_label_area_size(60)
         .build_cartesian_2d(0..n, -max_val*1.1..max_val*1.1)
-        .unwrap();
+        ?;
     
     chart
         .configure_mesh()
         .x_desc("Sample")
         .y_desc("Amplitude")
         .draw()
-        .unwrap();
+        ?;
     
     // Plot Q4 signal
     chart
@@ -243,9 +255,9 @@ pub fn plot_comparison(signal1: &[f64], signal2: &[f64], filename: &str) {
             (0..n).map(|i| (i, signal1[i])),
             &BLUE,
         ))
-        .unwrap()
+        ?
         .label("Q4 (Frequency-domain)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
     
     // Plot Q3 signal
     chart
@@ -253,17 +265,96 @@ pub fn plot_comparison(signal1: &[f64], signal2: &[f64], filename: &str) {
             (0..n).map(|i| (i, signal2[i])),
             &RED,
         ))
-        .unwrap()
+        ?
         .label("Q3 (Time-domain)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
     
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()
-        .unwrap();
+        ?;
     
-    root.present().unwrap();
+    root.present()?;
     println!("  Saved: {}", filename);
+    Ok(())
+}
+
+/// Plot waveforms of several signals over the first `duration` seconds
+pub fn plot_waveform_comparison(
+    signals: &[(&[f64], &str)],
+    fs: f64,
+    duration: f64,
+    title: &str,
+    filename: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(filename, (1200, 800)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let samples_to_plot = signals
+        .iter()
+        .map(|(signal, _)| signal.len())
+        .fold((duration * fs) as usize, usize::min);
+
+    // Find min/max across all signals for automatic y-axis scaling
+    let mut y_min = f64::INFINITY;
+    let mut y_max = f64::NEG_INFINITY;
+
+    for (signal, _) in signals {
+        for &v in &signal[..samples_to_plot] {
+            y_min = y_min.min(v);
+            y_max = y_max.max(v);
+        }
+    }
+
+    // Add 10% margin
+    let y_range = y_max - y_min;
+    let margin = y_range * 0.1;
+    y_min -= margin;
+    y_max += margin;
+
+    // Ensure at least some range if signal is constant
+    if y_range < 1e-10 {
+        y_min = -0.1;
+        y_max = 0.1;
+    }
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(0f64..duration, y_min..y_max)
+        ?;
+
+    chart.configure_mesh()
+        .x_desc("Time (s)")
+        .y_desc("Amplitude")
+        .draw()
+        ?;
+
+    let colors = [GREEN, RED, BLUE, MAGENTA, CYAN];
+
+    for (idx, (signal, label)) in signals.iter().enumerate() {
+        let color = colors[idx % colors.len()];
+        chart
+            .draw_series(LineSeries::new(
+                (0..samples_to_plot).map(|i| (i as f64 / fs, signal[i])),
+                color,
+            ))
+            ?
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    chart.configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        ?;
+
+    root.present()?;
+    println!("  Saved: {}", filename);
+    Ok(())
 }
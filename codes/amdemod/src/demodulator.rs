@@ -0,0 +1,124 @@
+use std::f64::consts::PI;
+
+/// Multiply signal with carrier cos(2*pi*f_d*t)
+pub fn multiply_with_carrier(signal: &[f64], f_d: f64, f_s: f64) -> Vec<f64> {
+    multiply_with_carrier_from(signal, f_d, f_s, 0)
+}
+
+/// 同 [`multiply_with_carrier`]，但 signal[0] 是整段信号的第 `start` 个样本
+///
+/// 分块解调时载波相位由样本序号 start + i 确定，块与块之间保持连续
+pub fn multiply_with_carrier_from(signal: &[f64], f_d: f64, f_s: f64, start: usize) -> Vec<f64> {
+    signal
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let t = (start + i) as f64 / f_s;
+            let carrier = (2.0 * PI * f_d * t).cos();
+            // Multiply by 2 to compensate for the 1/2 factor from cos²(x) = (1 + cos(2x))/2
+            x * carrier * 2.0
+        })
+        .collect()
+}
+
+/// 数控振荡器（NCO）：按瞬时频率累加相位，频率变化时输出相位保持连续
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nco {
+    /// 当前相位 (rad)，取值 [0, 2π)
+    phase: f64,
+    sample_rate: f64,
+}
+
+impl Nco {
+    pub fn new(sample_rate: f64) -> Self {
+        Self::with_phase(sample_rate, 0.0)
+    }
+
+    /// 以给定初相 (rad) 启动
+    pub fn with_phase(sample_rate: f64, phase: f64) -> Self {
+        Self {
+            phase: phase.rem_euclid(2.0 * PI),
+            sample_rate,
+        }
+    }
+
+    /// 下一个输出样本的相位 (rad)
+    pub fn phase(&self) -> f64 {
+        self.phase
+    }
+
+    /// 输出 cos(φ[n])，再以瞬时频率 freq (Hz) 推进相位：φ[n+1] = φ[n] + 2π·freq/f_s
+    pub fn next_cos(&mut self, freq: f64) -> f64 {
+        let value = self.phase.cos();
+        self.phase = (self.phase + 2.0 * PI * freq / self.sample_rate).rem_euclid(2.0 * PI);
+        value
+    }
+}
+
+/// 用跟随 f_d(t) 的 NCO 代替固定载波：x_b[n] = 2·x[n]·cos(φ[n])
+///
+/// `f_d` 为逐样本的瞬时频率偏差 (Hz)，长度须与信号一致
+pub fn multiply_with_nco(signal: &[f64], f_d: &[f64], f_s: f64) -> Vec<f64> {
+    assert_eq!(signal.len(), f_d.len(), "f_d trajectory length must match the signal");
+    let mut nco = Nco::new(f_s);
+    signal
+        .iter()
+        .zip(f_d)
+        .map(|(&x, &f)| x * nco.next_cos(f) * 2.0)
+        .collect()
+}
+
+/// 以给定的逐样本载波相位 θ[n] (rad) 解调：x_b[n] = 2·x[n]·cos(θ[n])，例如载波恢复环路输出的 NCO 相位
+pub fn multiply_with_phase(signal: &[f64], phase: &[f64]) -> Vec<f64> {
+    assert_eq!(signal.len(), phase.len(), "carrier phase length must match the signal");
+    signal
+        .iter()
+        .zip(phase)
+        .map(|(&x, &theta)| x * theta.cos() * 2.0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_carrier_multiplication() {
+        let f_d = 1000.0;
+        let f_s = 8000.0;
+        let signal = vec![1.0; 100];
+        
+        let output = multiply_with_carrier(&signal, f_d, f_s);
+        
+        // Output should oscillate with carrier frequency
+        assert_eq!(output.len(), 100);
+        // At t=0, cos(0) = 1.0, scaled by the factor of 2
+        assert!((output[0] - 2.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_nco_matches_fixed_carrier() {
+        let f_s = 8000.0;
+        let signal: Vec<f64> = (0..1000).map(|i| (i as f64 * 0.01).sin()).collect();
+
+        let fixed = multiply_with_carrier(&signal, 1234.5, f_s);
+        let tracked = multiply_with_nco(&signal, &vec![1234.5; signal.len()], f_s);
+        for (a, b) in fixed.iter().zip(&tracked) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_nco_phase_continuous() {
+        // 频率跳变时相位只按新频率推进，不会跳变
+        let f_s = 1000.0;
+        let mut nco = Nco::new(f_s);
+        for _ in 0..10 {
+            nco.next_cos(100.0);
+        }
+        let before = nco.phase();
+        nco.next_cos(250.0);
+        let step = (nco.phase() - before).rem_euclid(2.0 * PI);
+        assert!((step - 2.0 * PI * 250.0 / f_s).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,677 @@
+// 2. FFT 计算模块
+// 使用 rustfft 库对音频信号进行快速傅里叶变换
+// 音频是实信号：正变换走 realfft 的实数→复数路径，只算 N/2+1 个频点，速度约为复数 FFT 的两倍。
+// FftResult、STFT、Welch 等只保存 N/2+1 个频点，内存减半；需要完整 N 点频谱时
+// （频域解调的循环搬移会用到负频率）按共轭对称补齐。
+// 结果与复数 FFT 不逐位相同，相差数个 ULP（相对最大频点约 1e-16）
+
+use crate::window::{Correction, Symmetry, Window, WindowGains};
+use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
+use rustfft::{Fft, FftPlanner, num_complex::Complex};
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// 同一线程内复用的 FFT 规划器，同一长度只规划一次（STFT、Welch 等逐帧变换受益最大）
+struct Planners {
+    complex: FftPlanner<f64>,
+    real: RealFftPlanner<f64>,
+}
+
+thread_local! {
+    static PLANNERS: RefCell<Planners> = RefCell::new(Planners {
+        complex: FftPlanner::new(),
+        real: RealFftPlanner::new(),
+    });
+}
+
+fn plan_fft(len: usize, inverse: bool) -> Arc<dyn Fft<f64>> {
+    PLANNERS.with(|p| {
+        let planner = &mut p.borrow_mut().complex;
+        if inverse { planner.plan_fft_inverse(len) } else { planner.plan_fft_forward(len) }
+    })
+}
+
+fn plan_rfft(len: usize) -> Arc<dyn RealToComplex<f64>> {
+    PLANNERS.with(|p| p.borrow_mut().real.plan_fft_forward(len))
+}
+
+fn plan_irfft(len: usize) -> Arc<dyn ComplexToReal<f64>> {
+    PLANNERS.with(|p| p.borrow_mut().real.plan_fft_inverse(len))
+}
+
+/// FFT 结果结构
+///
+/// 实信号的频谱满足 X[N-k] = conj X[k]，只保存 0 到 Nyquist 的 N/2+1 个频点；
+/// 完整的 N 点频谱由 [`FftResult::full_spectrum`] 按需展开
+#[derive(Debug, Clone)]
+pub struct FftResult {
+    /// 单边复数频谱，N/2+1 个频点
+    pub spectrum: Vec<Complex<f64>>,
+    /// 频率轴（Hz），0 到 Nyquist 频率
+    pub frequencies: Vec<f64>,
+    /// 幅度谱（除以 N 归一化，与双边频谱的同一频点相同）
+    pub magnitude: Vec<f64>,
+    /// 相位谱
+    pub phase: Vec<f64>,
+    /// 采样率
+    pub sample_rate: f64,
+    /// 时域信号长度 N
+    pub num_samples: usize,
+}
+
+impl FftResult {
+    /// 计算信号的 FFT，保存 N/2+1 个频点的单边频谱（见 [`compute_rfft`]）
+    pub fn compute(samples: &[f64], sample_rate: f64) -> Self {
+        let n = samples.len();
+        let result = Self::from_half_spectrum(compute_rfft(samples), n, sample_rate);
+
+        println!("FFT 计算完成:");
+        println!("  FFT 点数: {}", n);
+        println!("  频率分辨率: {:.2} Hz", sample_rate / n as f64);
+
+        result
+    }
+
+    /// 由完整的 N 点复数频谱构造 FFT 结果（例如经过频域滤波或搬移后的频谱）
+    ///
+    /// 频谱须共轭对称（实信号的频谱），只保留前 N/2+1 个频点
+    pub fn from_spectrum(mut spectrum: Vec<Complex<f64>>, sample_rate: f64) -> Self {
+        let n = spectrum.len();
+        spectrum.truncate(n / 2 + 1);
+        Self::from_half_spectrum(spectrum, n, sample_rate)
+    }
+
+    /// 由长度为 n 的实信号的单边频谱（n/2+1 个频点）构造 FFT 结果
+    pub fn from_half_spectrum(spectrum: Vec<Complex<f64>>, n: usize, sample_rate: f64) -> Self {
+        debug_assert_eq!(spectrum.len(), if n == 0 { 0 } else { n / 2 + 1 }, "单边频谱长度应为 n/2 + 1");
+
+        // 计算频率轴
+        let frequencies: Vec<f64> = (0..spectrum.len())
+            .map(|k| k as f64 * sample_rate / n as f64)
+            .collect();
+
+        // 计算幅度谱（归一化）
+        let magnitude: Vec<f64> = spectrum
+            .iter()
+            .map(|c| c.norm() / n as f64)
+            .collect();
+
+        // 计算相位谱
+        let phase: Vec<f64> = spectrum
+            .iter()
+            .map(|c| c.arg())
+            .collect();
+
+        FftResult {
+            spectrum,
+            frequencies,
+            magnitude,
+            phase,
+            sample_rate,
+            num_samples: n,
+        }
+    }
+
+    /// 完整的 N 点复数频谱，后一半按 X[N-k] = conj X[k] 补齐，排列与复数 FFT 相同
+    pub fn full_spectrum(&self) -> Vec<Complex<f64>> {
+        let n = self.num_samples;
+        let mut spectrum = self.spectrum.clone();
+        spectrum.reserve(n.saturating_sub(spectrum.len()));
+        for k in spectrum.len()..n {
+            let mirrored = spectrum[n - k].conj();
+            spectrum.push(mirrored);
+        }
+        spectrum
+    }
+
+    /// 按双边布局（频率 0 到 f_s，共 N 点）展开的 (频率轴, 幅度谱)，后一半为前一半的镜像
+    ///
+    /// 供按完整频谱编写的 f_d 估计器使用，布局与 `PowerSpectralDensity::two_sided_amplitude` 相同
+    pub fn two_sided_magnitude(&self) -> (Vec<f64>, Vec<f64>) {
+        let n = self.num_samples;
+        let frequencies = (0..n).map(|k| k as f64 * self.sample_rate / n as f64).collect();
+        let magnitude = (0..n).map(|k| self.magnitude[k.min(n - k)]).collect();
+        (frequencies, magnitude)
+    }
+
+    /// 由单边频谱恢复时域信号（长度 N）
+    pub fn to_signal(&self) -> Vec<f64> {
+        compute_irfft(&self.spectrum, self.num_samples)
+    }
+
+    /// 执行逆 FFT（输入为完整的 N 点频谱）
+    pub fn ifft(spectrum: &[Complex<f64>]) -> Vec<f64> {
+        compute_ifft(spectrum)
+    }
+
+    /// 获取单边频谱（0 到 Nyquist 频率）
+    pub fn get_single_sided(&self) -> (Vec<f64>, Vec<f64>) {
+        (self.frequencies.clone(), self.magnitude.clone())
+    }
+
+    /// 获取单边频谱的 (频率, 幅度) 序列（不含 Nyquist 频点）
+    pub fn get_single_sided_pairs(&self) -> Vec<(f64, f64)> {
+        let half = self.num_samples / 2;
+        self.frequencies[..half]
+            .iter()
+            .copied()
+            .zip(self.magnitude[..half].iter().copied())
+            .collect()
+    }
+
+    /// 获取 dB 刻度的幅度谱
+    pub fn get_magnitude_db(&self) -> Vec<f64> {
+        self.magnitude
+            .iter()
+            .map(|&m| {
+                if m > 1e-10 {
+                    20.0 * m.log10()
+                } else {
+                    -200.0
+                }
+            })
+            .collect()
+    }
+
+    /// 加窗后计算 FFT，并按 correction 修正窗带来的幅度或能量损失
+    ///
+    /// 使用周期窗：幅度修正后正弦分量在其频点上的幅度（`magnitude`，双边）为 A/2，
+    /// 能量修正后双边幅度谱（[`FftResult::two_sided_magnitude`]）的 Σ|magnitude|² 与未加窗时一样等于信号的均方值
+    pub fn compute_windowed(
+        samples: &[f64],
+        sample_rate: f64,
+        window: Window,
+        correction: Correction,
+    ) -> Result<Self, String> {
+        window.validate(samples.len())?;
+        let coefficients = window.generate(samples.len(), Symmetry::Periodic);
+        let gains = WindowGains::from_coefficients(&coefficients);
+        let factor = correction.factor(&gains);
+        let windowed: Vec<f64> = samples.iter().zip(&coefficients).map(|(x, w)| x * w).collect();
+        let spectrum = compute_rfft(&windowed).into_iter().map(|c| c * factor).collect();
+
+        println!("加窗 FFT 计算完成:");
+        println!("  窗函数: {}（周期窗）", window);
+        println!(
+            "  相干增益 {:.4}，ENBW {:.4} 频点，{:?} 修正系数 {:.4}",
+            gains.coherent_gain, gains.enbw_bins, correction, factor
+        );
+
+        Ok(Self::from_half_spectrum(spectrum, samples.len(), sample_rate))
+    }
+
+    /// 对信号加窗
+    pub fn apply_window(samples: &[f64], window: Window, symmetry: Symmetry) -> Vec<f64> {
+        samples
+            .iter()
+            .zip(window.generate(samples.len(), symmetry))
+            .map(|(&x, w)| x * w)
+            .collect()
+    }
+
+    /// 应用窗函数（Hanning 窗，对称）
+    pub fn apply_hanning_window(samples: &[f64]) -> Vec<f64> {
+        Self::apply_window(samples, Window::Hann, Symmetry::Symmetric)
+    }
+
+    /// 应用窗函数（Hamming 窗，对称）
+    pub fn apply_hamming_window(samples: &[f64]) -> Vec<f64> {
+        Self::apply_window(samples, Window::Hamming, Symmetry::Symmetric)
+    }
+}
+
+/// 细化频谱：在任意频段 [f1, f2] 上以远小于 f_s/N 的间隔计算的 DTFT
+#[derive(Debug, Clone)]
+pub struct ZoomSpectrum {
+    /// DTFT 复数值（未归一化，与 `FftResult::spectrum` 同一尺度）
+    pub spectrum: Vec<Complex<f64>>,
+    /// 频率轴（Hz），从 f1 到 f2 均匀分布
+    pub frequencies: Vec<f64>,
+    /// 幅度谱（除以 N 归一化，与 `FftResult::magnitude` 可直接比较）
+    pub magnitude: Vec<f64>,
+    /// 采样率
+    pub sample_rate: f64,
+    /// 时域信号长度 N
+    pub num_samples: usize,
+}
+
+impl ZoomSpectrum {
+    /// 频率间隔 (Hz)
+    pub fn resolution(&self) -> f64 {
+        if self.frequencies.len() > 1 {
+            self.frequencies[1] - self.frequencies[0]
+        } else {
+            0.0
+        }
+    }
+
+    /// 幅度最大的频点 (频率, 幅度)
+    pub fn peak(&self) -> (f64, f64) {
+        self.frequencies
+            .iter()
+            .copied()
+            .zip(self.magnitude.iter().copied())
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0.0, 0.0))
+    }
+
+    /// 获取 dB 刻度的幅度谱
+    pub fn get_magnitude_db(&self) -> Vec<f64> {
+        self.magnitude
+            .iter()
+            .map(|&m| if m > 1e-10 { 20.0 * m.log10() } else { -200.0 })
+            .collect()
+    }
+}
+
+impl FftResult {
+    /// 细化频谱：用线性调频 Z 变换在 [f1, f2] 内均匀计算 points 个频点的 DTFT
+    ///
+    /// 与补零 FFT 等价，但只计算感兴趣的频段，例如 f_d 附近 ±50 Hz 内以 0.05 Hz 间隔观察频谱
+    pub fn compute_zoom(
+        samples: &[f64],
+        sample_rate: f64,
+        f1: f64,
+        f2: f64,
+        points: usize,
+    ) -> Result<ZoomSpectrum, String> {
+        if samples.is_empty() {
+            return Err("信号为空".to_string());
+        }
+        if !(f1.is_finite() && f2.is_finite()) || f1 >= f2 || points < 2 {
+            return Err(format!(
+                "细化频段 [{}, {}] Hz 或点数 {} 无效（要求 f1 < f2，点数至少为 2）",
+                f1, f2, points
+            ));
+        }
+
+        let n = samples.len();
+        let step = (f2 - f1) / (points - 1) as f64;
+        let spectrum = compute_czt(samples, f1, step, points, sample_rate);
+        let frequencies = (0..points).map(|k| f1 + k as f64 * step).collect();
+        let magnitude = spectrum.iter().map(|c| c.norm() / n as f64).collect();
+
+        println!("细化频谱计算完成:");
+        println!("  频段: {:.2}–{:.2} Hz，{} 点", f1, f2, points);
+        println!(
+            "  频率间隔: {:.4} Hz（DFT 频点间隔 {:.4} Hz）",
+            step,
+            sample_rate / n as f64
+        );
+
+        Ok(ZoomSpectrum {
+            spectrum,
+            frequencies,
+            magnitude,
+            sample_rate,
+            num_samples: n,
+        })
+    }
+}
+
+/// 计算实信号的复数频谱（未归一化），共 N 个频点
+///
+/// 由 [`compute_rfft`] 得到前 N/2+1 个频点，其余按 X[N-k] = conj(X[k]) 补齐，排列与复数 FFT 相同。
+/// 与复数 FFT 不逐位相同：蝶形运算的顺序不同，各频点相差数个 ULP（相对最大频点约 1e-16）。
+/// 只需要单边频谱时应直接使用 [`compute_rfft`]，不必分配另一半。
+pub fn compute_fft(samples: &[f64]) -> Vec<Complex<f64>> {
+    let n = samples.len();
+    let mut spectrum = compute_rfft(samples);
+    spectrum.reserve(n.saturating_sub(spectrum.len()));
+    for k in spectrum.len()..n {
+        let mirrored = spectrum[n - k].conj();
+        spectrum.push(mirrored);
+    }
+    spectrum
+}
+
+/// 计算实信号的单边频谱（未归一化），共 N/2+1 个频点（0 到 Nyquist）
+pub fn compute_rfft(samples: &[f64]) -> Vec<Complex<f64>> {
+    let n = samples.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let rfft = plan_rfft(n);
+    let mut input = samples.to_vec();
+    let mut spectrum = rfft.make_output_vec();
+    rfft.process(&mut input, &mut spectrum)
+        .expect("实数 FFT 的缓冲区长度由规划器给出");
+    spectrum
+}
+
+/// 由单边频谱（N/2+1 个频点）计算长度为 n 的实信号，已归一化
+///
+/// 直流与 Nyquist 频点的虚部被忽略（实信号的这两个频点必为实数）。
+/// half.len() 必须等于 n/2 + 1。
+pub fn compute_irfft(half: &[Complex<f64>], n: usize) -> Vec<f64> {
+    if n == 0 {
+        return Vec::new();
+    }
+    assert_eq!(half.len(), n / 2 + 1, "单边频谱长度应为 n/2 + 1");
+    let irfft = plan_irfft(n);
+    let mut input = half.to_vec();
+    input[0].im = 0.0;
+    if n.is_multiple_of(2) {
+        input[n / 2].im = 0.0;
+    }
+    let mut output = irfft.make_output_vec();
+    irfft.process(&mut input, &mut output)
+        .expect("实数 IFFT 的缓冲区长度由规划器给出");
+    output.iter().map(|x| x / n as f64).collect()
+}
+
+/// 计算逆 FFT 并返回归一化后的实部
+///
+/// Re(IFFT(X)) 等于 X 的共轭对称部分 (X[k] + conj X[N-k])/2 的逆变换，
+/// 因此只需一次长度 N 的复数→实数变换，频谱不必事先满足共轭对称。
+pub fn compute_ifft(spectrum: &[Complex<f64>]) -> Vec<f64> {
+    let n = spectrum.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let half: Vec<Complex<f64>> = (0..n / 2 + 1)
+        .map(|k| (spectrum[k] + spectrum[(n - k) % n].conj()) / 2.0)
+        .collect();
+    compute_irfft(&half, n)
+}
+
+/// 计算逆 FFT 并返回归一化后的复数序列（例如由单边频谱得到解析信号）
+pub fn compute_ifft_complex(spectrum: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let n = spectrum.len();
+    let ifft = plan_fft(n, true);
+
+    let mut buffer = spectrum.to_vec();
+    ifft.process(&mut buffer);
+
+    // 归一化
+    buffer
+        .iter()
+        .map(|c| c / n as f64)
+        .collect()
+}
+
+/// 线性调频 Z 变换（Bluestein 算法）：在 f_start + k·f_step (k = 0..m) 处计算 DTFT
+///
+/// X[k] = Σ x[n]·e^{-j2π(f_start + k·f_step)n/f_s}，频点间隔不受 f_s/N 限制。
+/// 利用 nk = (n² + k² - (k - n)²)/2 把求和化为与线性调频序列的卷积，用 FFT 计算。
+pub fn compute_czt(
+    samples: &[f64],
+    f_start: f64,
+    f_step: f64,
+    m: usize,
+    sample_rate: f64,
+) -> Vec<Complex<f64>> {
+    let n = samples.len();
+    if n == 0 || m == 0 {
+        return vec![Complex::new(0.0, 0.0); m];
+    }
+
+    // W^{i²/2}，i² 以整数计算，避免大下标时的舍入误差
+    let chirp = |i: usize| Complex::from_polar(1.0, -PI * f_step / sample_rate * (i * i) as f64);
+    let len = (n + m - 1).next_power_of_two();
+
+    let mut y = vec![Complex::new(0.0, 0.0); len];
+    for (i, (value, &x)) in y.iter_mut().zip(samples).enumerate() {
+        let shift = Complex::from_polar(1.0, -2.0 * PI * f_start * i as f64 / sample_rate);
+        *value = x * shift * chirp(i);
+    }
+    let mut v = vec![Complex::new(0.0, 0.0); len];
+    for (i, value) in v.iter_mut().enumerate().take(m) {
+        *value = chirp(i).conj();
+    }
+    for i in 1..n {
+        v[len - i] = chirp(i).conj();
+    }
+
+    let fft = plan_fft(len, false);
+    fft.process(&mut y);
+    fft.process(&mut v);
+    let mut product: Vec<Complex<f64>> = y.iter().zip(&v).map(|(a, b)| a * b).collect();
+    plan_fft(len, true).process(&mut product);
+
+    (0..m)
+        .map(|k| chirp(k) * product[k] / len as f64)
+        .collect()
+}
+
+/// 频谱搬移（循环移位）
+pub fn circshift(spectrum: &[Complex<f64>], shift: isize) -> Vec<Complex<f64>> {
+    let n = spectrum.len();
+    let shift = shift.rem_euclid(n as isize) as usize;
+    
+    let mut result = vec![Complex::new(0.0, 0.0); n];
+    for (i, &value) in spectrum.iter().enumerate() {
+        result[(i + shift) % n] = value;
+    }
+    result
+}
+
+/// 计算频域搬移后的和（用于解调）
+pub fn frequency_shift_and_add(
+    spectrum: &[Complex<f64>],
+    shift_hz: f64,
+    sample_rate: f64,
+) -> Vec<Complex<f64>> {
+    let n = spectrum.len();
+    let shift_bins = (shift_hz * n as f64 / sample_rate).round() as isize;
+
+    // 正向搬移和负向搬移
+    let shifted_pos = circshift(spectrum, shift_bins);
+    let shifted_neg = circshift(spectrum, -shift_bins);
+
+    // 相加并除以 2
+    shifted_pos
+        .iter()
+        .zip(shifted_neg.iter())
+        .map(|(a, b)| (a + b) / 2.0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fft() {
+        // 测试 FFT 和 IFFT
+        let sample_rate = 1000.0;
+        let duration = 1.0;
+        let n = (sample_rate * duration) as usize;
+        
+        // 生成测试信号：10 Hz 正弦波
+        let samples: Vec<f64> = (0..n)
+            .map(|i| (2.0 * PI * 10.0 * i as f64 / sample_rate).sin())
+            .collect();
+
+        let fft_result = FftResult::compute(&samples, sample_rate);
+        assert_eq!(fft_result.magnitude.len(), n / 2 + 1);
+        assert_eq!(fft_result.frequencies.len(), n / 2 + 1);
+        assert_eq!(fft_result.num_samples, n);
+
+        // 测试 IFFT：单边频谱直接恢复，与展开后的完整频谱结果相同
+        let reconstructed = fft_result.to_signal();
+        assert_eq!(reconstructed.len(), n);
+        assert_eq!(FftResult::ifft(&fft_result.full_spectrum()), reconstructed);
+        
+        // 验证重构误差
+        let error: f64 = samples
+            .iter()
+            .zip(reconstructed.iter())
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt()
+            / n as f64;
+        assert!(error < 1e-10);
+    }
+
+    #[test]
+    fn test_czt_matches_fft() {
+        let sample_rate = 1000.0;
+        let samples: Vec<f64> = (0..100)
+            .map(|i| (2.0 * PI * 37.3 * i as f64 / sample_rate).sin() + 0.1 * i as f64)
+            .collect();
+
+        // 以 f_s/N 为步长覆盖整个频率轴时与 DFT 一致
+        let dft = compute_fft(&samples);
+        let czt = compute_czt(&samples, 0.0, sample_rate / 100.0, 100, sample_rate);
+        for (a, b) in dft.iter().zip(&czt) {
+            assert!((a - b).norm() < 1e-9);
+        }
+
+        // 任意起点与步长：与直接计算的 DTFT 一致
+        let czt = compute_czt(&samples, 30.05, 0.1, 150, sample_rate);
+        for (k, value) in czt.iter().enumerate() {
+            let f = 30.05 + 0.1 * k as f64;
+            let dtft: Complex<f64> = samples
+                .iter()
+                .enumerate()
+                .map(|(i, &x)| x * Complex::from_polar(1.0, -2.0 * PI * f * i as f64 / sample_rate))
+                .sum();
+            assert!((value - dtft).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_zoom_spectrum() {
+        // 1 s、f_s = 1000 Hz，DFT 频点间隔 1 Hz；位于两个频点之间的正弦在细化频谱中能定位到 0.01 Hz
+        let sample_rate = 1000.0;
+        let samples: Vec<f64> = (0..1000)
+            .map(|i| {
+                let t = i as f64 / sample_rate;
+                (2.0 * PI * 100.25 * t).cos()
+            })
+            .collect();
+
+        let zoom = FftResult::compute_zoom(&samples, sample_rate, 95.0, 105.0, 1001).unwrap();
+        assert_eq!(zoom.frequencies.len(), 1001);
+        assert!((zoom.resolution() - 0.01).abs() < 1e-12);
+        let (peak_frequency, peak_magnitude) = zoom.peak();
+        assert!((peak_frequency - 100.25).abs() < 0.011);
+        // 余弦幅度 1，单边谱峰值为 1/2
+        assert!((peak_magnitude - 0.5).abs() < 1e-3);
+
+        // 在 DFT 频点上与 FftResult 的幅度一致
+        let fft = FftResult::from_spectrum(compute_fft(&samples), sample_rate);
+        let at_100 = zoom.frequencies.iter().position(|&f| (f - 100.0).abs() < 1e-9).unwrap();
+        assert!((zoom.magnitude[at_100] - fft.magnitude[100]).abs() < 1e-9);
+
+        assert!(FftResult::compute_zoom(&samples, sample_rate, 105.0, 95.0, 10).is_err());
+        assert!(FftResult::compute_zoom(&samples, sample_rate, 95.0, 105.0, 1).is_err());
+    }
+
+    #[test]
+    fn test_circshift() {
+        let data: Vec<Complex<f64>> = (0..5)
+            .map(|i| Complex::new(i as f64, 0.0))
+            .collect();
+        
+        let shifted = circshift(&data, 2);
+        assert_eq!(shifted[0].re, 3.0);
+        assert_eq!(shifted[1].re, 4.0);
+        assert_eq!(shifted[2].re, 0.0);
+    }
+
+    #[test]
+    fn test_windowed_spectrum_corrections() {
+        let (fs, n) = (8000.0, 4096);
+        // 频率落在两个频点正中，幅度修正后平顶窗仍能读出准确幅度
+        let f0 = 1000.5 * fs / n as f64;
+        let amplitude = 0.7;
+        let samples: Vec<f64> = (0..n)
+            .map(|i| amplitude * (2.0 * PI * f0 * i as f64 / fs).cos())
+            .collect();
+
+        let flat_top = FftResult::compute_windowed(&samples, fs, Window::FlatTop, Correction::Amplitude).unwrap();
+        let peak = flat_top.magnitude[..n / 2].iter().cloned().fold(0.0, f64::max);
+        assert!((peak / (amplitude / 2.0) - 1.0).abs() < 0.01, "flat-top peak {}", peak);
+
+        let hann = FftResult::compute_windowed(&samples, fs, Window::Hann, Correction::Amplitude).unwrap();
+        let peak = hann.magnitude[..n / 2].iter().cloned().fold(0.0, f64::max);
+        // Hann 窗在半个频点处损失约 1.42 dB
+        assert!((20.0 * (peak / (amplitude / 2.0)).log10() + 1.42).abs() < 0.05);
+
+        // 能量修正后 Σ|X/N|² 等于均方值 A²/2
+        for window in [Window::Hann, Window::BlackmanHarris, Window::Kaiser(8.6)] {
+            let spectrum = FftResult::compute_windowed(&samples, fs, window, Correction::Energy).unwrap();
+            let energy: f64 = spectrum.two_sided_magnitude().1.iter().map(|m| m * m).sum();
+            assert!((energy / (amplitude * amplitude / 2.0) - 1.0).abs() < 0.02, "{}: {}", window, energy);
+        }
+
+        assert!(FftResult::compute_windowed(&samples, fs, Window::Tukey(2.0), Correction::Amplitude).is_err());
+    }
+
+    #[test]
+    fn test_real_fft_matches_complex_fft() {
+        // 奇偶长度都与复数 FFT 一致，只差数个 ULP（不逐位相同）
+        for n in [1, 2, 7, 64, 1001, 31265] {
+            let samples: Vec<f64> = (0..n)
+                .map(|i| (0.37 * i as f64).sin() + 0.2 * (1.3 * i as f64).cos() + 0.05)
+                .collect();
+            let mut reference: Vec<Complex<f64>> = samples.iter().map(|&x| Complex::new(x, 0.0)).collect();
+            FftPlanner::new().plan_fft_forward(n).process(&mut reference);
+            let scale = reference.iter().map(|c| c.norm()).fold(0.0, f64::max);
+
+            let spectrum = compute_fft(&samples);
+            assert_eq!(spectrum.len(), n);
+            let error = spectrum.iter().zip(&reference).map(|(a, b)| (a - b).norm()).fold(0.0, f64::max);
+            assert!(error < 1e-14 * scale, "n = {}: {}", n, error);
+
+            let half = compute_rfft(&samples);
+            assert_eq!(half.len(), n / 2 + 1);
+            assert!(half.iter().zip(&spectrum).all(|(a, b)| a == b));
+
+            let restored = compute_irfft(&half, n);
+            let error = restored.iter().zip(&samples).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+            assert!(error < 1e-12, "n = {}: {}", n, error);
+        }
+    }
+
+    #[test]
+    fn test_fft_result_expands_to_complex_fft() {
+        // FftResult 只保存 N/2+1 个频点，展开后与旧的复数 FFT 输出一致
+        let sample_rate = 8000.0;
+        for n in [1, 2, 7, 64, 1001, 4096] {
+            let samples: Vec<f64> = (0..n)
+                .map(|i| (0.37 * i as f64).sin() + 0.2 * (1.3 * i as f64).cos() + 0.05)
+                .collect();
+            let mut reference: Vec<Complex<f64>> = samples.iter().map(|&x| Complex::new(x, 0.0)).collect();
+            FftPlanner::new().plan_fft_forward(n).process(&mut reference);
+            let scale = reference.iter().map(|c| c.norm()).fold(0.0, f64::max);
+
+            let fft = FftResult::compute(&samples, sample_rate);
+            assert_eq!(fft.spectrum.len(), n / 2 + 1);
+            let full = fft.full_spectrum();
+            assert_eq!(full.len(), n);
+            let error = full.iter().zip(&reference).map(|(a, b)| (a - b).norm()).fold(0.0, f64::max);
+            assert!(error < 1e-14 * scale, "n = {}: {}", n, error);
+            assert_eq!(full, compute_fft(&samples));
+
+            // 双边幅度谱与旧布局（频率 0 到 f_s，幅度 |X[k]|/N）相同
+            let (frequencies, magnitude) = fft.two_sided_magnitude();
+            assert_eq!(frequencies.len(), n);
+            for k in 0..n {
+                assert_eq!(frequencies[k], k as f64 * sample_rate / n as f64);
+                assert!((magnitude[k] - reference[k].norm() / n as f64).abs() < 1e-14 * scale);
+            }
+            assert_eq!(FftResult::from_spectrum(full, sample_rate).spectrum, fft.spectrum);
+        }
+    }
+
+    #[test]
+    fn test_ifft_of_non_hermitian_spectrum() {
+        // 频谱不共轭对称时（如单边搬移后的频谱），结果仍等于复数 IFFT 的实部
+        for n in [8, 9, 1000] {
+            let spectrum: Vec<Complex<f64>> = (0..n)
+                .map(|k| Complex::new((0.7 * k as f64).cos(), (1.9 * k as f64).sin()))
+                .collect();
+            let reference = compute_ifft_complex(&spectrum);
+            let real = compute_ifft(&spectrum);
+            assert_eq!(real.len(), n);
+            let error = real.iter().zip(&reference).map(|(a, b)| (a - b.re).abs()).fold(0.0, f64::max);
+            assert!(error < 1e-13, "n = {}: {}", n, error);
+        }
+        assert!(compute_fft(&[]).is_empty());
+        assert!(compute_ifft(&[]).is_empty());
+    }
+}
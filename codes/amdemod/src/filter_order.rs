@@ -0,0 +1,312 @@
+// 最小阶数估计模块
+// 由通带边缘、阻带边缘、通带最大衰减与阻带最小衰减求各族满足指标的最小阶数和自然截止频率，
+// 与 MATLAB buttord / cheb1ord / cheb2ord / ellipord 对应（Bessel 没有闭式公式，按阶数逐一试探）。
+// 只处理低通与高通：通带边缘低于阻带边缘为低通，反之为高通
+
+use crate::analog_prototype::{self, ellipk, ellipkm1, FilterFamily};
+use crate::butterworth_filter::{FilterType, IirDesign};
+use num_complex::Complex;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Bessel 试探的最高阶数
+const MAX_BESSEL_ORDER: usize = 25;
+
+/// 低通或高通的设计指标
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilterSpec {
+    /// 通带边缘 (Hz)
+    pub passband_edge: f64,
+    /// 阻带边缘 (Hz)
+    pub stopband_edge: f64,
+    /// 通带内允许的最大衰减 (dB)
+    pub ripple_db: f64,
+    /// 阻带内要求的最小衰减 (dB)
+    pub attenuation_db: f64,
+    pub sample_rate: f64,
+}
+
+impl FilterSpec {
+    pub fn filter_type(&self) -> FilterType {
+        if self.passband_edge < self.stopband_edge {
+            FilterType::Lowpass
+        } else {
+            FilterType::Highpass
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let nyquist = self.sample_rate / 2.0;
+        for (name, edge) in [("passband_edge", self.passband_edge), ("stopband_edge", self.stopband_edge)] {
+            if !(edge > 0.0 && edge < nyquist) {
+                return Err(format!("{} = {} Hz must lie strictly between 0 and f_s/2 = {} Hz", name, edge, nyquist));
+            }
+        }
+        if self.passband_edge == self.stopband_edge {
+            return Err("passband and stopband edges must differ".to_string());
+        }
+        if !(self.ripple_db > 0.0 && self.attenuation_db > self.ripple_db) {
+            return Err(format!(
+                "need 0 < ripple_db < attenuation_db, got {} and {}",
+                self.ripple_db, self.attenuation_db
+            ));
+        }
+        Ok(())
+    }
+
+    /// 预畸变后的边缘 tan(π·f/f_s)（省去公共因子 2f_s）
+    fn prewarped(&self) -> (f64, f64) {
+        let warp = |f: f64| (PI * f / self.sample_rate).tan();
+        (warp(self.passband_edge), warp(self.stopband_edge))
+    }
+
+    /// 模拟原型上阻带边缘与通带边缘之比（> 1），高通按 Ω → 1/Ω 换算
+    fn selectivity(&self) -> f64 {
+        let (pass, stop) = self.prewarped();
+        match self.filter_type() {
+            FilterType::Highpass => pass / stop,
+            _ => stop / pass,
+        }
+    }
+
+    /// 原型上的频率 Ω 换回数字频率 (Hz)：Ω 相对通带边缘取值，高通取倒数
+    fn prototype_to_hertz(&self, omega: f64) -> f64 {
+        let (pass, _) = self.prewarped();
+        let warped = match self.filter_type() {
+            FilterType::Highpass => pass / omega,
+            _ => pass * omega,
+        };
+        self.sample_rate / PI * warped.atan()
+    }
+
+    /// (10^{Rs/10} - 1) / (10^{Rp/10} - 1) 的平方根，即阻带与通带的判别比
+    fn discrimination(&self) -> f64 {
+        ((10f64.powf(0.1 * self.attenuation_db) - 1.0) / (10f64.powf(0.1 * self.ripple_db) - 1.0)).sqrt()
+    }
+}
+
+/// 最小阶数估计的结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderEstimate {
+    /// 波纹、衰减参数已按指标填好的族
+    pub family: FilterFamily,
+    pub order: usize,
+    /// 自然截止频率 (Hz)，含义见 `FilterFamily`，可直接作为设计的 cutoff
+    pub cutoff: f64,
+    pub filter_type: FilterType,
+    pub sample_rate: f64,
+}
+
+impl OrderEstimate {
+    /// 按估计的阶数与截止频率设计数字滤波器
+    pub fn design(&self) -> Result<IirDesign, String> {
+        IirDesign::with_family(self.family, self.order, self.cutoff, self.sample_rate, self.filter_type)
+    }
+}
+
+/// 满足指标的最小阶数与自然截止频率
+///
+/// 只看 family 属于哪一族，其中的波纹、衰减参数以指标为准。
+/// Butterworth、Chebyshev II 的截止频率落在两个边缘之间，使通带边缘的衰减恰为 ripple_db；
+/// Chebyshev I、椭圆的截止频率就是通带边缘；Bessel 与 Butterworth 相同，按 -3 dB 截止频率给出
+pub fn minimum_order(family: &FilterFamily, spec: &FilterSpec) -> Result<OrderEstimate, String> {
+    spec.validate()?;
+    let nat = spec.selectivity();
+    let discrimination = spec.discrimination();
+    let (family, order, cutoff) = match family {
+        FilterFamily::Butterworth => {
+            // buttord：(Ω_s)^{2N} ≥ (10^{Rs/10} - 1)/(10^{Rp/10} - 1)
+            let order = ceil_order(discrimination.ln() / nat.ln());
+            let passband_ratio = (10f64.powf(0.1 * spec.ripple_db) - 1.0).powf(0.5 / order as f64);
+            (FilterFamily::Butterworth, order, spec.prototype_to_hertz(1.0 / passband_ratio))
+        }
+        FilterFamily::Chebyshev1 { .. } => {
+            let order = ceil_order(discrimination.acosh() / nat.acosh());
+            (FilterFamily::Chebyshev1 { ripple_db: spec.ripple_db }, order, spec.passband_edge)
+        }
+        FilterFamily::Chebyshev2 { .. } => {
+            // 阶数与 Chebyshev I 相同；截止频率取在阻带衰减恰好达到 Rs 处
+            let order = ceil_order(discrimination.acosh() / nat.acosh());
+            let stop_ratio = (discrimination.acosh() / order as f64).cosh();
+            (FilterFamily::Chebyshev2 { attenuation_db: spec.attenuation_db }, order, spec.prototype_to_hertz(stop_ratio))
+        }
+        FilterFamily::Elliptic { .. } => {
+            // ellipord：N ≥ K(1/Ω_s²)·K'(k1²) / (K'(1/Ω_s²)·K(k1²))，k1 = 1/判别比
+            let m0 = 1.0 / (nat * nat);
+            let m1 = 1.0 / (discrimination * discrimination);
+            let order = ceil_order(ellipk(m0) * ellipkm1(m1) / (ellipkm1(m0) * ellipk(m1)));
+            let family = FilterFamily::Elliptic { ripple_db: spec.ripple_db, attenuation_db: spec.attenuation_db };
+            (family, order, spec.passband_edge)
+        }
+        FilterFamily::Bessel => {
+            let (order, passband_ratio) = bessel_order(spec, nat)?;
+            (FilterFamily::Bessel, order, spec.prototype_to_hertz(1.0 / passband_ratio))
+        }
+    };
+    Ok(OrderEstimate { family, order, cutoff, filter_type: spec.filter_type(), sample_rate: spec.sample_rate })
+}
+
+/// 取整为阶数，去掉浮点误差造成的多余一阶
+fn ceil_order(value: f64) -> usize {
+    ((value - 1e-9).ceil() as usize).max(1)
+}
+
+/// 逐阶试探 Bessel 原型：返回阶数及通带边缘在原型上的频率（-3 dB 点为 1）
+fn bessel_order(spec: &FilterSpec, nat: f64) -> Result<(usize, f64), String> {
+    for order in 1..=MAX_BESSEL_ORDER {
+        let prototype = analog_prototype::prototype(&FilterFamily::Bessel, order)?;
+        let loss = |w: f64| -20.0 * prototype.evaluate(Complex::new(0.0, w)).norm().log10();
+        let passband = frequency_at_loss(&loss, spec.ripple_db);
+        let stopband = frequency_at_loss(&loss, spec.attenuation_db);
+        if stopband / passband <= nat {
+            return Ok((order, passband));
+        }
+    }
+    Err(format!(
+        "Bessel filter cannot meet the specification with order ≤ {}; widen the transition band",
+        MAX_BESSEL_ORDER
+    ))
+}
+
+/// 单调增加的衰减曲线上衰减恰为 target_db 的频率：先倍增找到区间，再二分
+fn frequency_at_loss(loss: &dyn Fn(f64) -> f64, target_db: f64) -> f64 {
+    let mut high = 1.0;
+    while loss(high) < target_db {
+        high *= 2.0;
+    }
+    let mut low = 0.0;
+    for _ in 0..200 {
+        let mid = 0.5 * (low + high);
+        if loss(mid) < target_db {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// 以截止频率为基准的过渡带指标，用来代替固定阶数
+///
+/// 截止频率保持不变（高通在 f_d、低通在 f_B），过渡带放在截止频率的阻带一侧；
+/// Chebyshev II 的截止频率本身是阻带边缘，过渡带放在通带一侧
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TransitionSpec {
+    /// 过渡带宽度 (Hz)
+    pub width: f64,
+    /// 通带边缘允许的最大衰减 (dB)，Chebyshev I、II 与椭圆使用
+    pub ripple_db: f64,
+    /// 阻带边缘要求的最小衰减 (dB)
+    pub attenuation_db: f64,
+}
+
+impl TransitionSpec {
+    /// 截止频率在 cutoff 的 filter_type 滤波器对应的完整指标
+    pub fn around(&self, family: &FilterFamily, filter_type: FilterType, cutoff: f64, sample_rate: f64) -> FilterSpec {
+        let toward_stopband = match filter_type {
+            FilterType::Highpass => -self.width,
+            _ => self.width,
+        };
+        let (passband_edge, stopband_edge, ripple_db) = match family {
+            // 截止频率处衰减 3 dB
+            FilterFamily::Butterworth | FilterFamily::Bessel => {
+                (cutoff, cutoff + toward_stopband, -20.0 * family.cutoff_gain().log10())
+            }
+            FilterFamily::Chebyshev1 { .. } | FilterFamily::Elliptic { .. } => {
+                (cutoff, cutoff + toward_stopband, self.ripple_db)
+            }
+            FilterFamily::Chebyshev2 { .. } => (cutoff - toward_stopband, cutoff, self.ripple_db),
+        };
+        FilterSpec { passband_edge, stopband_edge, ripple_db, attenuation_db: self.attenuation_db, sample_rate }
+    }
+
+    /// 截止频率保持在 cutoff 时满足本指标的最小阶数，以及参数已填好的族
+    pub fn order_at(
+        &self,
+        family: &FilterFamily,
+        filter_type: FilterType,
+        cutoff: f64,
+        sample_rate: f64,
+    ) -> Result<(usize, FilterFamily), String> {
+        let estimate = minimum_order(family, &self.around(family, filter_type, cutoff, sample_rate))?;
+        Ok((estimate.order, estimate.family))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loss_db(filter: &IirDesign, f: f64) -> f64 {
+        let z = Complex::from_polar(1.0, 2.0 * PI * f / filter.sample_rate);
+        -20.0 * filter.zpk().unwrap().evaluate(z).norm().log10()
+    }
+
+    const FAMILIES: [FilterFamily; 4] = [
+        FilterFamily::Butterworth,
+        FilterFamily::Chebyshev1 { ripple_db: 0.0 },
+        FilterFamily::Chebyshev2 { attenuation_db: 0.0 },
+        FilterFamily::Elliptic { ripple_db: 0.0, attenuation_db: 0.0 },
+    ];
+
+    fn assert_meets(filter: &IirDesign, spec: &FilterSpec) {
+        assert_eq!(filter.filter_type, spec.filter_type());
+        assert!(loss_db(filter, spec.passband_edge) <= spec.ripple_db + 1e-6, "{}", filter.family);
+        assert!(loss_db(filter, spec.stopband_edge) >= spec.attenuation_db - 1e-6, "{}", filter.family);
+    }
+
+    #[test]
+    fn test_designs_meet_the_specification() {
+        // 高通：f_d = 3000 Hz 以上为通带，2700 Hz 以下衰减 60 dB；低通同理
+        let specs = [
+            FilterSpec { passband_edge: 3000.0, stopband_edge: 2700.0, ripple_db: 1.0, attenuation_db: 60.0, sample_rate: 22050.0 },
+            FilterSpec { passband_edge: 4000.0, stopband_edge: 5000.0, ripple_db: 0.5, attenuation_db: 40.0, sample_rate: 22050.0 },
+        ];
+        for spec in specs {
+            let mut orders = Vec::new();
+            for family in FAMILIES {
+                let estimate = minimum_order(&family, &spec).unwrap();
+                assert_meets(&estimate.design().unwrap(), &spec);
+                orders.push(estimate.order);
+
+                // 少一阶即不满足：公式给出的是任何截止频率下都达不到指标的下界
+                if estimate.order > 1 {
+                    let lower = OrderEstimate { order: estimate.order - 1, ..estimate }.design().unwrap();
+                    assert!(
+                        loss_db(&lower, spec.stopband_edge) < spec.attenuation_db
+                            || loss_db(&lower, spec.passband_edge) > spec.ripple_db,
+                        "{} order {} is not minimal",
+                        estimate.family,
+                        estimate.order
+                    );
+                }
+            }
+            // 同一指标下：椭圆 ≤ Chebyshev I = Chebyshev II ≤ Butterworth
+            assert!(orders[3] <= orders[1] && orders[1] == orders[2] && orders[2] <= orders[0]);
+        }
+
+        // 过渡带很缓的 Bessel 只能满足宽松的指标
+        let spec = FilterSpec { passband_edge: 1000.0, stopband_edge: 6000.0, ripple_db: 1.0, attenuation_db: 30.0, sample_rate: 22050.0 };
+        let estimate = minimum_order(&FilterFamily::Bessel, &spec).unwrap();
+        assert_meets(&estimate.design().unwrap(), &spec);
+        let lower = OrderEstimate { order: estimate.order - 1, ..estimate }.design().unwrap();
+        assert!(loss_db(&lower, spec.stopband_edge) < spec.attenuation_db || loss_db(&lower, spec.passband_edge) > spec.ripple_db);
+        let strict = FilterSpec { stopband_edge: 1100.0, ..spec };
+        assert!(minimum_order(&FilterFamily::Bessel, &strict).unwrap_err().contains("widen"));
+    }
+
+    #[test]
+    fn test_transition_spec_keeps_cutoff() {
+        let transition = TransitionSpec { width: 300.0, ripple_db: 1.0, attenuation_db: 60.0 };
+        for family in FAMILIES {
+            for (filter_type, cutoff) in [(FilterType::Highpass, 3000.0), (FilterType::Lowpass, 4000.0)] {
+                let (order, family) = transition.order_at(&family, filter_type, cutoff, 22050.0).unwrap();
+                let filter = IirDesign::with_family(family, order, cutoff, 22050.0, filter_type).unwrap();
+                assert_meets(&filter, &transition.around(&family, filter_type, cutoff, 22050.0));
+            }
+        }
+
+        let spec = FilterSpec { passband_edge: 3000.0, stopband_edge: 3000.0, ripple_db: 1.0, attenuation_db: 60.0, sample_rate: 22050.0 };
+        assert!(minimum_order(&FilterFamily::Butterworth, &spec).is_err());
+    }
+}
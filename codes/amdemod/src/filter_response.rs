@@ -0,0 +1,250 @@
+use num_complex::Complex;
+use std::f64::consts::PI;
+use crate::analog_system::AnalogSystem;
+use crate::butterworth_filter::IirDesign;
+
+/// Frequency response of a filter
+pub struct FilterResponse {
+    pub frequencies: Vec<f64>,
+    pub magnitude: Vec<f64>,
+    pub phase: Vec<f64>,
+    pub complex_response: Vec<Complex<f64>>,
+}
+
+impl FilterResponse {
+    /// Compute frequency response of a filter at specified frequency points
+    pub fn compute(filter: &IirDesign, sample_rate: f64, num_points: usize) -> Self {
+        let mut frequencies = Vec::with_capacity(num_points / 2 + 1);
+        let mut magnitude = Vec::with_capacity(num_points / 2 + 1);
+        let mut phase = Vec::with_capacity(num_points / 2 + 1);
+        let mut complex_response = Vec::with_capacity(num_points / 2 + 1);
+
+        // Generate frequency points from 0 to Nyquist frequency
+        for k in 0..=(num_points / 2) {
+            let freq = k as f64 * sample_rate / num_points as f64;
+            frequencies.push(freq);
+
+            // Compute H(e^(jω)) at this frequency
+            let omega = 2.0 * PI * freq / sample_rate;
+            let h = frequency_response_at_omega(filter, omega);
+
+            complex_response.push(h);
+            magnitude.push(h.norm());
+            phase.push(h.arg());
+        }
+
+        Self {
+            frequencies,
+            magnitude,
+            phase,
+            complex_response,
+        }
+    }
+
+    /// Phase with the 2π jumps of `phase` removed
+    pub fn unwrapped_phase(&self) -> Vec<f64> {
+        unwrap_phase(&self.phase)
+    }
+
+    /// Build a response from complex values H at the given frequencies (Hz)
+    pub fn from_complex(frequencies: Vec<f64>, complex_response: Vec<Complex<f64>>) -> Self {
+        let magnitude = complex_response.iter().map(|h| h.norm()).collect();
+        let phase = complex_response.iter().map(|h| h.arg()).collect();
+        Self {
+            frequencies,
+            magnitude,
+            phase,
+            complex_response,
+        }
+    }
+
+    /// Digital response H(e^(jω)) on the full DFT grid f = k·f_s/N, k = 0..N-1
+    ///
+    /// H(e^(jω)) is periodic in f_s, so bins above N/2 repeat the negative frequencies
+    pub fn compute_dft_grid(filter: &IirDesign, num_points: usize) -> Self {
+        let frequencies = dft_frequencies(filter.sample_rate, num_points);
+        let response = freqz(filter, &frequencies);
+        Self::from_complex(frequencies, response)
+    }
+
+    /// Analog response H(j2πf) on the same grid f = k·f_s/N, k = 0..N-1
+    ///
+    /// Unlike the digital response this is not periodic: bins above N/2 are evaluated at f itself
+    pub fn compute_analog_dft_grid(system: &AnalogSystem, sample_rate: f64, num_points: usize) -> Self {
+        let frequencies = dft_frequencies(sample_rate, num_points);
+        let response = freqs(system, &frequencies);
+        Self::from_complex(frequencies, response)
+    }
+}
+
+/// Frequencies f_k = k·f_s/N (Hz) of all N DFT bins of an N-sample recording
+pub fn dft_frequencies(sample_rate: f64, num_points: usize) -> Vec<f64> {
+    (0..num_points)
+        .map(|k| k as f64 * sample_rate / num_points as f64)
+        .collect()
+}
+
+/// Analog frequency response H(j2πf) at the given frequencies in Hz (MATLAB `freqs` / `freqresp`)
+pub fn freqs(system: &AnalogSystem, frequencies: &[f64]) -> Vec<Complex<f64>> {
+    frequencies.iter().map(|&f| system.frequency_response(f)).collect()
+}
+
+/// Digital frequency response H(e^(j2πf/f_s)) at the given frequencies in Hz (MATLAB `freqz(b, a, f, fs)`)
+pub fn freqz(filter: &IirDesign, frequencies: &[f64]) -> Vec<Complex<f64>> {
+    frequencies
+        .iter()
+        .map(|&f| frequency_response_at_omega(filter, 2.0 * PI * f / filter.sample_rate))
+        .collect()
+}
+
+/// Magnitude difference 20·log10|H_other| - 20·log10|H_reference| (dB) at each common frequency
+///
+/// With the analog prototype as reference this is the error introduced by the bilinear transform
+pub fn magnitude_error_db(reference: &FilterResponse, other: &FilterResponse) -> Vec<f64> {
+    reference
+        .magnitude
+        .iter()
+        .zip(&other.magnitude)
+        .map(|(&r, &o)| magnitude_to_db(o) - magnitude_to_db(r))
+        .collect()
+}
+
+/// Analog level (dB) below which bins are left out of [`max_warping_error`]: there both responses are
+/// in the stopband and their dB difference says little about the frequency warping
+pub const WARPING_FLOOR_DB: f64 = -60.0;
+
+/// Largest bilinear warping error between an analog and a digital response on the same DFT grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WarpingError {
+    /// DFT bin k of the largest error
+    pub bin: usize,
+    /// Frequency of that bin (Hz)
+    pub frequency: f64,
+    /// Signed error 20·log10|H_digital| - 20·log10|H_analog| (dB)
+    pub error_db: f64,
+}
+
+/// Largest |warping error| up to f_s/2 (bins 0..=N/2) where the analog response is above [`WARPING_FLOOR_DB`]
+///
+/// Above f_s/2 the digital response repeats the negative frequencies, so the two no longer describe
+/// the same physical frequency
+pub fn max_warping_error(analog: &FilterResponse, digital: &FilterResponse) -> WarpingError {
+    let error = magnitude_error_db(analog, digital);
+    let (bin, error_db) = error[..=error.len() / 2]
+        .iter()
+        .enumerate()
+        .filter(|&(k, _)| magnitude_to_db(analog.magnitude[k]) > WARPING_FLOOR_DB)
+        .fold((0, 0.0f64), |best, (k, &e)| if e.abs() > best.1.abs() { (k, e) } else { best });
+    WarpingError {
+        bin,
+        frequency: digital.frequencies[bin],
+        error_db,
+    }
+}
+
+/// Calculate frequency response H(e^(jω)) for a given normalized frequency ω
+pub fn frequency_response_at_omega(filter: &IirDesign, omega: f64) -> Complex<f64> {
+    // H(e^(jω)) = B(e^(jω)) / A(e^(jω))
+    // where B(e^(jω)) = Σ b[k] * e^(-jωk)
+    //       A(e^(jω)) = Σ a[k] * e^(-jωk)
+
+    let mut numerator = Complex::new(0.0, 0.0);
+    let mut denominator = Complex::new(0.0, 0.0);
+
+    // Calculate numerator: Σ b[k] * e^(-jωk)
+    for (k, &b_k) in filter.b.iter().enumerate() {
+        let exp_term = Complex::new(
+            (-(k as f64) * omega).cos(),
+            (-(k as f64) * omega).sin(),
+        );
+        numerator += b_k * exp_term;
+    }
+
+    // Calculate denominator: Σ a[k] * e^(-jωk)
+    for (k, &a_k) in filter.a.iter().enumerate() {
+        let exp_term = Complex::new(
+            (-(k as f64) * omega).cos(),
+            (-(k as f64) * omega).sin(),
+        );
+        denominator += a_k * exp_term;
+    }
+
+    // Return H(e^(jω)) = B(e^(jω)) / A(e^(jω))
+    numerator / denominator
+}
+
+/// Unwrap a phase sequence by removing 2π jumps between neighbouring points (numpy `unwrap`)
+///
+/// Jumps of exactly π (zeros on the unit circle) are ambiguous; `group_delay::digital_phase` resolves them analytically
+pub fn unwrap_phase(phase: &[f64]) -> Vec<f64> {
+    let mut unwrapped = Vec::with_capacity(phase.len());
+    let mut offset = 0.0;
+    for (k, &p) in phase.iter().enumerate() {
+        if k > 0 {
+            let jump = p - phase[k - 1];
+            offset -= 2.0 * PI * ((jump + PI) / (2.0 * PI)).floor();
+        }
+        unwrapped.push(p + offset);
+    }
+    unwrapped
+}
+
+/// Calculate magnitude in dB: 20 * log10(|H|)
+pub fn magnitude_to_db(magnitude: f64) -> f64 {
+    20.0 * magnitude.max(1e-10).log10()
+}
+
+/// Calculate phase in degrees
+pub fn phase_to_degrees(phase_rad: f64) -> f64 {
+    phase_rad * 180.0 / PI
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dft_grid_responses() {
+        let (sample_rate, num_points) = (22050.0, 31265);
+        let filter = IirDesign::lowpass(8, 4000.0, sample_rate);
+        let analog = filter.analog().unwrap();
+        let digital = FilterResponse::compute_dft_grid(&filter, num_points);
+        let continuous = FilterResponse::compute_analog_dft_grid(&analog, sample_rate, num_points);
+
+        assert_eq!(digital.frequencies.len(), num_points);
+        assert_eq!(digital.frequencies, continuous.frequencies);
+        assert!((digital.frequencies[num_points - 1] - sample_rate * (num_points - 1) as f64 / num_points as f64).abs() < 1e-9);
+
+        // Matches the one-sided grid; the digital response is conjugate symmetric, the analog one keeps falling above f_s/2
+        let one_sided = FilterResponse::compute(&filter, sample_rate, num_points);
+        for (k, h) in one_sided.complex_response.iter().enumerate() {
+            assert!((h - digital.complex_response[k]).norm() < 1e-12);
+        }
+        for k in 1..num_points {
+            assert!((digital.complex_response[k] - digital.complex_response[num_points - k].conj()).norm() < 1e-9);
+        }
+        assert!(continuous.magnitude[num_points - 1] < continuous.magnitude[num_points / 2]);
+
+        // Prewarping makes both agree at DC and at the cutoff; since tan is convex the digital response
+        // is higher below the cutoff and falls faster above it
+        let error = magnitude_error_db(&continuous, &digital);
+        let cutoff = digital.frequencies.iter().position(|&f| f >= 4000.0).unwrap();
+        assert!(error[0].abs() < 1e-9 && error[cutoff].abs() < 0.01);
+        assert!(error[..cutoff].iter().all(|&e| e > -1e-9));
+        assert!(error[cutoff + 1..num_points / 2].iter().all(|&e| e < 1e-9));
+        let quarter = digital.frequencies.iter().position(|&f| f >= sample_rate / 4.0).unwrap();
+        assert!(error[quarter] < -1.0);
+
+        // The largest error lies above the cutoff, where the analog response is still above the floor
+        let worst = max_warping_error(&continuous, &digital);
+        assert!(worst.bin > cutoff && worst.bin <= num_points / 2);
+        assert_eq!(worst.frequency, digital.frequencies[worst.bin]);
+        assert_eq!(worst.error_db, error[worst.bin]);
+        assert!(magnitude_to_db(continuous.magnitude[worst.bin]) > WARPING_FLOOR_DB);
+        assert!(error[..=num_points / 2]
+            .iter()
+            .zip(&continuous.magnitude)
+            .filter(|&(_, &m)| magnitude_to_db(m) > WARPING_FLOOR_DB)
+            .all(|(e, _)| e.abs() <= worst.error_db.abs()));
+    }
+}
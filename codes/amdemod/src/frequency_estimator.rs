@@ -0,0 +1,831 @@
+// 4. 频率偏差估计模块
+// 通过分析频谱找出频率偏差 f_d
+
+use crate::fft_processor::{compute_ifft_complex, FftResult};
+use rustfft::num_complex::Complex;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// 证据强度低于该值时认为符号无法判别
+const MIN_SIGN_EVIDENCE: f64 = 0.05;
+
+/// 每个候选对称轴至少需要的镜像频点对数
+const MIN_MIRROR_PAIRS: usize = 32;
+
+/// 对称轴估计的参数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SymmetricAxisConfig {
+    /// 对称轴的搜索范围 (Hz)
+    pub search_range: (f64, f64),
+    /// 镜像比较的偏移范围 (Hz)：比较 axis + Δ 与 axis - Δ 处的幅度，Δ 取该范围
+    pub offset_range: (f64, f64),
+    /// 返回的候选个数上限
+    pub max_candidates: usize,
+    /// 候选对称轴之间的最小间隔 (Hz)
+    pub min_separation: f64,
+    /// 接受对称轴所需的最低镜像能量占比
+    pub min_score: f64,
+}
+
+impl Default for SymmetricAxisConfig {
+    fn default() -> Self {
+        Self {
+            search_range: (100.0, 10000.0),
+            offset_range: (20.0, 4000.0),
+            max_candidates: 5,
+            min_separation: 50.0,
+            min_score: 0.5,
+        }
+    }
+}
+
+/// 候选对称轴
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AxisCandidate {
+    /// 对称轴频率 (Hz)，已做抛物线插值
+    pub frequency: f64,
+    /// 镜像对称的能量占比，取值 [0, 1]
+    pub score: f64,
+    /// 参与比较的频点对数
+    pub pairs: usize,
+}
+
+/// 对称轴估计结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymmetricAxisEstimate {
+    /// 选定的对称轴 (Hz)；没有候选达到 `min_score` 时为 `None`
+    pub axis: Option<f64>,
+    /// 选定结果的置信度，取值 [0, 1]
+    pub confidence: f64,
+    /// 按镜像能量占比降序排列的候选
+    pub candidates: Vec<AxisCandidate>,
+    /// 选择（或放弃）的理由
+    pub reason: String,
+}
+
+/// f_d = f_c - f̃_c 的符号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OffsetSign {
+    /// f_c > f̃_c
+    Positive,
+    /// f_c < f̃_c
+    Negative,
+    /// 数据不足以区分两种情况
+    Ambiguous,
+}
+
+/// 判别符号时使用的先验知识
+///
+/// 错误解调后的实信号 m(t)cos(2πf_d t + φ) 与 m(t)cos(-2πf_d t - φ) 完全相同，
+/// 符号只能借助打破这一对称性的先验来确定
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignPrior {
+    /// 没有先验：边带强弱不能说明哪一侧来自上边带，只报告统计量，结论为无法确定
+    None,
+    /// 已知收发两端载波的初相差 θ = θ_c - θ̃_c (rad)
+    CarrierPhase(f64),
+    /// 已知发送信号的上边带能量更强（如上边带单边带 / 残留边带）
+    UpperSidebandDominant,
+    /// 已知发送信号的下边带能量更强
+    LowerSidebandDominant,
+}
+
+/// 频率偏差符号判别结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignResolution {
+    /// 带符号的频率偏差 f_c - f̃_c (Hz)；无法判别时取正值
+    pub f_d: f64,
+    pub sign: OffsetSign,
+    /// 判别结果的置信度，取值 [0, 1]，0 表示两种符号同样可能
+    pub confidence: f64,
+    /// 外侧（高于 f_d）与内侧边带的能量不对称度 (E_外 - E_内) / (E_外 + E_内)
+    pub sideband_asymmetry: f64,
+    /// 按频点功率平方加权的边带不对称度，浊音谐波的谱峰占主导，宽带噪声的影响被压低
+    pub harmonic_asymmetry: f64,
+    /// 复基带信号的镜像相干度 |Σw²| / Σ|w|²，1 表示关于 f_d 共轭对称的双边带结构
+    pub mirror_coherence: f64,
+    /// 复基带信号的相位 φ (rad)，取值 (-π/2, π/2]，只在模 π 意义下确定
+    pub carrier_phase: f64,
+    pub prior: SignPrior,
+    /// 判别过程说明
+    pub report: String,
+}
+
+/// 频率偏差估计器
+pub struct FrequencyEstimator;
+
+impl FrequencyEstimator {
+    /// 估计频率偏差 f_d
+    /// 
+    /// # 参数
+    /// - frequencies: 频率轴
+    /// - magnitude: 幅度谱
+    /// - search_range: 搜索范围 (Hz)，例如 (10.0, 10000.0)
+    /// - exclude_dc: 是否排除直流分量
+    /// 
+    /// # 返回
+    /// (peak_frequency, peak_magnitude, peak_index)
+    pub fn estimate_frequency_offset(
+        frequencies: &[f64],
+        magnitude: &[f64],
+        search_range: (f64, f64),
+        exclude_dc: bool,
+    ) -> (f64, f64, usize) {
+        let (min_freq, max_freq) = search_range;
+        
+        // 在指定范围内搜索峰值
+        let mut peak_magnitude = 0.0;
+        let mut peak_index = 0;
+        let mut peak_frequency = 0.0;
+
+        let start_idx = if exclude_dc { 1 } else { 0 };
+
+        for (i, (&freq, &mag)) in frequencies
+            .iter()
+            .zip(magnitude.iter())
+            .enumerate()
+            .skip(start_idx)
+        {
+            if freq >= min_freq && freq <= max_freq && mag > peak_magnitude {
+                peak_magnitude = mag;
+                peak_index = i;
+                peak_frequency = freq;
+            }
+        }
+
+        println!("频率偏差估计结果:");
+        println!("  峰值频率 f_d = {:.2} Hz", peak_frequency);
+        println!("  峰值幅度 = {:.6}", peak_magnitude);
+        println!("  峰值索引 = {}", peak_index);
+
+        (peak_frequency, peak_magnitude, peak_index)
+    }
+
+    /// 精确估计频率（使用抛物线插值）
+    pub fn refined_frequency_estimate(
+        frequencies: &[f64],
+        magnitude: &[f64],
+        peak_index: usize,
+    ) -> f64 {
+        if peak_index == 0 || peak_index >= magnitude.len() - 1 {
+            return frequencies[peak_index];
+        }
+
+        // 使用三点抛物线插值
+        let y1 = magnitude[peak_index - 1];
+        let y2 = magnitude[peak_index];
+        let y3 = magnitude[peak_index + 1];
+
+        // 抛物线顶点位置
+        let delta = 0.5 * (y1 - y3) / (y1 - 2.0 * y2 + y3);
+        
+        let freq_resolution = if frequencies.len() > 1 {
+            frequencies[1] - frequencies[0]
+        } else {
+            1.0
+        };
+
+        let refined_freq = frequencies[peak_index] + delta * freq_resolution;
+
+        println!("精确频率估计:");
+        println!("  原始峰值频率: {:.2} Hz", frequencies[peak_index]);
+        println!("  精确频率: {:.4} Hz", refined_freq);
+
+        refined_freq
+    }
+
+    /// 寻找多个峰值
+    pub fn find_multiple_peaks(
+        frequencies: &[f64],
+        magnitude: &[f64],
+        num_peaks: usize,
+        min_distance: usize,
+        threshold: f64,
+    ) -> Vec<(f64, f64, usize)> {
+        let mut peaks = Vec::new();
+        let n = magnitude.len();
+
+        // 找出所有局部最大值
+        for i in 1..n-1 {
+            if magnitude[i] > magnitude[i-1] 
+                && magnitude[i] > magnitude[i+1] 
+                && magnitude[i] > threshold 
+            {
+                peaks.push((frequencies[i], magnitude[i], i));
+            }
+        }
+
+        // 按幅度降序排序
+        peaks.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        // 移除距离太近的峰值
+        let mut filtered_peaks = Vec::new();
+        for peak in peaks {
+            let is_far_enough = filtered_peaks
+                .iter()
+                .all(|(_, _, idx)| (*idx as isize - peak.2 as isize).abs() >= min_distance as isize);
+            
+            if is_far_enough {
+                filtered_peaks.push(peak);
+                if filtered_peaks.len() >= num_peaks {
+                    break;
+                }
+            }
+        }
+
+        println!("找到 {} 个峰值:", filtered_peaks.len());
+        for (i, (freq, mag, idx)) in filtered_peaks.iter().enumerate() {
+            println!("  峰值 {}: 频率 = {:.2} Hz, 幅度 = {:.6}, 索引 = {}", 
+                     i + 1, freq, mag, idx);
+        }
+
+        filtered_peaks
+    }
+
+    /// 估计幅度谱的对称轴（频率偏差 f_d）
+    ///
+    /// 错误解调后的幅度谱关于 f_d 镜像对称：|X(f_d + Δ)| = |X(f_d - Δ)|。
+    /// 对搜索范围内每个候选轴（半个频点的步长，保证镜像频点恰好对齐）计算
+    /// 幅度谱与其镜像的公共部分，并以整个正频率范围的能量归一化：
+    ///
+    ///   ρ(a) = 2·Σ min(|X(a+Δ)|, |X(a-Δ)|)² / Σ|X(f)|²，
+    ///
+    /// 即关于 a 镜像对称的能量占比。两侧幅度不等或只有少量能量参与时 ρ 都会变小，
+    /// 因此噪声底和幅度悬殊的峰值对不会被误选。取 ρ 的局部极大值作为候选，按 ρ 排序；
+    /// 置信度综合最佳候选的 ρ 与它相对次优候选的领先程度。
+    ///
+    /// # 参数
+    /// - frequencies: 频率轴
+    /// - magnitude: 幅度谱（双边，只使用前一半）
+    /// - config: 搜索参数
+    pub fn estimate_symmetric_axis(
+        frequencies: &[f64],
+        magnitude: &[f64],
+        config: &SymmetricAxisConfig,
+    ) -> SymmetricAxisEstimate {
+        let estimate = find_symmetric_axis(frequencies, magnitude, config);
+
+        println!("对称轴估计:");
+        for (i, c) in estimate.candidates.iter().enumerate() {
+            println!(
+                "  候选 {}: {:.2} Hz（镜像能量占比 {:.4}，{} 对频点）",
+                i + 1,
+                c.frequency,
+                c.score,
+                c.pairs
+            );
+        }
+        match estimate.axis {
+            Some(axis) => println!("  对称轴 = {:.4} Hz（置信度 {:.4}）", axis, estimate.confidence),
+            None => println!("  未找到对称轴"),
+        }
+        println!("  理由: {}", estimate.reason);
+
+        estimate
+    }
+
+    /// 计算信号的能量分布
+    pub fn compute_energy_distribution(
+        magnitude: &[f64],
+        frequencies: &[f64],
+        bands: &[(f64, f64)], // 频带范围
+    ) -> Vec<(String, f64)> {
+        let total_energy: f64 = magnitude.iter().map(|&m| m * m).sum();
+        
+        let mut band_energies = Vec::new();
+        
+        for (low, high) in bands {
+            let energy: f64 = frequencies
+                .iter()
+                .zip(magnitude.iter())
+                .filter(|(&f, _)| f >= *low && f <= *high)
+                .map(|(_, &m)| m * m)
+                .sum();
+            
+            let percentage = (energy / total_energy) * 100.0;
+            band_energies.push((
+                format!("{:.0}-{:.0} Hz", low, high),
+                percentage,
+            ));
+        }
+
+        println!("\n能量分布:");
+        for (band, percent) in &band_energies {
+            println!("  {}: {:.2}%", band, percent);
+        }
+
+        band_energies
+    }
+
+    /// 判断 f_c_tilde 与 f_c 的大小关系
+    ///
+    /// 不带先验地调用 [`Self::resolve_offset_sign`] 并打印判别报告；结论总是无法确定，报告只给出各项统计量
+    pub fn analyze_frequency_relationship(
+        fft: &FftResult,
+        f_d: f64,
+        bandwidth: f64,
+    ) -> SignResolution {
+        let resolution = Self::resolve_offset_sign(fft, f_d, bandwidth, SignPrior::None);
+        println!("\n频率关系分析:");
+        print!("{}", resolution.report);
+        resolution
+    }
+
+    /// 判别频率偏差 f_d = f_c - f̃_c 的符号
+    ///
+    /// 取 f_d ± bandwidth 内的正频率分量构造解析信号 z(t)（Hilbert 变换），
+    /// 再下变频得到复基带 w(t) = z(t)·e^{-j2πf_d t}：
+    /// - 双边带信号 w(t) = m(t)e^{jφ} 落在复平面的一条直线上，镜像相干度接近 1，
+    ///   直线方向给出 φ（模 π）；两种符号分别对应 φ = θ 与 φ = -θ
+    /// - 外侧边带在 f_d > 0 时来自原信号的上边带，在 f_d < 0 时来自下边带，
+    ///   因此边带能量不对称度配合边带先验可以确定符号
+    /// - 没有先验时结论总是无法确定（置信度 0）：哪一侧边带更强是发送端的性质，
+    ///   数据本身不能说明较强的一侧来自上边带还是下边带
+    ///
+    /// # 参数
+    /// - fft: 错误解调后信号的 FFT
+    /// - f_d: 频率偏差的幅值 (Hz)
+    /// - bandwidth: 基带带宽 f_B (Hz)
+    /// - prior: 判别所依据的先验
+    pub fn resolve_offset_sign(
+        fft: &FftResult,
+        f_d: f64,
+        bandwidth: f64,
+        prior: SignPrior,
+    ) -> SignResolution {
+        let n = fft.num_samples;
+        let sample_rate = fft.sample_rate;
+        let f_d = f_d.abs();
+
+        // 只保留 f_d ± B 内的正频率分量，且不越过 0 与 Nyquist 频率
+        let half_width = bandwidth.min(f_d).min(sample_rate / 2.0 - f_d);
+        let mut analytic = vec![Complex::new(0.0, 0.0); n];
+        let (mut outer_energy, mut inner_energy) = (0.0, 0.0);
+        let (mut outer_peaks, mut inner_peaks) = (0.0, 0.0);
+        let positive = 1..n.div_ceil(2);
+        for ((bin, &freq), &value) in analytic[positive.clone()]
+            .iter_mut()
+            .zip(&fft.frequencies[positive.clone()])
+            .zip(&fft.spectrum[positive])
+        {
+            let offset = freq - f_d;
+            if offset.abs() < half_width {
+                *bin = value * 2.0;
+                let power = value.norm_sqr();
+                if offset > 0.0 {
+                    outer_energy += power;
+                    outer_peaks += power * power;
+                } else if offset < 0.0 {
+                    inner_energy += power;
+                    inner_peaks += power * power;
+                }
+            }
+        }
+
+        // 复基带 w(t) 及其二阶统计量
+        let (mut sum_sq, mut power) = (Complex::new(0.0, 0.0), 0.0);
+        for (i, z) in compute_ifft_complex(&analytic).into_iter().enumerate() {
+            let w = z * Complex::from_polar(1.0, -2.0 * PI * f_d * i as f64 / sample_rate);
+            sum_sq += w * w;
+            power += w.norm_sqr();
+        }
+
+        let asymmetry = |outer: f64, inner: f64| {
+            if outer + inner > 0.0 {
+                (outer - inner) / (outer + inner)
+            } else {
+                0.0
+            }
+        };
+        let sideband_asymmetry = asymmetry(outer_energy, inner_energy);
+        let harmonic_asymmetry = asymmetry(outer_peaks, inner_peaks);
+        let mirror_coherence = if power > 0.0 { sum_sq.norm() / power } else { 0.0 };
+        let carrier_phase = 0.5 * sum_sq.arg();
+
+        // 正值支持 f_d > 0，负值支持 f_d < 0
+        let score = match prior {
+            SignPrior::None => 0.0,
+            SignPrior::CarrierPhase(theta) => {
+                let d_positive = phase_distance_mod_pi(carrier_phase, theta);
+                let d_negative = phase_distance_mod_pi(carrier_phase, -theta);
+                mirror_coherence * (d_negative - d_positive) / FRAC_PI_2
+            }
+            SignPrior::UpperSidebandDominant => sideband_asymmetry,
+            SignPrior::LowerSidebandDominant => -sideband_asymmetry,
+        };
+        let (sign, confidence) = if score.abs() < MIN_SIGN_EVIDENCE {
+            (OffsetSign::Ambiguous, 0.0)
+        } else if score > 0.0 {
+            (OffsetSign::Positive, score.min(1.0))
+        } else {
+            (OffsetSign::Negative, (-score).min(1.0))
+        };
+        let signed_f_d = if sign == OffsetSign::Negative { -f_d } else { f_d };
+
+        let mut report = String::new();
+        report.push_str(&format!("  频率偏差幅值 |f_d| = {:.4} Hz\n", f_d));
+        report.push_str(&format!(
+            "  边带能量不对称度 a = {:+.4}（外侧 {:.4e}，内侧 {:.4e}）\n",
+            sideband_asymmetry, outer_energy, inner_energy
+        ));
+        report.push_str(&format!("  谐波不对称度 h = {:+.4}（按功率平方加权）\n", harmonic_asymmetry));
+        report.push_str(&format!(
+            "  镜像相干度 ρ = {:.4}（1 表示关于 f_d 共轭对称的双边带结构）\n",
+            mirror_coherence
+        ));
+        report.push_str(&format!("  复基带相位 φ = {:+.4} rad（模 π）\n", carrier_phase));
+        let reason = match prior {
+            SignPrior::None => "无先验：m(t)cos(2πf_d t + φ) 与 m(t)cos(-2πf_d t - φ) 无法区分，\
+                 边带不对称只有在已知发送端哪个边带更强时才能确定符号，需给出载波初相差或边带先验"
+                .to_string(),
+            SignPrior::CarrierPhase(theta) => format!(
+                "载波初相差先验 θ = {:+.4} rad：φ 距 +θ {:.4} rad、距 -θ {:.4} rad（模 π）",
+                theta,
+                phase_distance_mod_pi(carrier_phase, theta),
+                phase_distance_mod_pi(carrier_phase, -theta)
+            ),
+            SignPrior::UpperSidebandDominant => {
+                "上边带占优先验：外侧边带更强说明外侧来自上边带，即 f_c > f̃_c".to_string()
+            }
+            SignPrior::LowerSidebandDominant => {
+                "下边带占优先验：外侧边带更强说明外侧来自下边带，即 f_c < f̃_c".to_string()
+            }
+        };
+        report.push_str(&format!("  依据: {}\n", reason));
+        report.push_str(&match sign {
+            OffsetSign::Positive => format!(
+                "  结论: f̃_c < f_c，f_d = {:+.4} Hz（置信度 {:.2}）\n",
+                signed_f_d, confidence
+            ),
+            OffsetSign::Negative => format!(
+                "  结论: f̃_c > f_c，f_d = {:+.4} Hz（置信度 {:.2}）\n",
+                signed_f_d, confidence
+            ),
+            OffsetSign::Ambiguous => {
+                "  结论: 无法确定 f̃_c 与 f_c 的大小关系（置信度 0）\n".to_string()
+            }
+        });
+
+        SignResolution {
+            f_d: signed_f_d,
+            sign,
+            confidence,
+            sideband_asymmetry,
+            harmonic_asymmetry,
+            mirror_coherence,
+            carrier_phase,
+            prior,
+            report,
+        }
+    }
+
+    /// 计算信噪比（SNR）估计
+    pub fn estimate_snr(
+        magnitude: &[f64],
+        signal_band: (usize, usize),
+        noise_band: (usize, usize),
+    ) -> f64 {
+        let signal_power: f64 = magnitude[signal_band.0..signal_band.1]
+            .iter()
+            .map(|&m| m * m)
+            .sum::<f64>() / (signal_band.1 - signal_band.0) as f64;
+
+        let noise_power: f64 = magnitude[noise_band.0..noise_band.1]
+            .iter()
+            .map(|&m| m * m)
+            .sum::<f64>() / (noise_band.1 - noise_band.0) as f64;
+
+        let snr_db = if noise_power > 0.0 {
+            10.0 * (signal_power / noise_power).log10()
+        } else {
+            f64::INFINITY
+        };
+
+        println!("\n信噪比估计:");
+        println!("  信号功率: {:.6}", signal_power);
+        println!("  噪声功率: {:.6}", noise_power);
+        println!("  SNR: {:.2} dB", snr_db);
+
+        snr_db
+    }
+}
+
+/// 估计幅度谱的对称轴，不打印过程（算法见 [`FrequencyEstimator::estimate_symmetric_axis`]）
+pub(crate) fn find_symmetric_axis(
+    frequencies: &[f64],
+    magnitude: &[f64],
+    config: &SymmetricAxisConfig,
+) -> SymmetricAxisEstimate {
+    let half = magnitude.len() / 2;
+    let resolution = if frequencies.len() > 1 {
+        frequencies[1] - frequencies[0]
+    } else {
+        1.0
+    };
+    let mags = &magnitude[..half];
+
+    // 正频率范围（不含直流）的总能量
+    let total_energy: f64 = mags.iter().skip(1).map(|&m| m * m).sum();
+
+    // 以半个频点为单位：候选轴 a = c·Δf/2，镜像频点对 (lo, hi) 满足 lo + hi = c
+    let to_half_bins = |f: f64| 2.0 * f / resolution;
+    let c_min = to_half_bins(config.search_range.0).ceil().max(0.0) as usize;
+    let c_max = (to_half_bins(config.search_range.1).floor() as usize).min(2 * half.saturating_sub(1));
+    let d_min = to_half_bins(config.offset_range.0).ceil().max(1.0) as usize;
+    let d_max = to_half_bins(config.offset_range.1).floor() as usize;
+
+    let mirror_score = |c: usize| -> Option<(f64, usize)> {
+        // lo = (c - d) / 2，其中 d ∈ [d_min, d_max]，且 1 ≤ lo < hi ≤ half - 1
+        let lo_start = c.saturating_sub(d_max).div_ceil(2).max(1).max((c + 1).saturating_sub(half));
+        let lo_end = c.checked_sub(d_min)? / 2;
+        if lo_end < lo_start || lo_end - lo_start + 1 < MIN_MIRROR_PAIRS {
+            return None;
+        }
+        let common: f64 = (lo_start..=lo_end).map(|lo| mags[lo].min(mags[c - lo]).powi(2)).sum();
+        (total_energy > 0.0).then(|| (2.0 * common / total_energy, lo_end - lo_start + 1))
+    };
+
+    let scores: Vec<Option<(f64, usize)>> = if c_min <= c_max {
+        (c_min..=c_max).map(mirror_score).collect()
+    } else {
+        Vec::new()
+    };
+    let score_at = |i: usize| scores[i].map(|(s, _)| s);
+
+    // 局部极大值作为候选
+    let mut maxima: Vec<(usize, f64, usize)> = Vec::new();
+    for (i, entry) in scores.iter().enumerate() {
+        let Some((score, pairs)) = *entry else { continue };
+        let left = i.checked_sub(1).and_then(score_at).unwrap_or(f64::NEG_INFINITY);
+        let right = scores.get(i + 1).copied().flatten().map_or(f64::NEG_INFINITY, |(s, _)| s);
+        if score >= left && score > right {
+            maxima.push((i, score, pairs));
+        }
+    }
+    maxima.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut candidates: Vec<AxisCandidate> = Vec::new();
+    for (i, score, pairs) in maxima {
+        // 在 ρ 的三点上做抛物线插值
+        let delta = match (i.checked_sub(1).and_then(score_at), scores.get(i + 1).copied().flatten()) {
+            (Some(y1), Some((y3, _))) => {
+                let denom = y1 - 2.0 * score + y3;
+                if denom.abs() > 1e-15 { (0.5 * (y1 - y3) / denom).clamp(-0.5, 0.5) } else { 0.0 }
+            }
+            _ => 0.0,
+        };
+        let frequency = ((c_min + i) as f64 + delta) * resolution / 2.0;
+        if candidates
+            .iter()
+            .all(|c| (c.frequency - frequency).abs() >= config.min_separation)
+        {
+            candidates.push(AxisCandidate { frequency, score, pairs });
+            if candidates.len() >= config.max_candidates {
+                break;
+            }
+        }
+    }
+
+    let (axis, confidence, reason) = match candidates.first() {
+        None => (
+            None,
+            0.0,
+            format!(
+                "{:.0}–{:.0} Hz 内没有可比较的镜像频点（每个候选至少需要 {} 对）",
+                config.search_range.0, config.search_range.1, MIN_MIRROR_PAIRS
+            ),
+        ),
+        Some(best) if best.score < config.min_score => (
+            None,
+            0.0,
+            format!(
+                "最佳候选 {:.2} Hz 的镜像能量占比 {:.4} 低于阈值 {:.2}，幅度谱没有明显的对称轴",
+                best.frequency, best.score, config.min_score
+            ),
+        ),
+        Some(best) => {
+            let runner_up = candidates.get(1).map_or(0.0, |c| c.score.max(0.0));
+            let margin = ((best.score - runner_up) / (1.0 - runner_up)).clamp(0.0, 1.0);
+            let confidence = (best.score * margin).clamp(0.0, 1.0);
+            let reason = match candidates.get(1) {
+                Some(second) => format!(
+                    "{:.2} Hz 两侧 {} 对频点的镜像能量占比为 {:.4}，高于次优候选 {:.2} Hz 的 {:.4}",
+                    best.frequency, best.pairs, best.score, second.frequency, second.score
+                ),
+                None => format!(
+                    "{:.2} Hz 两侧 {} 对频点的镜像能量占比为 {:.4}，没有其他候选",
+                    best.frequency, best.pairs, best.score
+                ),
+            };
+            (Some(best.frequency), confidence, reason)
+        }
+    };
+
+    SymmetricAxisEstimate {
+        axis,
+        confidence,
+        candidates,
+        reason,
+    }
+}
+
+/// 两个相位在模 π 意义下的距离，取值 [0, π/2]
+fn phase_distance_mod_pi(a: f64, b: f64) -> f64 {
+    let d = (a - b).rem_euclid(PI);
+    d.min(PI - d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frequency_estimation() {
+        // 生成测试信号：包含 100 Hz 的峰值
+        let n = 1000;
+        let sample_rate = 1000.0;
+        let frequencies: Vec<f64> = (0..n).map(|i| i as f64 * sample_rate / n as f64).collect();
+        
+        let magnitude: Vec<f64> = frequencies
+            .iter()
+            .map(|&f| {
+                if (f - 100.0).abs() < 1.0 {
+                    1.0
+                } else {
+                    0.01
+                }
+            })
+            .collect();
+
+        let (peak_freq, peak_mag, peak_idx) = FrequencyEstimator::estimate_frequency_offset(
+            &frequencies,
+            &magnitude,
+            (10.0, 500.0),
+            true,
+        );
+
+        assert!((peak_freq - 100.0).abs() < 2.0);
+        assert!(peak_mag > 0.5);
+        assert!(peak_idx > 0);
+    }
+
+    #[test]
+    fn test_multiple_peaks() {
+        let n = 1000;
+        let sample_rate = 1000.0;
+        let frequencies: Vec<f64> = (0..n).map(|i| i as f64 * sample_rate / n as f64).collect();
+        
+        // 生成包含两个峰值的信号
+        let magnitude: Vec<f64> = frequencies
+            .iter()
+            .map(|&f| {
+                let peak1 = (-((f - 100.0) / 10.0).powi(2)).exp();
+                let peak2 = 0.8 * (-((f - 300.0) / 10.0).powi(2)).exp();
+                peak1 + peak2
+            })
+            .collect();
+
+        let peaks = FrequencyEstimator::find_multiple_peaks(
+            &frequencies,
+            &magnitude,
+            2,
+            20,
+            0.1,
+        );
+
+        assert_eq!(peaks.len(), 2);
+    }
+
+    /// m(t)cos(2πf_c t + θ_c) 以 f̃_c 解调后的信号，基带为两个正弦分量
+    fn misdemodulated(f_d: f64, phase: f64, upper_gain: f64, lower_gain: f64) -> FftResult {
+        let sample_rate = 8000.0;
+        let n = 8000;
+        let tones = [(300.0, 1.0, 0.3), (700.0, 0.6, 1.1)];
+        let samples: Vec<f64> = (0..n)
+            .map(|i| {
+                let t = i as f64 / sample_rate;
+                tones
+                    .iter()
+                    .map(|&(f_m, amp, p)| {
+                        upper_gain * amp * (2.0 * PI * (f_d + f_m) * t + phase + p).cos()
+                            + lower_gain * amp * (2.0 * PI * (f_d - f_m) * t + phase - p).cos()
+                    })
+                    .sum::<f64>()
+            })
+            .collect();
+        FftResult::compute(&samples, sample_rate)
+    }
+
+    #[test]
+    fn test_sign_ambiguous_without_prior() {
+        let fft = misdemodulated(2000.0, 0.4, 1.0, 1.0);
+        let result = FrequencyEstimator::resolve_offset_sign(&fft, 2000.0, 1000.0, SignPrior::None);
+
+        assert_eq!(result.sign, OffsetSign::Ambiguous);
+        assert_eq!(result.confidence, 0.0);
+        assert!(result.mirror_coherence > 0.99);
+        assert!(result.sideband_asymmetry.abs() < 1e-6);
+        assert!((result.carrier_phase - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sign_from_harmonics_without_prior() {
+        // 一侧边带被削弱时谐波明显不对称，但不知道发送端哪个边带更强，仍无法确定符号
+        for (upper, lower) in [(1.0, 0.3), (0.3, 1.0)] {
+            let fft = misdemodulated(2000.0, 0.4, upper, lower);
+            let result = FrequencyEstimator::resolve_offset_sign(&fft, 2000.0, 1000.0, SignPrior::None);
+            assert_eq!(result.sign, OffsetSign::Ambiguous);
+            assert_eq!(result.confidence, 0.0);
+            assert!(result.harmonic_asymmetry.abs() > 0.5);
+            assert_eq!(result.f_d, 2000.0);
+        }
+    }
+
+    #[test]
+    fn test_sign_from_carrier_phase() {
+        // f_d = -2000 Hz 的信号等价于 f_d = 2000 Hz、相位取反
+        let positive = misdemodulated(2000.0, 0.5, 1.0, 1.0);
+        let negative = misdemodulated(2000.0, -0.5, 1.0, 1.0);
+        let prior = SignPrior::CarrierPhase(0.5);
+
+        let result = FrequencyEstimator::resolve_offset_sign(&positive, 2000.0, 1000.0, prior);
+        assert_eq!(result.sign, OffsetSign::Positive);
+        assert!(result.f_d > 0.0 && result.confidence > 0.5);
+
+        let result = FrequencyEstimator::resolve_offset_sign(&negative, 2000.0, 1000.0, prior);
+        assert_eq!(result.sign, OffsetSign::Negative);
+        assert!((result.f_d + 2000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sign_from_sideband_asymmetry() {
+        // 上边带单边带：f_d > 0 时落在外侧，f_d < 0 时落在内侧
+        let outer = misdemodulated(2000.0, 0.0, 1.0, 0.0);
+        let inner = misdemodulated(2000.0, 0.0, 0.0, 1.0);
+        let prior = SignPrior::UpperSidebandDominant;
+
+        let result = FrequencyEstimator::resolve_offset_sign(&outer, 2000.0, 1000.0, prior);
+        assert_eq!(result.sign, OffsetSign::Positive);
+        assert!(result.mirror_coherence < 0.1);
+        assert!(result.confidence > 0.99);
+
+        let result = FrequencyEstimator::resolve_offset_sign(&inner, 2000.0, 1000.0, prior);
+        assert_eq!(result.sign, OffsetSign::Negative);
+    }
+
+    #[test]
+    fn test_symmetric_axis() {
+        // 基带含 3 个正弦分量，被 f_d = 1234.5 Hz 的载波调制，另有一个不对称的强干扰
+        let sample_rate = 8000.0;
+        let n = 8000;
+        let samples: Vec<f64> = (0..n)
+            .map(|i| {
+                let t = i as f64 / sample_rate;
+                let m = (2.0 * PI * 150.0 * t).cos()
+                    + 0.7 * (2.0 * PI * 420.0 * t).sin()
+                    + 0.4 * (2.0 * PI * 810.0 * t).cos();
+                m * (2.0 * PI * 1234.5 * t).cos() + 0.3 * (2.0 * PI * 2900.0 * t).sin()
+            })
+            .collect();
+        let fft = FftResult::compute(&samples, sample_rate);
+        let config = SymmetricAxisConfig {
+            offset_range: (20.0, 1000.0),
+            ..SymmetricAxisConfig::default()
+        };
+
+        let estimate = FrequencyEstimator::estimate_symmetric_axis(&fft.frequencies, &fft.magnitude, &config);
+        let axis = estimate.axis.unwrap();
+        assert!((axis - 1234.5).abs() < 0.1, "axis = {}", axis);
+        assert!(estimate.confidence > 0.5);
+        assert!(estimate.candidates.len() > 1);
+        assert!(estimate.candidates.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_symmetric_axis_rejects_asymmetric_spectrum() {
+        // 单边带信号没有对称轴
+        let sample_rate = 8000.0;
+        let n = 8000;
+        let samples: Vec<f64> = (0..n)
+            .map(|i| {
+                let t = i as f64 / sample_rate;
+                [(150.0, 1.0), (420.0, 0.5), (810.0, 0.25)]
+                    .iter()
+                    .map(|&(f, a)| a * (2.0 * PI * (1234.0 + f) * t).cos())
+                    .sum::<f64>()
+            })
+            .collect();
+        let fft = FftResult::compute(&samples, sample_rate);
+        let config = SymmetricAxisConfig {
+            offset_range: (20.0, 1000.0),
+            ..SymmetricAxisConfig::default()
+        };
+
+        let estimate = FrequencyEstimator::estimate_symmetric_axis(&fft.frequencies, &fft.magnitude, &config);
+        assert!(estimate.axis.is_none());
+        assert_eq!(estimate.confidence, 0.0);
+        assert!(!estimate.reason.is_empty());
+    }
+}
@@ -9,25 +9,40 @@ pub fn frequency_shift(
     n: usize,
 ) -> Vec<Complex<f64>> {
     // Calculate shift amount in bins
-    let shift_bins = (f_d * n as f64 / f_s).round() as isize;
+    let shift_bins = shift_bins(f_d, f_s, n);
     
     // Create result vector
     let mut result = vec![Complex::new(0.0, 0.0); n];
     
     // For each output frequency bin, find contributions from shifted input
-    for i in 0..n {
+    for (i, value) in result.iter_mut().enumerate() {
         // Contribution from f - f_d (shift down)
         let idx_minus = (i as isize + shift_bins).rem_euclid(n as isize) as usize;
-        result[i] = result[i] + spectrum[idx_minus] * 0.5;
+        *value += spectrum[idx_minus] * 0.5;
         
         // Contribution from f + f_d (shift up)
         let idx_plus = (i as isize - shift_bins).rem_euclid(n as isize) as usize;
-        result[i] = result[i] + spectrum[idx_plus] * 0.5;
+        *value += spectrum[idx_plus] * 0.5;
     }
     
     result
 }
 
+/// Carrier frequency actually applied by [`frequency_shift`]: f_d rounded to the nearest bin k·f_s/n
+///
+/// Time-domain code that must reproduce the batch spectrum shift (e.g. the streaming
+/// frequency-domain path) multiplies by a carrier at this frequency instead of f_d
+pub fn bin_aligned_frequency(f_d: f64, f_s: f64, n: usize) -> f64 {
+    if n == 0 {
+        return f_d;
+    }
+    shift_bins(f_d, f_s, n) as f64 * f_s / n as f64
+}
+
+fn shift_bins(f_d: f64, f_s: f64, n: usize) -> isize {
+    (f_d * n as f64 / f_s).round() as isize
+}
+
 /// Alternative implementation using explicit frequency mapping
 pub fn frequency_shift_explicit(
     spectrum: &[Complex<f64>],
@@ -38,7 +53,7 @@ pub fn frequency_shift_explicit(
     let df = f_s / n as f64;
     let mut result = vec![Complex::new(0.0, 0.0); n];
     
-    for i in 0..n {
+    for (i, value) in result.iter_mut().enumerate() {
         // Current frequency
         let f_i = if i <= n / 2 {
             i as f64 * df
@@ -56,10 +71,10 @@ pub fn frequency_shift_explicit(
         
         // Accumulate contributions
         if idx_minus < n {
-            result[i] = result[i] + spectrum[idx_minus] * 0.5;
+            *value += spectrum[idx_minus] * 0.5;
         }
         if idx_plus < n {
-            result[i] = result[i] + spectrum[idx_plus] * 0.5;
+            *value += spectrum[idx_plus] * 0.5;
         }
     }
     
@@ -0,0 +1,262 @@
+// 相位与时延分析模块
+// 由零极点解析地求展开相位、群时延 τ_g = -dφ/dω 与相位时延 τ_p = -φ/ω，
+// 不对 arg H 做数值展开或差分；并统计通带内的时延起伏，用于估计滤波器对语音的相位失真
+
+use crate::analog_system::AnalogSystem;
+use crate::butterworth_filter::IirDesign;
+use crate::zpk::Zpk;
+use num_complex::Complex;
+use serde::Serialize;
+use std::f64::consts::PI;
+
+/// 求值点与单位圆（或虚轴）上的零点重合时，视为落在零点上
+const ROOT_TOL: f64 = 1e-12;
+
+/// 频率网格上的展开相位、群时延与相位时延
+#[derive(Debug, Clone)]
+pub struct DelayResponse {
+    /// 频率 (Hz)
+    pub frequencies: Vec<f64>,
+    /// 展开相位 (rad)，只在单位圆（虚轴）上的零点处有 π 的真实跳变
+    pub unwrapped_phase: Vec<f64>,
+    /// 群时延 (s)
+    pub group_delay: Vec<f64>,
+    /// 相位时延 (s)，f = 0 处无定义，记为 NaN
+    pub phase_delay: Vec<f64>,
+}
+
+/// 一个频段内的时延统计（单位 s）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PassbandDelay {
+    pub low: f64,
+    pub high: f64,
+    pub min_group_delay: f64,
+    pub max_group_delay: f64,
+    pub mean_group_delay: f64,
+    /// 群时延的峰峰值 max - min，为零时频段内各分量延迟相同、波形不失真
+    pub group_delay_variation: f64,
+    pub min_phase_delay: f64,
+    pub max_phase_delay: f64,
+}
+
+impl DelayResponse {
+    /// 数字滤波器在 f = k·f_s/N（k = 0..N/2，与 `FilterResponse::compute` 相同）上的相位与时延
+    pub fn digital(filter: &IirDesign, num_points: usize) -> Result<Self, String> {
+        let zpk = filter.zpk()?;
+        let sample_rate = filter.sample_rate;
+        let frequencies = one_sided_frequencies(sample_rate, num_points);
+        let omega = |f: f64| 2.0 * PI * f / sample_rate;
+        Ok(Self::from_functions(
+            frequencies,
+            |f| digital_phase(&zpk, omega(f)),
+            |f| digital_group_delay(&zpk, omega(f)) / sample_rate,
+        ))
+    }
+
+    /// 模拟滤波器在同一组频点上的相位与时延
+    pub fn analog(system: &AnalogSystem, sample_rate: f64, num_points: usize) -> Self {
+        let zpk = system.zpk();
+        Self::from_functions(
+            one_sided_frequencies(sample_rate, num_points),
+            |f| analog_phase(zpk, 2.0 * PI * f),
+            |f| analog_group_delay(zpk, 2.0 * PI * f),
+        )
+    }
+
+    fn from_functions(frequencies: Vec<f64>, phase: impl Fn(f64) -> f64, group_delay: impl Fn(f64) -> f64) -> Self {
+        let unwrapped_phase: Vec<f64> = frequencies.iter().map(|&f| phase(f)).collect();
+        let group_delay = frequencies.iter().map(|&f| group_delay(f)).collect();
+        let phase_delay = frequencies
+            .iter()
+            .zip(&unwrapped_phase)
+            .map(|(&f, &phi)| if f > 0.0 { -phi / (2.0 * PI * f) } else { f64::NAN })
+            .collect();
+        Self {
+            frequencies,
+            unwrapped_phase,
+            group_delay,
+            phase_delay,
+        }
+    }
+
+    /// 频段 [low, high] (Hz) 内的时延统计
+    pub fn passband(&self, low: f64, high: f64) -> Result<PassbandDelay, String> {
+        let in_band: Vec<usize> = (0..self.frequencies.len())
+            .filter(|&k| self.frequencies[k] >= low && self.frequencies[k] <= high)
+            .filter(|&k| self.group_delay[k].is_finite())
+            .collect();
+        if in_band.is_empty() {
+            return Err(format!("频段 [{}, {}] Hz 内没有频点", low, high));
+        }
+        let range = |values: &[f64]| {
+            in_band
+                .iter()
+                .map(|&k| values[k])
+                .filter(|v| v.is_finite())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(v), max.max(v)))
+        };
+        let (min_group_delay, max_group_delay) = range(&self.group_delay);
+        let (min_phase_delay, max_phase_delay) = range(&self.phase_delay);
+        let mean_group_delay = in_band.iter().map(|&k| self.group_delay[k]).sum::<f64>() / in_band.len() as f64;
+        Ok(PassbandDelay {
+            low,
+            high,
+            min_group_delay,
+            max_group_delay,
+            mean_group_delay,
+            group_delay_variation: max_group_delay - min_group_delay,
+            min_phase_delay,
+            max_phase_delay,
+        })
+    }
+}
+
+fn one_sided_frequencies(sample_rate: f64, num_points: usize) -> Vec<f64> {
+    (0..=num_points / 2)
+        .map(|k| k as f64 * sample_rate / num_points as f64)
+        .collect()
+}
+
+/// 数字滤波器在 z = e^{jω} 处的展开相位 (rad)
+///
+/// 每个因子 e^{jω} - r 写成 e^{jω}(1 - r e^{-jω})（|r| ≤ 1）或 -r(1 - e^{jω}/r)（|r| > 1），
+/// 括号内的实部非负，主值辐角随 ω 连续变化，逐项相加即得展开的相位
+pub fn digital_phase(zpk: &Zpk, omega: f64) -> f64 {
+    let z = Complex::from_polar(1.0, omega);
+    let factor = |r: &Complex<f64>| {
+        if r.norm() <= 1.0 {
+            omega + (1.0 - r / z).arg()
+        } else {
+            (-r).arg() + (1.0 - z / r).arg()
+        }
+    };
+    gain_phase(zpk.gain) + zpk.zeros.iter().map(factor).sum::<f64>() - zpk.poles.iter().map(factor).sum::<f64>()
+}
+
+/// 数字滤波器在 ω (rad/样本) 处的群时延（样本数）
+///
+/// 因子 z - r 对 -dφ/dω 的贡献为 -Re{e^{jω} / (e^{jω} - r)}；单位圆上的零点在自身以外处处贡献 -1/2，
+/// 在零点上取同一极限值
+pub fn digital_group_delay(zpk: &Zpk, omega: f64) -> f64 {
+    let z = Complex::from_polar(1.0, omega);
+    let factor = |r: &Complex<f64>| {
+        let d = z - r;
+        if d.norm() < ROOT_TOL {
+            0.5
+        } else {
+            (z / d).re
+        }
+    };
+    zpk.poles.iter().map(factor).sum::<f64>() - zpk.zeros.iter().map(factor).sum::<f64>()
+}
+
+/// 模拟滤波器在 s = jΩ 处的展开相位 (rad)
+///
+/// 左半平面的根取 jΩ - r 的主值辐角，右半平面的根取 π + arg(r - jΩ)，两者实部都非负，随 Ω 连续
+pub fn analog_phase(zpk: &Zpk, w: f64) -> f64 {
+    let s = Complex::new(0.0, w);
+    let factor = |r: &Complex<f64>| if r.re <= 0.0 { (s - r).arg() } else { PI + (r - s).arg() };
+    gain_phase(zpk.gain) + zpk.zeros.iter().map(factor).sum::<f64>() - zpk.poles.iter().map(factor).sum::<f64>()
+}
+
+/// 模拟滤波器在 Ω (rad/s) 处的群时延 (s)
+///
+/// 因子 s - r 对 -dφ/dΩ 的贡献为 -Re{1 / (jΩ - r)}；虚轴上的零点在自身以外贡献为零
+pub fn analog_group_delay(zpk: &Zpk, w: f64) -> f64 {
+    let s = Complex::new(0.0, w);
+    let factor = |r: &Complex<f64>| {
+        let d = s - r;
+        if d.norm() < ROOT_TOL {
+            0.0
+        } else {
+            (1.0 / d).re
+        }
+    };
+    zpk.poles.iter().map(factor).sum::<f64>() - zpk.zeros.iter().map(factor).sum::<f64>()
+}
+
+fn gain_phase(gain: f64) -> f64 {
+    if gain < 0.0 {
+        PI
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter_response::{unwrap_phase, FilterResponse};
+
+    #[test]
+    fn test_simple_delays() {
+        // (1 + z^{-1})/2：线性相位，群时延与相位时延都是半个样本
+        let average = Zpk::new(vec![Complex::new(-1.0, 0.0)], vec![Complex::new(0.0, 0.0)], 0.5);
+        for omega in [0.0, 0.3, 1.5, 3.0] {
+            assert!((digital_group_delay(&average, omega) - 0.5).abs() < 1e-12);
+            assert!((digital_phase(&average, omega) + 0.5 * omega).abs() < 1e-12);
+        }
+        // 一阶模拟低通 a/(s + a)：τ_g(Ω) = a/(a² + Ω²)，φ = -atan(Ω/a)
+        let a = 2.0 * PI * 100.0;
+        let first_order = Zpk::new(vec![], vec![Complex::new(-a, 0.0)], a);
+        for w in [0.0, a, 5.0 * a] {
+            assert!((analog_group_delay(&first_order, w) - a / (a * a + w * w)).abs() < 1e-15);
+            assert!((analog_phase(&first_order, w) + (w / a).atan()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_filter_delays_match_numerical_phase() {
+        let num_points = 31265;
+        for filter in [
+            IirDesign::highpass(8, 300.0, 22050.0),
+            IirDesign::lowpass(8, 4000.0, 22050.0),
+        ] {
+            let delay = DelayResponse::digital(&filter, num_points).unwrap();
+            let response = FilterResponse::compute(&filter, filter.sample_rate, num_points);
+            assert_eq!(delay.frequencies, response.frequencies);
+
+            // 解析相位与 arg H 只差 2π 的整数倍，并且与数值展开的相位只差一个常数；
+            // 高通在直流附近 |H| 极小，b/a 求值误差很大，这里用零极点求 H
+            let zpk = filter.zpk().unwrap();
+            let phase: Vec<f64> = delay
+                .frequencies
+                .iter()
+                .map(|&f| zpk.evaluate(Complex::from_polar(1.0, 2.0 * PI * f / filter.sample_rate)).arg())
+                .collect();
+            let numerical = unwrap_phase(&phase);
+            let offset = delay.unwrapped_phase[1] - numerical[1];
+            assert!(((offset / (2.0 * PI)).round() * 2.0 * PI - offset).abs() < 1e-6);
+            for (k, (analytic, numerical)) in delay.unwrapped_phase.iter().zip(&numerical).enumerate().skip(1) {
+                assert!((analytic - numerical - offset).abs() < 1e-6, "k = {}", k);
+            }
+
+            // 群时延与相位的中心差分一致
+            let step = 2.0 * PI * (delay.frequencies[1] - delay.frequencies[0]);
+            for k in (2..delay.frequencies.len() - 2).step_by(97) {
+                let derivative = -(delay.unwrapped_phase[k + 1] - delay.unwrapped_phase[k - 1]) / (2.0 * step);
+                let tolerance = 1e-3 * delay.group_delay[k].abs().max(1e-5);
+                assert!((derivative - delay.group_delay[k]).abs() < tolerance, "k = {}", k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_passband_delay() {
+        let filter = IirDesign::lowpass(8, 4000.0, 22050.0);
+        let digital = DelayResponse::digital(&filter, 31265).unwrap();
+        let analog = DelayResponse::analog(&filter.analog().unwrap(), 22050.0, 31265);
+
+        // 直流处数字滤波器等价于截止频率预畸变为 2f_s·tan(πf_c/f_s) 的模拟原型，时延按截止频率之比缩短
+        let ratio = 2.0 * PI * 4000.0 / (2.0 * 22050.0 * (PI * 4000.0 / 22050.0).tan());
+        assert!((digital.group_delay[0] - ratio * analog.group_delay[0]).abs() < 1e-9 * analog.group_delay[0]);
+        assert!(digital.phase_delay[0].is_nan());
+
+        // Butterworth 的群时延在截止频率附近出现峰值，通带内起伏明显
+        let summary = digital.passband(0.0, 4000.0).unwrap();
+        assert!(summary.min_group_delay > 0.0 && summary.max_group_delay > 2.0 * summary.min_group_delay);
+        assert!(summary.min_group_delay <= summary.mean_group_delay && summary.mean_group_delay <= summary.max_group_delay);
+        assert!((summary.group_delay_variation - (summary.max_group_delay - summary.min_group_delay)).abs() < 1e-15);
+        assert!(digital.passband(30000.0, 40000.0).is_err());
+    }
+}
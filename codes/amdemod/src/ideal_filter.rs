@@ -0,0 +1,158 @@
+use crate::window::Window;
+use num_complex::Complex;
+use std::f64::consts::PI;
+
+/// Apply ideal high-pass filter in frequency domain
+/// H_h(f) = 0 for |f| < f_c, 1 for |f| >= f_c
+pub fn apply_highpass(
+    spectrum: &[Complex<f64>],
+    f_c: f64,
+    f_s: f64,
+    n: usize,
+) -> Vec<Complex<f64>> {
+    let df = f_s / n as f64;
+    let mut result = spectrum.to_vec();
+    
+    for (i, value) in result.iter_mut().enumerate() {
+        // Calculate frequency for this bin
+        let freq = if i <= n / 2 {
+            i as f64 * df
+        } else {
+            (i as f64 - n as f64) * df
+        };
+        
+        // Apply ideal high-pass filter
+        if freq.abs() < f_c {
+            *value = Complex::new(0.0, 0.0);
+        }
+    }
+    
+    result
+}
+
+/// Apply ideal low-pass filter in frequency domain
+/// H_l(f) = 1 for |f| <= f_c, 0 for |f| > f_c
+pub fn apply_lowpass(
+    spectrum: &[Complex<f64>],
+    f_c: f64,
+    f_s: f64,
+    n: usize,
+) -> Vec<Complex<f64>> {
+    let df = f_s / n as f64;
+    let mut result = spectrum.to_vec();
+    
+    for (i, value) in result.iter_mut().enumerate() {
+        // Calculate frequency for this bin
+        let freq = if i <= n / 2 {
+            i as f64 * df
+        } else {
+            (i as f64 - n as f64) * df
+        };
+        
+        // Apply ideal low-pass filter
+        if freq.abs() > f_c {
+            *value = Complex::new(0.0, 0.0);
+        }
+    }
+    
+    result
+}
+
+/// 理想低通的有限长近似：加窗 sinc，线性相位，群时延 (taps - 1)/2 个样本
+///
+/// h[n] = w[n]·(2f_c/f_s)·sinc(2f_c/f_s·(n - D))，用于分块（重叠保留）的频域解调；
+/// 过渡带宽度随 taps 增大而减小，Kaiser 窗 β 越大阻带越低、过渡带越宽
+pub fn lowpass_fir(f_c: f64, f_s: f64, taps: usize, window: Window) -> Result<Vec<f64>, String> {
+    if taps.is_multiple_of(2) {
+        return Err(format!("FIR 长度 {} 必须为奇数", taps));
+    }
+    if !(f_c > 0.0 && f_c < f_s / 2.0) {
+        return Err(format!("截止频率 {} Hz 不在 (0, f_s/2) 内", f_c));
+    }
+    window.validate(taps)?;
+    let w = window.coefficients(taps);
+    let delay = (taps / 2) as f64;
+    let fc = f_c / f_s;
+    Ok(w.iter()
+        .enumerate()
+        .map(|(n, &w)| {
+            let m = n as f64 - delay;
+            let sinc = if m == 0.0 { 2.0 * fc } else { (2.0 * PI * fc * m).sin() / (PI * m) };
+            w * sinc
+        })
+        .collect())
+}
+
+/// 理想高通的有限长近似：δ[n - D] 减去同截止频率的 [`lowpass_fir`]
+pub fn highpass_fir(f_c: f64, f_s: f64, taps: usize, window: Window) -> Result<Vec<f64>, String> {
+    let mut h = lowpass_fir(f_c, f_s, taps, window)?;
+    h.iter_mut().for_each(|v| *v = -*v);
+    h[taps / 2] += 1.0;
+    Ok(h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highpass_filter() {
+        let n = 100;
+        let f_s = 1000.0;
+        let f_c = 100.0;
+        
+        // Create test spectrum
+        let spectrum: Vec<Complex<f64>> = (0..n)
+            .map(|_| Complex::new(1.0, 0.0))
+            .collect();
+        
+        let filtered = apply_highpass(&spectrum, f_c, f_s, n);
+        
+        // Check that low frequencies are zeroed
+        assert_eq!(filtered[0].norm(), 0.0); // DC component
+        assert_eq!(filtered[5].norm(), 0.0); // 50 Hz
+    }
+
+    #[test]
+    fn test_lowpass_filter() {
+        let n = 100;
+        let f_s = 1000.0;
+        let f_c = 100.0;
+        
+        let spectrum: Vec<Complex<f64>> = (0..n)
+            .map(|_| Complex::new(1.0, 0.0))
+            .collect();
+        
+        let filtered = apply_lowpass(&spectrum, f_c, f_s, n);
+        
+        // Check that high frequencies are zeroed
+        let high_freq_idx = (200.0 / f_s * n as f64) as usize;
+        assert_eq!(filtered[high_freq_idx].norm(), 0.0);
+    }
+
+    #[test]
+    fn test_fir_approximations() {
+        let (f_s, taps) = (8000.0, 1001);
+        let window = Window::Kaiser(8.6);
+        let lp = lowpass_fir(1000.0, f_s, taps, window).unwrap();
+        let hp = highpass_fir(1000.0, f_s, taps, window).unwrap();
+        let response = |h: &[f64], f: f64| {
+            h.iter()
+                .enumerate()
+                .map(|(n, &v)| Complex::from_polar(v, -2.0 * PI * f * n as f64 / f_s))
+                .sum::<Complex<f64>>()
+                .norm()
+        };
+        // 离截止频率 100 Hz 以外：通带增益 1，阻带低于 -80 dB
+        assert!((response(&lp, 0.0) - 1.0).abs() < 1e-3);
+        assert!((response(&lp, 500.0) - 1.0).abs() < 1e-3);
+        assert!(response(&lp, 1500.0) < 1e-4);
+        assert!(response(&hp, 500.0) < 1e-4);
+        assert!((response(&hp, 3000.0) - 1.0).abs() < 1e-3);
+        // 线性相位：系数关于中点对称
+        assert!(lp.iter().zip(lp.iter().rev()).all(|(a, b)| (a - b).abs() < 1e-15));
+
+        assert!(lowpass_fir(1000.0, f_s, 1000, window).is_err());
+        assert!(lowpass_fir(5000.0, f_s, 101, window).is_err());
+    }
+}
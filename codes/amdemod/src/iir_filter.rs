@@ -0,0 +1,315 @@
+// IIR 滤波模块
+// 直接 I 型、转置直接 II 型和二阶节级联三种结构，滤波器对象保存内部状态，可跨数据块复用。
+// 高阶滤波器写成单个多项式时系数对舍入极其敏感（极点靠近 z = 1 时尤甚），级联二阶节则每节只有一对极点
+
+use crate::state_space::StateSpace;
+use serde::{Deserialize, Serialize};
+
+/// 二阶节系数 [b0, b1, b2, a0, a1, a2]，与 MATLAB / SciPy 的 sos 矩阵的一行相同
+pub type SosSection = [f64; 6];
+
+/// 由 b/a 系数构造滤波器时采用的结构
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterStructure {
+    /// 直接 I 型：分别保存输入、输出历史
+    DirectForm1,
+    /// 转置直接 II 型：只保存 max(len(b), len(a)) - 1 个状态
+    DirectForm2Transposed,
+}
+
+/// 带内部状态的 IIR 滤波器
+///
+/// 系数在构造时按 a0 归一化；`process_sample` / `process_block` 之间状态持续保留，
+/// 分块调用的结果与一次处理整段信号相同，`reset` 回到零初始状态
+#[derive(Debug, Clone)]
+pub struct IirFilter {
+    kind: FilterKind,
+}
+
+#[derive(Debug, Clone)]
+enum FilterKind {
+    DirectForm1 {
+        b: Vec<f64>,
+        a: Vec<f64>,
+        /// 输入历史环形缓冲，x_history[x_head] 为最新的输入
+        x_history: Vec<f64>,
+        x_head: usize,
+        /// 输出历史 y[n-1], y[n-2], … 的环形缓冲，y_history[y_head] 为 y[n-1]
+        y_history: Vec<f64>,
+        y_head: usize,
+    },
+    DirectForm2Transposed {
+        b: Vec<f64>,
+        a: Vec<f64>,
+        state: Vec<f64>,
+    },
+    Sos {
+        sections: Vec<SosSection>,
+        /// 每节的转置直接 II 型状态
+        state: Vec<[f64; 2]>,
+    },
+}
+
+/// 检查并按 a0 归一化 b/a 系数
+fn normalize(b: &[f64], a: &[f64]) -> Result<(Vec<f64>, Vec<f64>), String> {
+    if b.is_empty() || a.is_empty() {
+        return Err("滤波器系数 b、a 不能为空".to_string());
+    }
+    if a[0] == 0.0 || !a[0].is_finite() {
+        return Err(format!("a[0] = {} 不能为零", a[0]));
+    }
+    if b.iter().chain(a).any(|v| !v.is_finite()) {
+        return Err("滤波器系数含有 NaN 或无穷大".to_string());
+    }
+    let a0 = a[0];
+    Ok((b.iter().map(|v| v / a0).collect(), a.iter().map(|v| v / a0).collect()))
+}
+
+impl IirFilter {
+    /// 由传递函数 H(z) = Σb_k z^{-k} / Σa_k z^{-k} 按指定结构构造
+    pub fn new(b: &[f64], a: &[f64], structure: FilterStructure) -> Result<Self, String> {
+        match structure {
+            FilterStructure::DirectForm1 => Self::direct_form_1(b, a),
+            FilterStructure::DirectForm2Transposed => Self::direct_form_2_transposed(b, a),
+        }
+    }
+
+    /// 直接 I 型：y[n] = Σ b_k x[n-k] - Σ_{k≥1} a_k y[n-k]
+    pub fn direct_form_1(b: &[f64], a: &[f64]) -> Result<Self, String> {
+        let (b, a) = normalize(b, a)?;
+        Ok(Self {
+            kind: FilterKind::DirectForm1 {
+                x_history: vec![0.0; b.len()],
+                x_head: 0,
+                y_history: vec![0.0; a.len() - 1],
+                y_head: 0,
+                b,
+                a,
+            },
+        })
+    }
+
+    /// 转置直接 II 型
+    pub fn direct_form_2_transposed(b: &[f64], a: &[f64]) -> Result<Self, String> {
+        let (mut b, mut a) = normalize(b, a)?;
+        let len = b.len().max(a.len());
+        b.resize(len, 0.0);
+        a.resize(len, 0.0);
+        Ok(Self {
+            kind: FilterKind::DirectForm2Transposed {
+                b,
+                a,
+                state: vec![0.0; len - 1],
+            },
+        })
+    }
+
+    /// 二阶节级联，每节按转置直接 II 型计算
+    pub fn from_sos(sections: &[SosSection]) -> Result<Self, String> {
+        if sections.is_empty() {
+            return Err("二阶节列表不能为空".to_string());
+        }
+        let sections = sections
+            .iter()
+            .enumerate()
+            .map(|(i, section)| {
+                let (b, a) = normalize(&section[..3], &section[3..]).map_err(|e| format!("第 {} 节：{}", i + 1, e))?;
+                Ok([b[0], b[1], b[2], 1.0, a[1], a[2]])
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self {
+            kind: FilterKind::Sos {
+                state: vec![[0.0; 2]; sections.len()],
+                sections,
+            },
+        })
+    }
+
+    /// 滤波器阶数（分母多项式的次数，二阶节级联时为各节之和）
+    pub fn order(&self) -> usize {
+        match &self.kind {
+            FilterKind::DirectForm1 { b, a, .. } | FilterKind::DirectForm2Transposed { b, a, .. } => {
+                let degree = |p: &[f64]| p.iter().rposition(|&v| v != 0.0).unwrap_or(0);
+                degree(b).max(degree(a))
+            }
+            FilterKind::Sos { sections, .. } => sections
+                .iter()
+                .map(|s| if s[5] != 0.0 || s[2] != 0.0 { 2 } else if s[4] != 0.0 || s[1] != 0.0 { 1 } else { 0 })
+                .sum(),
+        }
+    }
+
+    /// 处理一个样本
+    pub fn process_sample(&mut self, x: f64) -> f64 {
+        match &mut self.kind {
+            FilterKind::DirectForm1 { b, a, x_history, x_head, y_history, y_head } => {
+                *x_head = (*x_head + x_history.len() - 1) % x_history.len();
+                x_history[*x_head] = x;
+
+                // Feedforward part: sum(b[k] * x[n-k])
+                let mut y = 0.0;
+                let (newer, older) = (&x_history[*x_head..], &x_history[..*x_head]);
+                for (b, x) in b.iter().zip(newer.iter().chain(older)) {
+                    y += b * x;
+                }
+
+                // Feedback part: -sum(a[k] * y[n-k]) for k > 0
+                if !y_history.is_empty() {
+                    let (newer, older) = (&y_history[*y_head..], &y_history[..*y_head]);
+                    for (a, y_k) in a[1..].iter().zip(newer.iter().chain(older)) {
+                        y -= a * y_k;
+                    }
+                    *y_head = (*y_head + y_history.len() - 1) % y_history.len();
+                    y_history[*y_head] = y;
+                }
+                y
+            }
+            FilterKind::DirectForm2Transposed { b, a, state } => {
+                let y = b[0] * x + state.first().copied().unwrap_or(0.0);
+                let last = state.len();
+                for i in 0..last {
+                    let next = if i + 1 < last { state[i + 1] } else { 0.0 };
+                    state[i] = b[i + 1] * x - a[i + 1] * y + next;
+                }
+                y
+            }
+            FilterKind::Sos { sections, state } => sections.iter().zip(state.iter_mut()).fold(x, |x, (s, z)| {
+                let y = s[0] * x + z[0];
+                z[0] = s[1] * x - s[4] * y + z[1];
+                z[1] = s[2] * x - s[5] * y;
+                y
+            }),
+        }
+    }
+
+    /// 处理一个数据块，状态延续到下一块
+    pub fn process_block(&mut self, input: &[f64]) -> Vec<f64> {
+        input.iter().map(|&x| self.process_sample(x)).collect()
+    }
+
+    /// 系数对应的离散状态空间模型（不含当前的内部状态）：b/a 结构给出能控标准型，二阶节逐节串联
+    pub fn state_space(&self, sample_rate: f64) -> Result<StateSpace, String> {
+        match &self.kind {
+            FilterKind::DirectForm1 { b, a, .. } | FilterKind::DirectForm2Transposed { b, a, .. } => {
+                StateSpace::controllable(b, a, Some(sample_rate))
+            }
+            FilterKind::Sos { sections, .. } => StateSpace::from_sections(sections, Some(sample_rate)),
+        }
+    }
+
+    /// 清零内部状态
+    pub fn reset(&mut self) {
+        match &mut self.kind {
+            FilterKind::DirectForm1 { x_history, x_head, y_history, y_head, .. } => {
+                x_history.iter_mut().for_each(|v| *v = 0.0);
+                y_history.iter_mut().for_each(|v| *v = 0.0);
+                *x_head = 0;
+                *y_head = 0;
+            }
+            FilterKind::DirectForm2Transposed { state, .. } => state.iter_mut().for_each(|v| *v = 0.0),
+            FilterKind::Sos { state, .. } => state.iter_mut().for_each(|z| *z = [0.0; 2]),
+        }
+    }
+}
+
+/// 对整段信号做直接 I 型 IIR 滤波（零初始状态）
+/// y[n] = sum(b[i] * x[n-i]) - sum(a[j] * y[n-j]) for j > 0
+///
+/// 系数非法（为空或 a[0] = 0）时 panic；需要跨块保留状态或使用二阶节时用 [`IirFilter`]
+pub fn apply_filter(input: &[f64], b: &[f64], a: &[f64]) -> Vec<f64> {
+    IirFilter::direct_form_1(b, a)
+        .unwrap_or_else(|e| panic!("invalid filter coefficients: {}", e))
+        .process_block(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_filter() {
+        // Simple moving average filter: y[n] = 0.5*x[n] + 0.5*x[n-1]
+        let b = vec![0.5, 0.5];
+        let a = vec![1.0];
+        let input = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        
+        let output = apply_filter(&input, &b, &a);
+        
+        // Expected: [0.5, 1.5, 2.5, 3.5, 4.5]
+        assert!((output[0] - 0.5).abs() < 1e-10);
+        assert!((output[1] - 1.5).abs() < 1e-10);
+        assert!((output[2] - 2.5).abs() < 1e-10);
+    }
+
+    /// 二阶节 (b, a) 依次卷积得到的整体传递函数
+    fn cascade_tf(sections: &[SosSection]) -> (Vec<f64>, Vec<f64>) {
+        let convolve = |p: &[f64], q: &[f64]| {
+            let mut r = vec![0.0; p.len() + q.len() - 1];
+            for (i, x) in p.iter().enumerate() {
+                for (j, y) in q.iter().enumerate() {
+                    r[i + j] += x * y;
+                }
+            }
+            r
+        };
+        sections.iter().fold((vec![1.0], vec![1.0]), |(b, a), s| (convolve(&b, &s[..3]), convolve(&a, &s[3..])))
+    }
+
+    #[test]
+    fn test_structures_agree_and_blocks_match_whole_signal() {
+        let sections = [[0.2, 0.4, 0.2, 1.0, -0.6, 0.25], [1.0, -1.0, 0.0, 2.0, -0.8, 0.0]];
+        let (b, a) = cascade_tf(&sections);
+        let input: Vec<f64> = (0..500).map(|i| (i as f64 * 0.3).sin() + (i % 7) as f64 * 0.1).collect();
+
+        let reference = IirFilter::direct_form_1(&b, &a).unwrap().process_block(&input);
+        assert_eq!(reference, apply_filter(&input, &b, &a));
+        for mut filter in [
+            IirFilter::new(&b, &a, FilterStructure::DirectForm2Transposed).unwrap(),
+            IirFilter::from_sos(&sections).unwrap(),
+        ] {
+            assert_eq!(filter.order(), 3);
+            let output = filter.process_block(&input);
+            assert!(output.iter().zip(&reference).all(|(x, y)| (x - y).abs() < 1e-12));
+
+            // 分成长度不一的块处理，结果与整段处理逐位相同；reset 后从零状态重来
+            filter.reset();
+            let mut blocks = Vec::new();
+            let mut start = 0;
+            for len in [1, 17, 64, 3, 200].iter().cycle() {
+                if start >= input.len() {
+                    break;
+                }
+                let end = (start + len).min(input.len());
+                blocks.extend(filter.process_block(&input[start..end]));
+                start = end;
+            }
+            assert_eq!(blocks, output);
+            filter.reset();
+            assert_eq!(filter.process_sample(input[0]), output[0]);
+        }
+
+        assert!(IirFilter::direct_form_1(&[1.0], &[0.0, 1.0]).is_err());
+        assert!(IirFilter::from_sos(&[[1.0, 0.0, 0.0, 0.0, 0.0, 0.0]]).is_err());
+        assert!(IirFilter::from_sos(&[]).is_err());
+    }
+
+    #[test]
+    fn test_sos_stays_accurate_at_high_order() {
+        // 六个相同的窄带低通二阶节，极点 0.995·e^{±j0.01} 各重复 6 次。
+        // 展开成 12 阶多项式后系数舍入使极点大幅移动，直接型的阶跃响应偏离真值；二阶节级联仍收敛到直流增益 1
+        let (r, theta) = (0.995f64, 0.01f64);
+        let (a1, a2) = (-2.0 * r * theta.cos(), r * r);
+        let gain = (1.0 + a1 + a2) / 4.0;
+        let sections = vec![[gain, 2.0 * gain, gain, 1.0, a1, a2]; 6];
+        let (b, a) = cascade_tf(&sections);
+        let step = vec![1.0; 20000];
+
+        let sos = IirFilter::from_sos(&sections).unwrap().process_block(&step);
+        assert!((sos.last().unwrap() - 1.0).abs() < 1e-9);
+
+        let direct = IirFilter::direct_form_2_transposed(&b, &a).unwrap().process_block(&step);
+        let deviation = direct.iter().zip(&sos).map(|(x, y)| (x - y).abs()).fold(0.0, f64::max);
+        assert!(deviation > 1e-3, "direct form deviation {}", deviation);
+    }
+}
@@ -0,0 +1,73 @@
+//! 调幅信号解调公共库
+//!
+//! Q1–Q4 共用的信号处理模块，按功能划分为：
+//! - 音频读写：`audio_reader`、`audio_writer`
+//! - 频谱分析：`fft_processor`、`frequency_estimator`、`sub_bin`（亚频点估计）；f_d(t) 跟踪：`offset_tracker`
+//! - 时频分析：`stft`、`window`；功率谱密度估计：`psd`
+//! - 滤波器设计：`butterworth_filter`、`analog_prototype`（Chebyshev、椭圆、Bessel 原型）、`filter_order`（最小阶数估计）、`filter_response`、`group_delay`（展开相位、群时延与相位时延）、`stability`（极点裕量、系数灵敏度与条件数）；零极点增益与二阶节互换：`zpk`
+//! - 连续时间模型：`analog_system`（H(s) 与 lsim 仿真）、`state_space`（状态空间、标准型与平衡实现、离散化）；小型稠密矩阵：`matrix`
+//! - 滤波：`iir_filter`、`ideal_filter`
+//! - 解调：`demodulator`、`frequency_shifter`；闭环载波恢复：`carrier_recovery`；分块（流式）解调：`streaming`
+//! - 结果对比：`comparator`
+//! - 阶段间参数传递：`params`
+//! - 流水线各阶段：`stages`；带缓存的端到端流水线：`pipeline`
+//! - 绘图：`spectrum_visualizer`、`response_visualizer`
+
+pub mod analog_prototype;
+pub mod analog_system;
+pub mod audio_reader;
+pub mod audio_writer;
+pub mod butterworth_filter;
+pub mod carrier_recovery;
+pub mod comparator;
+pub mod demodulator;
+pub mod fft_processor;
+pub mod filter_order;
+pub mod filter_response;
+pub mod frequency_estimator;
+pub mod frequency_shifter;
+pub mod group_delay;
+pub mod ideal_filter;
+pub mod iir_filter;
+pub mod matrix;
+pub mod offset_tracker;
+pub mod params;
+pub mod pipeline;
+pub mod psd;
+pub mod response_visualizer;
+pub mod spectrum_visualizer;
+pub mod stability;
+pub mod stages;
+pub mod state_space;
+pub mod stft;
+pub mod streaming;
+pub mod sub_bin;
+pub mod window;
+pub mod zpk;
+
+pub use analog_prototype::FilterFamily;
+pub use analog_system::AnalogSystem;
+pub use audio_reader::{read_wav, AudioData, WavBlockReader};
+pub use audio_writer::{write_wav, WavBlockWriter};
+pub use butterworth_filter::{FilterType, IirDesign};
+#[allow(deprecated)]
+pub use butterworth_filter::ButterworthFilter;
+pub use carrier_recovery::{CarrierRecovery, LoopConfig, LoopKind};
+pub use fft_processor::FftResult;
+pub use filter_order::{FilterSpec, OrderEstimate, TransitionSpec};
+pub use filter_response::FilterResponse;
+pub use frequency_estimator::{FrequencyEstimator, OffsetSign, SignPrior, SignResolution};
+pub use group_delay::{DelayResponse, PassbandDelay};
+pub use iir_filter::{FilterStructure, IirFilter, SosSection};
+pub use offset_tracker::{OffsetTrack, TrackerConfig};
+pub use params::{EstimatorMethod, FilterDesigns, FrequencyOffset, PipelineParams};
+pub use pipeline::{Pipeline, PipelineConfig, Stage};
+pub use psd::{PowerSpectralDensity, PsdMethod};
+pub use spectrum_visualizer::SpectrumVisualizer;
+pub use stability::{AnalogStability, StabilityReport};
+pub use state_space::{BalancedRealization, Discretization, StateSpace};
+pub use stft::{Spectrogram, StftConfig};
+pub use streaming::{OverlapSaveConfig, StreamingDemodulator, StreamingFrequencyDemod, StreamingTimeDemod};
+pub use sub_bin::SubBinMethod;
+pub use window::Window;
+pub use zpk::Zpk;
@@ -0,0 +1,542 @@
+// 小型稠密矩阵模块
+// 状态空间模型所需的基本运算：乘法、转置、分块拼接、线性方程组求解、矩阵指数、
+// 特征多项式（Hessenberg 约化）、对称矩阵特征分解与奇异值分解（Jacobi 方法）。
+// 状态数不超过几十，直接按行优先存储，不引入线性代数库
+
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+/// Jacobi 迭代的最大扫描次数
+const MAX_JACOBI_SWEEPS: usize = 100;
+
+/// 行优先存储的实矩阵，序列化为按行嵌套的数组（与 numpy、MATLAB 的矩阵字面量一致）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "Vec<Vec<f64>>", try_from = "Vec<Vec<f64>>")]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = 1.0;
+        }
+        m
+    }
+
+    /// 由各行构造，各行长度必须相同
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Self, String> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|r| r.len() != cols) {
+            return Err("矩阵各行长度不一致".to_string());
+        }
+        Ok(Self {
+            rows: rows.len(),
+            cols,
+            data: rows.concat(),
+        })
+    }
+
+    /// 列向量
+    pub fn column(values: &[f64]) -> Self {
+        Self { rows: values.len(), cols: 1, data: values.to_vec() }
+    }
+
+    /// 行向量
+    pub fn row(values: &[f64]) -> Self {
+        Self { rows: 1, cols: values.len(), data: values.to_vec() }
+    }
+
+    /// 1×1 矩阵
+    pub fn scalar(value: f64) -> Self {
+        Self { rows: 1, cols: 1, data: vec![value] }
+    }
+
+    /// 分块拼接，同一行的块行数相同，同一列的块列数相同
+    pub fn from_blocks(blocks: &[&[&Matrix]]) -> Result<Self, String> {
+        let rows: usize = blocks.iter().map(|r| r.first().map_or(0, |b| b.rows)).sum();
+        let cols: usize = blocks.first().map_or(0, |r| r.iter().map(|b| b.cols).sum());
+        let mut result = Self::zeros(rows, cols);
+        let mut top = 0;
+        for block_row in blocks {
+            let height = block_row.first().map_or(0, |b| b.rows);
+            let mut left = 0;
+            for block in block_row.iter() {
+                if block.rows != height {
+                    return Err("分块矩阵同一行的块行数不一致".to_string());
+                }
+                result.set_block(top, left, block);
+                left += block.cols;
+            }
+            if left != cols {
+                return Err("分块矩阵各行的总列数不一致".to_string());
+            }
+            top += height;
+        }
+        Ok(result)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// 按行导出，便于打印或写入文件
+    pub fn to_rows(&self) -> Vec<Vec<f64>> {
+        if self.cols == 0 {
+            return vec![Vec::new(); self.rows];
+        }
+        self.data.chunks(self.cols).map(<[f64]>::to_vec).collect()
+    }
+
+    /// 左上角位于 (row, col)、大小为 rows×cols 的子块
+    pub fn block(&self, row: usize, col: usize, rows: usize, cols: usize) -> Self {
+        let mut result = Self::zeros(rows, cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                result[(i, j)] = self[(row + i, col + j)];
+            }
+        }
+        result
+    }
+
+    /// 把 block 写到左上角 (row, col) 处
+    pub fn set_block(&mut self, row: usize, col: usize, block: &Matrix) {
+        for i in 0..block.rows {
+            for j in 0..block.cols {
+                self[(row + i, col + j)] = block[(i, j)];
+            }
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut result = Self::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                result[(j, i)] = self[(i, j)];
+            }
+        }
+        result
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        Self {
+            data: self.data.iter().map(|v| v * factor).collect(),
+            ..self.clone()
+        }
+    }
+
+    /// 无穷范数（最大行绝对值和）
+    pub fn norm_inf(&self) -> f64 {
+        (0..self.rows)
+            .map(|i| (0..self.cols).map(|j| self[(i, j)].abs()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    /// 解 self·X = rhs（列主元高斯消去），self 必须为非奇异方阵
+    pub fn solve(&self, rhs: &Matrix) -> Result<Matrix, String> {
+        if !self.is_square() || rhs.rows != self.rows {
+            return Err(format!(
+                "无法求解 {}×{} 系数矩阵与 {}×{} 右端项的方程组",
+                self.rows, self.cols, rhs.rows, rhs.cols
+            ));
+        }
+        let n = self.rows;
+        let mut a = self.clone();
+        let mut x = rhs.clone();
+        let scale = self.norm_inf().max(f64::MIN_POSITIVE);
+        for k in 0..n {
+            let pivot = (k..n)
+                .max_by(|&i, &j| a[(i, k)].abs().total_cmp(&a[(j, k)].abs()))
+                .expect("k < n");
+            if a[(pivot, k)].abs() <= f64::EPSILON * scale {
+                return Err("矩阵奇异，方程组无唯一解".to_string());
+            }
+            a.swap_rows(k, pivot);
+            x.swap_rows(k, pivot);
+            for i in k + 1..n {
+                let factor = a[(i, k)] / a[(k, k)];
+                if factor == 0.0 {
+                    continue;
+                }
+                for j in k..n {
+                    let v = a[(k, j)];
+                    a[(i, j)] -= factor * v;
+                }
+                for j in 0..x.cols {
+                    let v = x[(k, j)];
+                    x[(i, j)] -= factor * v;
+                }
+            }
+        }
+        for k in (0..n).rev() {
+            for j in 0..x.cols {
+                let tail: f64 = (k + 1..n).map(|m| a[(k, m)] * x[(m, j)]).sum();
+                x[(k, j)] = (x[(k, j)] - tail) / a[(k, k)];
+            }
+        }
+        Ok(x)
+    }
+
+    /// 矩阵指数 e^A（缩放与平方 + 6 阶 Padé 近似，Golub & Van Loan 算法 11.3.1）
+    pub fn expm(&self) -> Result<Matrix, String> {
+        if !self.is_square() {
+            return Err(format!("矩阵指数要求方阵，得到 {}×{}", self.rows, self.cols));
+        }
+        let n = self.rows;
+        let norm = self.norm_inf();
+        if !norm.is_finite() {
+            return Err("矩阵含有非有限值".to_string());
+        }
+        // 缩放到 ‖A/2^j‖ ≤ 1/2，此时 6 阶 Padé 近似的相对误差约为 1e-16
+        let squarings = if norm > 0.5 { (norm / 0.5).log2().ceil() as i32 } else { 0 };
+        let a = self.scale(0.5f64.powi(squarings));
+        const Q: usize = 6;
+        let mut c = 1.0;
+        let mut x = Matrix::identity(n);
+        let mut numerator = Matrix::identity(n);
+        let mut denominator = Matrix::identity(n);
+        for k in 1..=Q {
+            c *= (Q - k + 1) as f64 / ((2 * Q - k + 1) * k) as f64;
+            x = &a * &x;
+            numerator = &numerator + &x.scale(c);
+            let sign = if k % 2 == 0 { c } else { -c };
+            denominator = &denominator + &x.scale(sign);
+        }
+        let mut result = denominator.solve(&numerator)?;
+        for _ in 0..squarings {
+            result = &result * &result;
+        }
+        Ok(result)
+    }
+
+    /// 特征多项式 det(λI - A) 的降幂系数（首一）
+    ///
+    /// 先用 Householder 相似变换约化为上 Hessenberg 矩阵，再按行列式展开的递推求系数
+    pub fn characteristic_polynomial(&self) -> Result<Vec<f64>, String> {
+        if !self.is_square() {
+            return Err(format!("特征多项式要求方阵，得到 {}×{}", self.rows, self.cols));
+        }
+        let n = self.rows;
+        let h = self.hessenberg();
+        // p[k] 为左上角 k×k 子块的特征多项式，升幂系数
+        let mut p: Vec<Vec<f64>> = vec![vec![1.0]];
+        for k in 0..n {
+            let mut next = vec![0.0; k + 2];
+            for (i, &c) in p[k].iter().enumerate() {
+                next[i + 1] += c;
+                next[i] -= h[(k, k)] * c;
+            }
+            let mut product = 1.0;
+            for i in (0..k).rev() {
+                product *= h[(i + 1, i)];
+                let factor = h[(i, k)] * product;
+                for (j, &c) in p[i].iter().enumerate() {
+                    next[j] -= factor * c;
+                }
+            }
+            p.push(next);
+        }
+        let mut coefficients = p.pop().expect("至少有 p[0]");
+        coefficients.reverse();
+        Ok(coefficients)
+    }
+
+    /// Householder 相似变换得到的上 Hessenberg 矩阵（特征值不变）
+    fn hessenberg(&self) -> Matrix {
+        let n = self.rows;
+        let mut h = self.clone();
+        for k in 0..n.saturating_sub(2) {
+            let norm = (k + 1..n).map(|i| h[(i, k)] * h[(i, k)]).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                continue;
+            }
+            let alpha = if h[(k + 1, k)] > 0.0 { -norm } else { norm };
+            let mut v: Vec<f64> = (k + 1..n).map(|i| h[(i, k)]).collect();
+            v[0] -= alpha;
+            let vv: f64 = v.iter().map(|x| x * x).sum();
+            if vv == 0.0 {
+                continue;
+            }
+            // H ← (I - 2vvᵀ/vᵀv)·H·(I - 2vvᵀ/vᵀv)
+            for j in 0..n {
+                let dot: f64 = v.iter().enumerate().map(|(m, x)| x * h[(k + 1 + m, j)]).sum();
+                let factor = 2.0 * dot / vv;
+                for (m, x) in v.iter().enumerate() {
+                    h[(k + 1 + m, j)] -= factor * x;
+                }
+            }
+            for i in 0..n {
+                let dot: f64 = v.iter().enumerate().map(|(m, x)| x * h[(i, k + 1 + m)]).sum();
+                let factor = 2.0 * dot / vv;
+                for (m, x) in v.iter().enumerate() {
+                    h[(i, k + 1 + m)] -= factor * x;
+                }
+            }
+        }
+        h
+    }
+
+    /// 对称矩阵的特征分解 A = V·diag(λ)·Vᵀ（循环 Jacobi 旋转），特征值按降序排列
+    pub fn symmetric_eigen(&self) -> Result<(Vec<f64>, Matrix), String> {
+        if !self.is_square() {
+            return Err(format!("特征分解要求方阵，得到 {}×{}", self.rows, self.cols));
+        }
+        let n = self.rows;
+        let mut a = self.clone();
+        let mut v = Matrix::identity(n);
+        let scale = self.norm_inf().max(f64::MIN_POSITIVE);
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            let off: f64 = (0..n).flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j))).map(|(i, j)| a[(i, j)].powi(2)).sum();
+            if off.sqrt() <= f64::EPSILON * scale {
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[(p, q)] == 0.0 {
+                        continue;
+                    }
+                    let theta = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                    let t = if theta == 0.0 { 1.0 } else { t };
+                    let c = 1.0 / (t * t + 1.0).sqrt();
+                    let s = t * c;
+                    for k in 0..n {
+                        let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                        a[(k, p)] = c * akp - s * akq;
+                        a[(k, q)] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+                        a[(p, k)] = c * apk - s * aqk;
+                        a[(q, k)] = s * apk + c * aqk;
+                    }
+                    for k in 0..n {
+                        let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                        v[(k, p)] = c * vkp - s * vkq;
+                        v[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| a[(j, j)].total_cmp(&a[(i, i)]));
+        let values = order.iter().map(|&i| a[(i, i)]).collect();
+        let mut vectors = Matrix::zeros(n, n);
+        for (column, &i) in order.iter().enumerate() {
+            for k in 0..n {
+                vectors[(k, column)] = v[(k, i)];
+            }
+        }
+        Ok((values, vectors))
+    }
+
+    /// 奇异值分解 A = U·diag(σ)·Vᵀ（单边 Jacobi），要求行数不少于列数，奇异值按降序排列
+    pub fn svd(&self) -> Result<(Matrix, Vec<f64>, Matrix), String> {
+        if self.rows < self.cols {
+            return Err(format!("奇异值分解要求行数不少于列数，得到 {}×{}", self.rows, self.cols));
+        }
+        let (m, n) = (self.rows, self.cols);
+        let mut u = self.clone();
+        let mut v = Matrix::identity(n);
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let alpha: f64 = (0..m).map(|k| u[(k, p)].powi(2)).sum();
+                    let beta: f64 = (0..m).map(|k| u[(k, q)].powi(2)).sum();
+                    let gamma: f64 = (0..m).map(|k| u[(k, p)] * u[(k, q)]).sum();
+                    if gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() || gamma == 0.0 {
+                        continue;
+                    }
+                    rotated = true;
+                    let zeta = (beta - alpha) / (2.0 * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                    let t = if zeta == 0.0 { 1.0 } else { t };
+                    let c = 1.0 / (1.0 + t * t).sqrt();
+                    let s = c * t;
+                    for k in 0..m {
+                        let (ukp, ukq) = (u[(k, p)], u[(k, q)]);
+                        u[(k, p)] = c * ukp - s * ukq;
+                        u[(k, q)] = s * ukp + c * ukq;
+                    }
+                    for k in 0..n {
+                        let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                        v[(k, p)] = c * vkp - s * vkq;
+                        v[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+        let sigma: Vec<f64> = (0..n).map(|j| (0..m).map(|k| u[(k, j)].powi(2)).sum::<f64>().sqrt()).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| sigma[j].total_cmp(&sigma[i]));
+        let mut left = Matrix::zeros(m, n);
+        let mut right = Matrix::zeros(n, n);
+        for (column, &j) in order.iter().enumerate() {
+            for k in 0..m {
+                left[(k, column)] = if sigma[j] > 0.0 { u[(k, j)] / sigma[j] } else { 0.0 };
+            }
+            for k in 0..n {
+                right[(k, column)] = v[(k, j)];
+            }
+        }
+        Ok((left, order.iter().map(|&j| sigma[j]).collect(), right))
+    }
+
+    fn swap_rows(&mut self, i: usize, j: usize) {
+        if i != j {
+            for k in 0..self.cols {
+                self.data.swap(i * self.cols + k, j * self.cols + k);
+            }
+        }
+    }
+}
+
+impl From<Matrix> for Vec<Vec<f64>> {
+    fn from(matrix: Matrix) -> Self {
+        matrix.to_rows()
+    }
+}
+
+impl TryFrom<Vec<Vec<f64>>> for Matrix {
+    type Error = String;
+
+    fn try_from(rows: Vec<Vec<f64>>) -> Result<Self, String> {
+        Matrix::from_rows(&rows)
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, rhs: &Matrix) -> Matrix {
+        assert_eq!(self.cols, rhs.rows, "矩阵乘法维数不匹配");
+        let mut result = Matrix::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let v = self[(i, k)];
+                if v == 0.0 {
+                    continue;
+                }
+                for j in 0..rhs.cols {
+                    result[(i, j)] += v * rhs[(k, j)];
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Add for &Matrix {
+    type Output = Matrix;
+
+    fn add(self, rhs: &Matrix) -> Matrix {
+        assert_eq!((self.rows, self.cols), (rhs.rows, rhs.cols), "矩阵加法维数不匹配");
+        Matrix {
+            data: self.data.iter().zip(&rhs.data).map(|(a, b)| a + b).collect(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Sub for &Matrix {
+    type Output = Matrix;
+
+    fn sub(self, rhs: &Matrix) -> Matrix {
+        assert_eq!((self.rows, self.cols), (rhs.rows, rhs.cols), "矩阵减法维数不匹配");
+        Matrix {
+            data: self.data.iter().zip(&rhs.data).map(|(a, b)| a - b).collect(),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_and_expm() {
+        let a = Matrix::from_rows(&[vec![0.0, 2.0, 1.0], vec![1.0, -1.0, 0.0], vec![3.0, 0.0, 4.0]]).unwrap();
+        let x = Matrix::column(&[1.0, -2.0, 0.5]);
+        let solved = a.solve(&(&a * &x)).unwrap();
+        assert!((&solved - &x).norm_inf() < 1e-12);
+        assert!(Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap().solve(&x.block(0, 0, 2, 1)).is_err());
+
+        // 旋转生成元 [[0, θ], [-θ, 0]] 的指数为旋转矩阵，范数较大时需要多次平方
+        let theta = 7.3;
+        let rotation = Matrix::from_rows(&[vec![0.0, theta], vec![-theta, 0.0]]).unwrap().expm().unwrap();
+        let expected = Matrix::from_rows(&[vec![theta.cos(), theta.sin()], vec![-theta.sin(), theta.cos()]]).unwrap();
+        assert!((&rotation - &expected).norm_inf() < 1e-12);
+
+        let blocks = Matrix::from_blocks(&[&[&Matrix::identity(2), &x.block(0, 0, 2, 1)], &[&Matrix::row(&[5.0, 6.0]), &Matrix::scalar(7.0)]]).unwrap();
+        assert_eq!(blocks.to_rows(), vec![vec![1.0, 0.0, 1.0], vec![0.0, 1.0, -2.0], vec![5.0, 6.0, 7.0]]);
+    }
+
+    #[test]
+    fn test_decompositions() {
+        // 特征值 1、2、3 的矩阵：特征多项式 λ³ - 6λ² + 11λ - 6
+        let a = Matrix::from_rows(&[vec![2.0, 1.0, 0.5], vec![0.0, 3.0, -1.0], vec![0.0, 0.0, 1.0]]).unwrap();
+        let similar = Matrix::from_rows(&[vec![1.0, 2.0, 0.0], vec![0.5, 1.0, 1.0], vec![0.0, 1.0, 3.0]]).unwrap();
+        let mixed = &similar.solve(&a).unwrap() * &similar;
+        let p = mixed.characteristic_polynomial().unwrap();
+        for (x, y) in p.iter().zip(&[1.0, -6.0, 11.0, -6.0]) {
+            assert!((x - y).abs() < 1e-12, "{:?}", p);
+        }
+
+        let symmetric = Matrix::from_rows(&[vec![4.0, 1.0, 2.0], vec![1.0, 3.0, 0.0], vec![2.0, 0.0, 5.0]]).unwrap();
+        let (values, vectors) = symmetric.symmetric_eigen().unwrap();
+        assert!(values.windows(2).all(|w| w[0] >= w[1]));
+        let mut diagonal = Matrix::zeros(3, 3);
+        for (i, &v) in values.iter().enumerate() {
+            diagonal[(i, i)] = v;
+        }
+        assert!((&(&(&vectors * &diagonal) * &vectors.transpose()) - &symmetric).norm_inf() < 1e-12);
+
+        let (u, sigma, v) = mixed.svd().unwrap();
+        let mut diagonal = Matrix::zeros(3, 3);
+        for (i, &s) in sigma.iter().enumerate() {
+            diagonal[(i, i)] = s;
+        }
+        assert!((&(&(&u * &diagonal) * &v.transpose()) - &mixed).norm_inf() < 1e-12);
+        assert!((sigma.iter().product::<f64>() - 6.0).abs() < 1e-12);
+
+        // 序列化为按行嵌套的数组
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, "[[2.0,1.0,0.5],[0.0,3.0,-1.0],[0.0,0.0,1.0]]");
+        assert_eq!(serde_json::from_str::<Matrix>(&json).unwrap(), a);
+        assert!(serde_json::from_str::<Matrix>("[[1.0],[1.0,2.0]]").is_err());
+    }
+}
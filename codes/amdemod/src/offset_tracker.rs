@@ -0,0 +1,264 @@
+// 频率偏差跟踪模块
+// 接收机载波漂移时 f_d 随时间变化：分帧做 FFT，逐帧估计幅度谱对称轴，得到 f_d(t) 轨迹
+
+use crate::fft_processor::{compute_rfft, FftResult};
+use crate::frequency_estimator::{find_symmetric_axis, SymmetricAxisConfig};
+use serde::{Deserialize, Serialize};
+
+/// 短时跟踪的参数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrackerConfig {
+    /// 帧长（采样点数），决定频率分辨率 f_s / frame_length
+    pub frame_length: usize,
+    /// 帧移（采样点数）
+    pub hop: usize,
+    /// 每帧只在 nominal ± max_deviation (Hz) 内搜索对称轴
+    pub max_deviation: f64,
+    /// 镜像比较的偏移范围 (Hz)，同 [`SymmetricAxisConfig::offset_range`]
+    pub offset_range: (f64, f64),
+    /// 帧内镜像能量占比低于该值时认为该帧无法估计（如静音段）
+    pub min_score: f64,
+    /// 对逐帧结果做中值滤波的窗长（帧数，取奇数；1 表示不平滑）
+    pub median_window: usize,
+}
+
+impl Default for TrackerConfig {
+    fn default() -> Self {
+        Self {
+            frame_length: 4096,
+            hop: 1024,
+            max_deviation: 200.0,
+            offset_range: (20.0, 4000.0),
+            min_score: 0.5,
+            median_window: 5,
+        }
+    }
+}
+
+/// 一帧的估计结果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OffsetFrame {
+    /// 帧中心时刻 (s)
+    pub time: f64,
+    /// 该帧的对称轴 (Hz)；镜像能量占比不足时为 `None`
+    pub raw: Option<f64>,
+    /// 该帧最佳候选的镜像能量占比
+    pub score: f64,
+    /// 平滑并补齐缺失帧后的 f_d (Hz)
+    pub f_d: f64,
+}
+
+/// f_d(t) 轨迹
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OffsetTrack {
+    pub sample_rate: f64,
+    /// 跟踪所围绕的标称 f_d (Hz)，通常取整段信号的估计值
+    pub nominal: f64,
+    pub config: TrackerConfig,
+    pub frames: Vec<OffsetFrame>,
+}
+
+impl OffsetTrack {
+    /// 时刻 t (s) 的 f_d：在相邻帧中心之间线性插值，首帧之前和末帧之后保持端点值
+    pub fn offset_at(&self, t: f64) -> f64 {
+        let frames = &self.frames;
+        let Some(first) = frames.first() else {
+            return self.nominal;
+        };
+        let next = frames.partition_point(|frame| frame.time <= t);
+        if next == 0 {
+            return first.f_d;
+        }
+        if next == frames.len() {
+            return frames[next - 1].f_d;
+        }
+        let (a, b) = (&frames[next - 1], &frames[next]);
+        a.f_d + (b.f_d - a.f_d) * (t - a.time) / (b.time - a.time)
+    }
+
+    /// 逐样本的瞬时 f_d，可直接交给 [`crate::demodulator::multiply_with_nco`]
+    pub fn per_sample(&self, num_samples: usize) -> Vec<f64> {
+        (0..num_samples)
+            .map(|i| self.offset_at(i as f64 / self.sample_rate))
+            .collect()
+    }
+
+    /// 轨迹的 (最小值, 平均值, 最大值) (Hz)
+    pub fn summary(&self) -> (f64, f64, f64) {
+        if self.frames.is_empty() {
+            return (self.nominal, self.nominal, self.nominal);
+        }
+        let min = self.frames.iter().map(|f| f.f_d).fold(f64::INFINITY, f64::min);
+        let max = self.frames.iter().map(|f| f.f_d).fold(f64::NEG_INFINITY, f64::max);
+        let mean = self.frames.iter().map(|f| f.f_d).sum::<f64>() / self.frames.len() as f64;
+        (min, mean, max)
+    }
+
+    /// 能独立估计出对称轴的帧数
+    pub fn valid_frames(&self) -> usize {
+        self.frames.iter().filter(|f| f.raw.is_some()).count()
+    }
+}
+
+/// 跟踪 f_d(t)
+///
+/// 每帧加 Hanning 窗做 FFT，在 nominal ± max_deviation 内估计幅度谱的对称轴。
+/// 镜像能量占比不足的帧记为缺失；对有效帧做中值滤波去掉野值，缺失帧由相邻有效帧线性插值补齐。
+/// 信号短于一帧时整段作为一帧。没有任何有效帧时返回错误。
+pub fn track_frequency_offset(
+    samples: &[f64],
+    sample_rate: f64,
+    nominal: f64,
+    config: &TrackerConfig,
+) -> Result<OffsetTrack, String> {
+    if config.frame_length < 2 || config.hop == 0 {
+        return Err(format!(
+            "frame_length = {} / hop = {} 无效",
+            config.frame_length, config.hop
+        ));
+    }
+    if samples.is_empty() {
+        return Err("信号为空，无法跟踪 f_d".to_string());
+    }
+
+    let frame_length = config.frame_length.min(samples.len());
+    let axis_config = SymmetricAxisConfig {
+        search_range: (nominal - config.max_deviation, nominal + config.max_deviation),
+        offset_range: config.offset_range,
+        max_candidates: 1,
+        min_score: config.min_score,
+        ..SymmetricAxisConfig::default()
+    };
+
+    let mut frames: Vec<OffsetFrame> = (0..=samples.len() - frame_length)
+        .step_by(config.hop)
+        .map(|start| {
+            let frame = FftResult::apply_hanning_window(&samples[start..start + frame_length]);
+            let spectrum = FftResult::from_half_spectrum(compute_rfft(&frame), frame_length, sample_rate);
+            let (frequencies, magnitude) = spectrum.two_sided_magnitude();
+            let estimate = find_symmetric_axis(&frequencies, &magnitude, &axis_config);
+            OffsetFrame {
+                time: (start as f64 + frame_length as f64 / 2.0) / sample_rate,
+                raw: estimate.axis,
+                score: estimate.candidates.first().map_or(0.0, |c| c.score),
+                f_d: nominal,
+            }
+        })
+        .collect();
+
+    let valid: Vec<usize> = (0..frames.len()).filter(|&i| frames[i].raw.is_some()).collect();
+    if valid.is_empty() {
+        return Err(format!(
+            "{} 帧中没有一帧的镜像能量占比达到 {:.2}，无法跟踪 f_d",
+            frames.len(),
+            config.min_score
+        ));
+    }
+
+    // 在有效帧序列上做中值滤波
+    let raw: Vec<f64> = valid.iter().filter_map(|&i| frames[i].raw).collect();
+    let half_window = config.median_window.max(1) / 2;
+    for (k, &i) in valid.iter().enumerate() {
+        let lo = k.saturating_sub(half_window);
+        let hi = (k + half_window + 1).min(raw.len());
+        let mut window = raw[lo..hi].to_vec();
+        window.sort_by(f64::total_cmp);
+        frames[i].f_d = window[window.len() / 2];
+    }
+
+    // 缺失帧：两侧有效帧之间线性插值，两端保持最近的有效值
+    for i in 0..frames.len() {
+        if frames[i].raw.is_some() {
+            continue;
+        }
+        let next = valid.partition_point(|&v| v < i);
+        frames[i].f_d = match (next.checked_sub(1).map(|k| valid[k]), valid.get(next)) {
+            (Some(a), Some(&b)) => {
+                let (fa, fb) = (frames[a].f_d, frames[b].f_d);
+                fa + (fb - fa) * (i - a) as f64 / (b - a) as f64
+            }
+            (Some(a), None) => frames[a].f_d,
+            (None, Some(&b)) => frames[b].f_d,
+            (None, None) => nominal,
+        };
+    }
+
+    Ok(OffsetTrack {
+        sample_rate,
+        nominal,
+        config: *config,
+        frames,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    /// 载波频率偏差从 f_start 线性漂移到 f_end 的错误解调信号，基带为三个正弦分量
+    fn drifting(f_start: f64, f_end: f64, sample_rate: f64, n: usize) -> Vec<f64> {
+        let duration = n as f64 / sample_rate;
+        let tones = [(300.0, 1.0), (750.0, 0.7), (1300.0, 0.5)];
+        (0..n)
+            .map(|i| {
+                let t = i as f64 / sample_rate;
+                // 瞬时频率 f_start + (f_end - f_start)·t/T 的积分
+                let phase = 2.0 * PI * (f_start * t + 0.5 * (f_end - f_start) * t * t / duration);
+                let m: f64 = tones
+                    .iter()
+                    .map(|&(f_m, amp)| amp * (2.0 * PI * f_m * t + f_m).sin())
+                    .sum();
+                m * phase.cos()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tracks_linear_drift() {
+        let sample_rate = 22050.0;
+        let n = 4 * 22050;
+        let samples = drifting(2950.0, 3050.0, sample_rate, n);
+
+        let track = track_frequency_offset(&samples, sample_rate, 3000.0, &TrackerConfig::default()).unwrap();
+        assert_eq!(track.valid_frames(), track.frames.len());
+        for frame in &track.frames {
+            let expected = 2950.0 + 100.0 * frame.time / 4.0;
+            assert!(
+                (frame.f_d - expected).abs() < 3.0,
+                "t = {:.3} s: tracked {:.2} Hz, expected {:.2} Hz",
+                frame.time,
+                frame.f_d,
+                expected
+            );
+        }
+
+        let (min, _, max) = track.summary();
+        assert!(max - min > 80.0);
+    }
+
+    #[test]
+    fn test_offset_interpolation_and_gap_filling() {
+        let sample_rate = 22050.0;
+        let mut samples = drifting(3000.0, 3000.0, sample_rate, 22050);
+        // 中间一段静音，对应的帧应被标记为缺失并由两侧补齐
+        samples[8000..15000].iter_mut().for_each(|x| *x = 0.0);
+
+        let track = track_frequency_offset(&samples, sample_rate, 3000.0, &TrackerConfig::default()).unwrap();
+        assert!(track.valid_frames() < track.frames.len());
+        for frame in &track.frames {
+            assert!((frame.f_d - 3000.0).abs() < 3.0);
+        }
+
+        let per_sample = track.per_sample(samples.len());
+        assert_eq!(per_sample.len(), samples.len());
+        assert_eq!(per_sample[0], track.frames[0].f_d);
+        assert_eq!(per_sample[samples.len() - 1], track.frames.last().unwrap().f_d);
+    }
+
+    #[test]
+    fn test_silence_is_an_error() {
+        let err = track_frequency_offset(&[0.0; 8192], 22050.0, 3000.0, &TrackerConfig::default()).unwrap_err();
+        assert!(err.contains("无法跟踪"));
+    }
+}
@@ -0,0 +1,370 @@
+// 流水线参数文件模块
+// 各阶段之间通过带版本号的 JSON 文件传递 f_d、f_s、f_B 及滤波器设计结果
+
+use crate::butterworth_filter::{FilterType, IirDesign};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// 参数文件格式版本，字段含义发生变化时递增
+///
+/// 3：滤波器增加二阶节 `sos`、带通 / 带阻的 `upper_cutoff` 与模拟原型的族 `family`
+pub const PARAMS_VERSION: u32 = 3;
+
+/// 仍可读取的最早版本；版本 2 的文件没有版本 3 新增的字段，各项取默认值（b/a 形式的 Butterworth）
+pub const OLDEST_PARAMS_VERSION: u32 = 2;
+
+/// 频率偏差估计方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimatorMethod {
+    /// 幅度谱峰值搜索
+    PeakSearch,
+    /// 峰值附近三点抛物线插值
+    ParabolicInterpolation,
+    /// 幅度谱的镜像对称轴（旧版本的对称峰值对方法也记为此项）
+    #[serde(alias = "symmetric_peaks")]
+    SymmetricAxis,
+    /// 由用户直接指定
+    Manual,
+}
+
+/// f_d 的估计结果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FrequencyOffset {
+    /// 频率偏差 f_d (Hz)
+    pub f_d: f64,
+    /// 使用的估计方法
+    pub method: EstimatorMethod,
+    /// 置信度，取值 [0, 1]
+    pub confidence: f64,
+}
+
+impl FrequencyOffset {
+    /// 用户指定的 f_d，置信度记为 1
+    pub fn manual(f_d: f64) -> Self {
+        Self {
+            f_d,
+            method: EstimatorMethod::Manual,
+            confidence: 1.0,
+        }
+    }
+}
+
+/// Q2 设计的两个滤波器
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FilterDesigns {
+    /// 高通滤波器（截止频率 f_d）
+    pub highpass: IirDesign,
+    /// 低通滤波器（截止频率 f_B）
+    pub lowpass: IirDesign,
+}
+
+/// 流水线参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineParams {
+    /// 文件格式版本；读取时接受 `OLDEST_PARAMS_VERSION` 至 `PARAMS_VERSION`，并升级为 `PARAMS_VERSION`
+    pub version: u32,
+    /// 采样率 f_s (Hz)
+    pub sample_rate: f64,
+    /// 音频样本数 N，频率响应在 k·f_s/N 上计算
+    pub num_samples: usize,
+    /// 频率偏差估计结果
+    pub offset: FrequencyOffset,
+    /// 基带带宽 f_B (Hz)
+    pub bandwidth: f64,
+    /// 滤波器设计（Q2 之后才有）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<FilterDesigns>,
+}
+
+/// 只用于在完整解析前检查版本号
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: Option<u32>,
+}
+
+impl PipelineParams {
+    /// 由 Q1 的估计结果创建参数（尚未包含滤波器）
+    pub fn new(sample_rate: f64, num_samples: usize, offset: FrequencyOffset, bandwidth: f64) -> Self {
+        Self {
+            version: PARAMS_VERSION,
+            sample_rate,
+            num_samples,
+            offset,
+            bandwidth,
+            filters: None,
+        }
+    }
+
+    /// 频率偏差 f_d (Hz)
+    pub fn f_d(&self) -> f64 {
+        self.offset.f_d
+    }
+
+    /// 读取参数文件并做完整性检查
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read parameter file {}: {}", path.display(), e))?;
+        Self::from_json(&content).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    /// 从 JSON 文本解析参数并做完整性检查
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let probe: VersionProbe =
+            serde_json::from_str(content).map_err(|e| format!("invalid parameter file: {}", e))?;
+        match probe.version {
+            None => return Err("missing field `version`".to_string()),
+            Some(v) if !(OLDEST_PARAMS_VERSION..=PARAMS_VERSION).contains(&v) => {
+                return Err(format!(
+                    "unsupported parameter file version {} (expected {}..={})",
+                    v, OLDEST_PARAMS_VERSION, PARAMS_VERSION
+                ))
+            }
+            Some(_) => {}
+        }
+
+        let mut params: Self =
+            serde_json::from_str(content).map_err(|e| format!("invalid parameter file: {}", e))?;
+        params.version = PARAMS_VERSION;
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// 写入参数文件（会先做完整性检查）
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
+        self.validate()?;
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        println!("参数已保存到 {}", path.display());
+        Ok(())
+    }
+
+    /// 检查各字段取值是否合理、滤波器是否与参数一致
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version != PARAMS_VERSION {
+            return Err(format!(
+                "unsupported parameter file version {} (expected {})",
+                self.version, PARAMS_VERSION
+            ));
+        }
+        let nyquist = self.sample_rate / 2.0;
+        if !(self.sample_rate.is_finite() && self.sample_rate > 0.0) {
+            return Err(format!("sample_rate must be positive, got {}", self.sample_rate));
+        }
+        if self.num_samples == 0 {
+            return Err("num_samples must be positive".to_string());
+        }
+        if !(self.offset.f_d > 0.0 && self.offset.f_d < nyquist) {
+            return Err(format!(
+                "offset.f_d = {} Hz is outside (0, {}) Hz",
+                self.offset.f_d, nyquist
+            ));
+        }
+        if !(self.bandwidth > 0.0 && self.bandwidth < nyquist) {
+            return Err(format!(
+                "bandwidth = {} Hz is outside (0, {}) Hz",
+                self.bandwidth, nyquist
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.offset.confidence) {
+            return Err(format!(
+                "offset.confidence = {} is outside [0, 1]",
+                self.offset.confidence
+            ));
+        }
+
+        if let Some(filters) = &self.filters {
+            check_filter("filters.highpass", &filters.highpass, FilterType::Highpass, self.offset.f_d, self.sample_rate)?;
+            check_filter("filters.lowpass", &filters.lowpass, FilterType::Lowpass, self.bandwidth, self.sample_rate)?;
+        }
+        Ok(())
+    }
+
+    /// 获取滤波器设计，缺失时给出提示
+    pub fn require_filters(&self) -> Result<&FilterDesigns, String> {
+        self.filters
+            .as_ref()
+            .ok_or_else(|| "parameter file has no `filters` section; run the filter design stage (Q2) first".to_string())
+    }
+}
+
+fn check_filter(
+    name: &str,
+    filter: &IirDesign,
+    expected_type: FilterType,
+    expected_cutoff: f64,
+    sample_rate: f64,
+) -> Result<(), String> {
+    if filter.filter_type != expected_type {
+        return Err(format!(
+            "{}.filter_type is {:?}, expected {:?}",
+            name, filter.filter_type, expected_type
+        ));
+    }
+    if (filter.sample_rate - sample_rate).abs() > 1e-6 {
+        return Err(format!(
+            "{}.sample_rate = {} Hz does not match sample_rate = {} Hz",
+            name, filter.sample_rate, sample_rate
+        ));
+    }
+    if (filter.cutoff - expected_cutoff).abs() > 1e-6 {
+        return Err(format!(
+            "{}.cutoff = {} Hz does not match expected {} Hz",
+            name, filter.cutoff, expected_cutoff
+        ));
+    }
+    if filter.upper_cutoff.is_some() != filter.filter_type.is_band() {
+        return Err(format!("{}.upper_cutoff must be given exactly for band filters", name));
+    }
+    let degree = filter.degree();
+    if filter.b.len() != degree + 1 || filter.a.len() != degree + 1 {
+        return Err(format!(
+            "{} has {} b and {} a coefficients, expected {} each for order {}",
+            name,
+            filter.b.len(),
+            filter.a.len(),
+            degree + 1,
+            filter.order
+        ));
+    }
+    if filter.a.first().copied().unwrap_or(0.0) == 0.0 {
+        return Err(format!("{}.a[0] must be non-zero", name));
+    }
+    filter.family.validate().map_err(|e| format!("{}.family: {}", name, e))?;
+    if !filter.sos.is_empty() {
+        if filter.sos.len() != degree.div_ceil(2).max(1) {
+            return Err(format!(
+                "{} has {} second-order sections, expected {} for order {}",
+                name,
+                filter.sos.len(),
+                degree.div_ceil(2).max(1),
+                filter.order
+            ));
+        }
+        if filter.sos.iter().any(|section| section[3] == 0.0 || section.iter().any(|v| !v.is_finite())) {
+            return Err(format!("{}.sos must be finite with non-zero a0 in every section", name));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analog_prototype::FilterFamily;
+
+    fn sample_params() -> PipelineParams {
+        let offset = FrequencyOffset {
+            f_d: 3000.0,
+            method: EstimatorMethod::SymmetricAxis,
+            confidence: 0.95,
+        };
+        let mut params = PipelineParams::new(22050.0, 31265, offset, 4000.0);
+        params.filters = Some(FilterDesigns {
+            highpass: IirDesign::highpass(8, 3000.0, 22050.0),
+            lowpass: IirDesign::lowpass(8, 4000.0, 22050.0),
+        });
+        params
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let params = sample_params();
+        let json = serde_json::to_string(&params).unwrap();
+        let loaded = PipelineParams::from_json(&json).unwrap();
+
+        assert_eq!(loaded.offset, params.offset);
+        let filters = loaded.require_filters().unwrap();
+        assert_eq!(filters.highpass.b, params.filters.as_ref().unwrap().highpass.b);
+        assert_eq!(filters.highpass.sos, params.filters.as_ref().unwrap().highpass.sos);
+        assert!(!json.contains("family"));
+
+        // 非 Butterworth 的族随设计一起保存
+        let mut params = params;
+        let family = FilterFamily::Elliptic { ripple_db: 0.5, attenuation_db: 60.0 };
+        params.filters.as_mut().unwrap().highpass =
+            IirDesign::with_family(family, 8, 3000.0, 22050.0, FilterType::Highpass).unwrap();
+        let loaded = PipelineParams::from_json(&serde_json::to_string(&params).unwrap()).unwrap();
+        assert_eq!(loaded.require_filters().unwrap().highpass.family, family);
+    }
+
+    #[test]
+    fn test_file_without_sos_still_loads() {
+        // 早期的参数文件只有 b/a，滤波时退回直接型
+        let mut params = sample_params();
+        let filters = params.filters.as_mut().unwrap();
+        filters.highpass.sos.clear();
+        filters.lowpass.sos.clear();
+        let json = serde_json::to_string(&params).unwrap();
+        assert!(!json.contains("sos"));
+        let loaded = PipelineParams::from_json(&json).unwrap();
+        assert!(loaded.require_filters().unwrap().lowpass.to_iir_filter().is_ok());
+
+        params.filters.as_mut().unwrap().lowpass.sos = vec![[1.0, 0.0, 0.0, 1.0, 0.0, 0.0]];
+        assert!(params.validate().unwrap_err().contains("second-order sections"));
+    }
+
+    #[test]
+    fn test_version_2_file_still_loads() {
+        // 版本 2 的文件：滤波器只有 b/a，没有 sos、upper_cutoff 和 family
+        let mut params = sample_params();
+        let filters = params.filters.as_mut().unwrap();
+        filters.highpass.sos.clear();
+        filters.lowpass.sos.clear();
+        let json = serde_json::to_string(&params)
+            .unwrap()
+            .replace(&format!("\"version\":{}", PARAMS_VERSION), "\"version\":2");
+        assert!(json.contains("\"version\":2"));
+
+        let loaded = PipelineParams::from_json(&json).unwrap();
+        assert_eq!(loaded.version, PARAMS_VERSION);
+        let filters = loaded.require_filters().unwrap();
+        assert_eq!(filters.highpass.family, FilterFamily::Butterworth);
+        assert_eq!(filters.lowpass.b, params.filters.as_ref().unwrap().lowpass.b);
+
+        // 读入后按当前版本写出，再读回内容不变
+        let saved = serde_json::to_string(&loaded).unwrap();
+        assert!(saved.contains(&format!("\"version\":{}", PARAMS_VERSION)));
+        let reloaded = PipelineParams::from_json(&saved).unwrap();
+        assert_eq!(reloaded.offset, loaded.offset);
+        assert_eq!(reloaded.require_filters().unwrap().highpass.a, filters.highpass.a);
+
+        let json = json.replace("\"version\":2", "\"version\":1");
+        assert!(PipelineParams::from_json(&json).unwrap_err().contains("version 1"));
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let json = serde_json::to_string(&sample_params())
+            .unwrap()
+            .replace(&format!("\"version\":{}", PARAMS_VERSION), "\"version\":99");
+        let err = PipelineParams::from_json(&json).unwrap_err();
+        assert!(err.contains("version 99"));
+    }
+
+    #[test]
+    fn test_missing_field() {
+        let json = format!(
+            r#"{{"version":{},"sample_rate":22050.0,"num_samples":31265,"bandwidth":4000.0}}"#,
+            PARAMS_VERSION
+        );
+        let err = PipelineParams::from_json(&json).unwrap_err();
+        assert!(err.contains("offset"));
+    }
+
+    #[test]
+    fn test_mismatched_filter() {
+        let mut params = sample_params();
+        params.filters.as_mut().unwrap().highpass = IirDesign::highpass(8, 2500.0, 22050.0);
+        let err = params.validate().unwrap_err();
+        assert!(err.contains("filters.highpass.cutoff"));
+    }
+}
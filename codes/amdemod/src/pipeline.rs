@@ -0,0 +1,469 @@
+// 端到端流水线
+// 在同一进程内依次执行 f_d 估计 → 滤波器设计 → 时域解调 → 频域解调 → 结果对比，
+// 每个阶段的输出按其输入与参数的哈希缓存，只有输入变化的阶段才会重新计算
+
+use crate::analog_prototype::FilterFamily;
+use crate::comparator::{self, ComparisonResult};
+use crate::filter_order::TransitionSpec;
+use crate::params::{FilterDesigns, FrequencyOffset, PipelineParams};
+use crate::stages::{self, OffsetAnalysis, SpectrumWindow, DEFAULT_BANDWIDTH, DEFAULT_FILTER_ORDER};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 缓存格式版本，阶段算法或缓存文件布局变化时递增，使旧缓存全部失效
+const CACHE_VERSION: u32 = 4;
+
+/// 流水线阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Estimate,
+    Design,
+    DemodTime,
+    DemodFreq,
+    Compare,
+}
+
+impl Stage {
+    /// 阶段名，同时用作缓存文件名
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Estimate => "estimate",
+            Stage::Design => "design",
+            Stage::DemodTime => "demod_time",
+            Stage::DemodFreq => "demod_freq",
+            Stage::Compare => "compare",
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 流水线配置
+#[derive(Debug, Clone)]
+pub struct PipelineConfig {
+    /// 直接给定的频率偏差；为 `None` 时由输入频谱估计
+    pub offset: Option<FrequencyOffset>,
+    /// 基带带宽 f_B (Hz)
+    pub bandwidth: f64,
+    /// 估计 f_d 所用输入频谱的窗与修正方式
+    pub spectrum: SpectrumWindow,
+    /// 滤波器阶数
+    pub order: usize,
+    /// 滤波器族
+    pub family: FilterFamily,
+    /// 过渡带指标；给出时按指标求最小阶数，`order` 不再使用
+    pub transition: Option<TransitionSpec>,
+    /// 缓存目录；为 `None` 时不使用缓存
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            offset: None,
+            bandwidth: DEFAULT_BANDWIDTH,
+            spectrum: SpectrumWindow::default(),
+            order: DEFAULT_FILTER_ORDER,
+            family: FilterFamily::Butterworth,
+            transition: None,
+            cache_dir: None,
+        }
+    }
+}
+
+/// 单个阶段的执行情况
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageRun {
+    pub stage: Stage,
+    /// 该阶段输入与参数的哈希
+    pub key: u64,
+    /// 是否直接取自缓存
+    pub cached: bool,
+}
+
+/// 流水线的全部结果
+#[derive(Debug, Clone)]
+pub struct PipelineOutput {
+    /// f_d 估计的中间结果（配置中直接给定 f_d 时为 `None`）
+    pub analysis: Option<OffsetAnalysis>,
+    /// 含滤波器设计的参数
+    pub params: PipelineParams,
+    /// 时域解调输出
+    pub time_domain: Vec<f64>,
+    /// 频域解调输出
+    pub frequency_domain: Vec<f64>,
+    /// 频域结果相对时域结果的对比
+    pub comparison: ComparisonResult,
+    /// 按执行顺序记录的各阶段情况
+    pub stages: Vec<StageRun>,
+}
+
+impl PipelineOutput {
+    /// 重新计算（未命中缓存）的阶段
+    pub fn recomputed(&self) -> Vec<Stage> {
+        self.stages.iter().filter(|run| !run.cached).map(|run| run.stage).collect()
+    }
+}
+
+/// 端到端流水线
+pub struct Pipeline {
+    config: PipelineConfig,
+}
+
+impl Pipeline {
+    pub fn new(config: PipelineConfig) -> Self {
+        Self { config }
+    }
+
+    /// 对一段单声道录音执行全部阶段
+    pub fn run(&self, samples: &[f64], sample_rate: f64) -> Result<PipelineOutput, Box<dyn Error>> {
+        let config = &self.config;
+        let mut runs = Vec::new();
+
+        let mut input_key = KeyHasher::new("input");
+        input_key.write_f64(sample_rate);
+        input_key.write_f64s(samples);
+        let input_key = input_key.finish();
+
+        // 阶段 1：f_d 估计（直接给定 f_d 时跳过）
+        let (analysis, offset) = match config.offset {
+            Some(offset) => (None, offset),
+            None => {
+                let mut key = KeyHasher::new(Stage::Estimate.name());
+                key.write_u64(input_key);
+                key.write_json(&config.spectrum)?;
+                let key = key.finish();
+                let analysis: OffsetAnalysis = self.cached(Stage::Estimate, key, &mut runs, || {
+                    Ok(Json(stages::estimate_offset(&config.spectrum.compute(samples, sample_rate)?)))
+                })?.0;
+                let offset = analysis.offset;
+                (Some(analysis), offset)
+            }
+        };
+        let mut params = PipelineParams::new(sample_rate, samples.len(), offset, config.bandwidth);
+        params.validate()?;
+
+        // 阶段 2：滤波器设计，只依赖 f_s、f_d、f_B、阶数（或过渡带指标）与滤波器族
+        let mut key = KeyHasher::new(Stage::Design.name());
+        key.write_json(&params)?;
+        key.write_u64(config.order as u64);
+        key.write_json(&config.family)?;
+        key.write_json(&config.transition)?;
+        let design_key = key.finish();
+        let filters: FilterDesigns = self.cached(Stage::Design, design_key, &mut runs, || {
+            let filters = match &config.transition {
+                Some(transition) => stages::design_filters_for_transition(&params, config.family, transition)?,
+                None => stages::design_filters_with(&params, config.order, config.family)?,
+            };
+            Ok(Json(filters))
+        })?.0;
+        params.filters = Some(filters);
+        params.validate()?;
+
+        // 阶段 3：时域解调
+        let mut key = KeyHasher::new(Stage::DemodTime.name());
+        key.write_u64(input_key);
+        key.write_u64(design_key);
+        let time_key = key.finish();
+        let time_domain = self.cached(Stage::DemodTime, time_key, &mut runs, || {
+            Ok(stages::demodulate_time_domain(samples, &params)?.x_l)
+        })?;
+
+        // 阶段 4：频域解调，使用理想滤波器，与滤波器阶数无关
+        let mut key = KeyHasher::new(Stage::DemodFreq.name());
+        key.write_u64(input_key);
+        key.write_f64(params.f_d());
+        key.write_f64(params.bandwidth);
+        let freq_key = key.finish();
+        let frequency_domain = self.cached(Stage::DemodFreq, freq_key, &mut runs, || {
+            Ok(stages::demodulate_frequency_domain(samples, &params).output)
+        })?;
+
+        // 阶段 5：结果对比
+        let mut key = KeyHasher::new(Stage::Compare.name());
+        key.write_u64(time_key);
+        key.write_u64(freq_key);
+        let comparison: ComparisonResult = self.cached(Stage::Compare, key.finish(), &mut runs, || {
+            Ok(Json(comparator::compare_signals(&frequency_domain, &time_domain)))
+        })?.0;
+
+        Ok(PipelineOutput {
+            analysis,
+            params,
+            time_domain,
+            frequency_domain,
+            comparison,
+            stages: runs,
+        })
+    }
+
+    /// 命中缓存时直接读取，否则计算并写入缓存
+    fn cached<T: Cacheable>(
+        &self,
+        stage: Stage,
+        key: u64,
+        runs: &mut Vec<StageRun>,
+        compute: impl FnOnce() -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        let cache = self.config.cache_dir.as_deref().map(StageCache::new);
+
+        if let Some(value) = cache.as_ref().and_then(|cache| cache.load::<T>(stage, key)) {
+            println!("[{}] cached ({:016x})", stage, key);
+            runs.push(StageRun { stage, key, cached: true });
+            return Ok(value);
+        }
+
+        println!("[{}] computing ({:016x})", stage, key);
+        let value = compute()?;
+        if let Some(cache) = &cache {
+            cache.store(stage, key, &value)?;
+        }
+        runs.push(StageRun { stage, key, cached: false });
+        Ok(value)
+    }
+}
+
+/// 缓存目录：每个阶段一个数据文件和一个记录哈希的 `.key` 文件
+struct StageCache<'a> {
+    dir: &'a Path,
+}
+
+impl<'a> StageCache<'a> {
+    fn new(dir: &'a Path) -> Self {
+        Self { dir }
+    }
+
+    fn paths<T: Cacheable>(&self, stage: Stage) -> (PathBuf, PathBuf) {
+        (
+            self.dir.join(format!("{}.key", stage.name())),
+            self.dir.join(format!("{}.{}", stage.name(), T::EXTENSION)),
+        )
+    }
+
+    /// 哈希一致且数据可解析时返回缓存值，其余情况一律视为未命中
+    fn load<T: Cacheable>(&self, stage: Stage, key: u64) -> Option<T> {
+        let (key_path, data_path) = self.paths::<T>(stage);
+        let stored = fs::read_to_string(key_path).ok()?;
+        if stored.trim() != format!("{:016x}", key) {
+            return None;
+        }
+        T::decode(&fs::read(data_path).ok()?).ok()
+    }
+
+    /// 先写数据再写哈希，写入中断时不会留下看似有效的缓存
+    fn store<T: Cacheable>(&self, stage: Stage, key: u64, value: &T) -> Result<(), Box<dyn Error>> {
+        let (key_path, data_path) = self.paths::<T>(stage);
+        fs::create_dir_all(self.dir)?;
+        let _ = fs::remove_file(&key_path);
+        fs::write(data_path, value.encode()?)?;
+        fs::write(key_path, format!("{:016x}\n", key))?;
+        Ok(())
+    }
+}
+
+/// 可写入缓存的阶段输出
+trait Cacheable: Sized {
+    const EXTENSION: &'static str;
+    fn encode(&self) -> Result<Vec<u8>, Box<dyn Error>>;
+    fn decode(bytes: &[u8]) -> Result<Self, Box<dyn Error>>;
+}
+
+/// 信号以小端 f64 原样保存
+impl Cacheable for Vec<f64> {
+    const EXTENSION: &'static str = "f64";
+
+    fn encode(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(self.iter().flat_map(|x| x.to_le_bytes()).collect())
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        if !bytes.len().is_multiple_of(8) {
+            return Err("truncated signal cache".into());
+        }
+        Ok(bytes
+            .chunks_exact(8)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect())
+    }
+}
+
+/// 其余结构化输出以 JSON 保存
+struct Json<T>(T);
+
+impl<T: Serialize + DeserializeOwned> Cacheable for Json<T> {
+    const EXTENSION: &'static str = "json";
+
+    fn encode(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(serde_json::to_vec_pretty(&self.0)?)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Ok(Json(serde_json::from_slice(bytes)?))
+    }
+}
+
+/// 64 位 FNV-1a 哈希。与 `DefaultHasher` 不同，其结果在不同 Rust 版本间保持稳定，适合写入磁盘
+struct KeyHasher {
+    state: u64,
+}
+
+impl KeyHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    /// 以缓存版本和阶段名作为前缀，不同阶段的哈希互不相同
+    fn new(domain: &str) -> Self {
+        let mut hasher = Self { state: Self::OFFSET_BASIS };
+        hasher.write(&CACHE_VERSION.to_le_bytes());
+        hasher.write_bytes(domain.as_bytes());
+        hasher
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u64;
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// 带长度前缀，避免相邻字段拼接产生歧义
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_u64(bytes.len() as u64);
+        self.write(bytes);
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        self.write(&value.to_bits().to_le_bytes());
+    }
+
+    fn write_f64s(&mut self, values: &[f64]) {
+        self.write_u64(values.len() as u64);
+        for &value in values {
+            self.write_f64(value);
+        }
+    }
+
+    fn write_json<T: Serialize>(&mut self, value: &T) -> Result<(), Box<dyn Error>> {
+        self.write_bytes(&serde_json::to_vec(value)?);
+        Ok(())
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft_processor::FftResult;
+    use crate::window::{Correction, Window};
+    use std::f64::consts::PI;
+
+    fn test_signal() -> (Vec<f64>, f64) {
+        let f_s = 22050.0;
+        let samples = (0..4410)
+            .map(|i| {
+                let t = i as f64 / f_s;
+                (2.0 * PI * 500.0 * t).sin() * (2.0 * PI * 3000.0 * t).cos()
+            })
+            .collect();
+        (samples, f_s)
+    }
+
+    fn temp_cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("amdemod-pipeline-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_only_changed_stages_recompute() {
+        let (samples, f_s) = test_signal();
+        let dir = temp_cache_dir("recompute");
+        let mut config = PipelineConfig {
+            offset: Some(FrequencyOffset::manual(3000.0)),
+            cache_dir: Some(dir.clone()),
+            ..PipelineConfig::default()
+        };
+
+        let first = Pipeline::new(config.clone()).run(&samples, f_s).unwrap();
+        assert_eq!(first.recomputed().len(), 4);
+
+        let second = Pipeline::new(config.clone()).run(&samples, f_s).unwrap();
+        assert!(second.recomputed().is_empty());
+        assert_eq!(second.time_domain, first.time_domain);
+        assert_eq!(second.frequency_domain, first.frequency_domain);
+
+        // 阶数只影响滤波器设计、时域解调和对比，频域解调直接取缓存
+        config.order = 6;
+        let third = Pipeline::new(config).run(&samples, f_s).unwrap();
+        assert_eq!(third.recomputed(), vec![Stage::Design, Stage::DemodTime, Stage::Compare]);
+        assert_eq!(third.params.require_filters().unwrap().highpass.order, 6);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_corrupt_cache_is_recomputed() {
+        let (samples, f_s) = test_signal();
+        let dir = temp_cache_dir("corrupt");
+        let config = PipelineConfig {
+            offset: Some(FrequencyOffset::manual(3000.0)),
+            cache_dir: Some(dir.clone()),
+            ..PipelineConfig::default()
+        };
+
+        let first = Pipeline::new(config.clone()).run(&samples, f_s).unwrap();
+        fs::write(dir.join("demod_freq.f64"), [0u8; 5]).unwrap();
+
+        let second = Pipeline::new(config).run(&samples, f_s).unwrap();
+        assert_eq!(second.recomputed(), vec![Stage::DemodFreq]);
+        assert_eq!(second.frequency_domain, first.frequency_domain);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_spectrum_window_keys_the_estimate() {
+        let (samples, f_s) = test_signal();
+        let dir = temp_cache_dir("spectrum");
+        let mut config = PipelineConfig {
+            cache_dir: Some(dir.clone()),
+            ..PipelineConfig::default()
+        };
+
+        // 默认的矩形窗与不加窗的频谱给出相同的估计
+        let rectangular = Pipeline::new(config.clone()).run(&samples, f_s).unwrap();
+        let unwindowed = stages::estimate_offset(&FftResult::compute(&samples, f_s));
+        assert_eq!(rectangular.params.f_d(), unwindowed.offset.f_d);
+
+        config.spectrum = SpectrumWindow { window: Window::Hann, correction: Correction::Energy };
+        let hann = Pipeline::new(config).run(&samples, f_s).unwrap();
+        assert_eq!(hann.recomputed()[0], Stage::Estimate);
+        assert!((hann.params.f_d() - 3000.0).abs() < 5.0, "f_d = {}", hann.params.f_d());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_key_depends_on_input() {
+        let mut a = KeyHasher::new("input");
+        a.write_f64s(&[1.0, 2.0]);
+        let mut b = KeyHasher::new("input");
+        b.write_f64s(&[1.0, 2.0 + f64::EPSILON * 2.0]);
+        assert_ne!(a.finish(), b.finish());
+    }
+}
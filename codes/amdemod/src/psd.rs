@@ -0,0 +1,423 @@
+// 功率谱密度估计模块
+// 单次周期图的方差不随信号长度减小；分段平均（Bartlett、Welch）或多窗平均（DPSS）后得到
+// 以 V²/Hz 为单位、带置信区间的功率谱密度，供 f_d 估计和能量分布统计使用
+
+use crate::fft_processor::compute_rfft;
+use crate::window::{dpss, Window, WindowGains};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 功率谱密度估计方法
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum PsdMethod {
+    /// 不重叠的矩形窗分段，各段周期图取平均
+    Bartlett { segment_length: usize },
+    /// 加窗、重叠 overlap 个采样点的分段，各段修正周期图取平均
+    Welch {
+        segment_length: usize,
+        overlap: usize,
+        window: Window,
+    },
+    /// 整段信号分别乘以前 tapers 阶 DPSS（时间半带宽积 nw）后的周期图取平均
+    Multitaper { nw: f64, tapers: usize },
+}
+
+impl Default for PsdMethod {
+    fn default() -> Self {
+        PsdMethod::Welch {
+            segment_length: 4096,
+            overlap: 2048,
+            window: Window::Hann,
+        }
+    }
+}
+
+impl fmt::Display for PsdMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PsdMethod::Bartlett { segment_length } => write!(f, "bartlett(L={})", segment_length),
+            PsdMethod::Welch {
+                segment_length,
+                overlap,
+                window,
+            } => write!(f, "welch(L={}, overlap={}, {})", segment_length, overlap, window),
+            PsdMethod::Multitaper { nw, tapers } => write!(f, "multitaper(NW={}, K={})", nw, tapers),
+        }
+    }
+}
+
+/// 单边功率谱密度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerSpectralDensity {
+    pub method: PsdMethod,
+    pub sample_rate: f64,
+    /// 频率轴 (Hz)，0 到 Nyquist 频率
+    pub frequencies: Vec<f64>,
+    /// 单边功率谱密度 (V²/Hz)，∫ density df 等于信号的均方值
+    pub density: Vec<f64>,
+    /// 参与平均的分段数（多窗法为窗数）
+    pub segments: usize,
+    /// 估计量的等效自由度 ν：density·ν / S 服从 χ²(ν)
+    pub dof: f64,
+    /// 等效噪声带宽 (Hz)，即频率分辨率
+    pub enbw: f64,
+}
+
+impl PowerSpectralDensity {
+    /// 频点间隔 (Hz)
+    pub fn bin_width(&self) -> f64 {
+        if self.frequencies.len() > 1 {
+            self.frequencies[1] - self.frequencies[0]
+        } else {
+            self.sample_rate
+        }
+    }
+
+    /// 置信水平 level（如 0.95）下每个频点的 (下限, 上限) (V²/Hz)
+    ///
+    /// 按 χ²(ν) 分布：[ν·P / χ²_{ν, (1+level)/2}, ν·P / χ²_{ν, (1-level)/2}]。
+    /// 直流与 Nyquist 频点的实际自由度只有一半，区间偏窄。
+    pub fn confidence_interval(&self, level: f64) -> (Vec<f64>, Vec<f64>) {
+        let alpha = 1.0 - level.clamp(0.0, 1.0 - 1e-12);
+        let lower_factor = self.dof / chi_square_quantile(1.0 - alpha / 2.0, self.dof);
+        let upper_factor = self.dof / chi_square_quantile(alpha / 2.0, self.dof);
+        (
+            self.density.iter().map(|p| p * lower_factor).collect(),
+            self.density.iter().map(|p| p * upper_factor).collect(),
+        )
+    }
+
+    /// [f1, f2] 内的功率 (V²)
+    pub fn band_power(&self, f1: f64, f2: f64) -> f64 {
+        let df = self.bin_width();
+        self.frequencies
+            .iter()
+            .zip(&self.density)
+            .filter(|(&f, _)| f >= f1 && f <= f2)
+            .map(|(_, &p)| p * df)
+            .sum()
+    }
+
+    /// 总功率 (V²)，即信号的均方值
+    pub fn total_power(&self) -> f64 {
+        self.density.iter().sum::<f64>() * self.bin_width()
+    }
+
+    /// 幅度谱密度 √S (V/√Hz)；平方即为功率谱密度，
+    /// 可直接交给 [`crate::FrequencyEstimator::compute_energy_distribution`]
+    pub fn amplitude(&self) -> Vec<f64> {
+        self.density.iter().map(|p| p.sqrt()).collect()
+    }
+
+    /// 按 `FftResult` 的双边布局（频率 0 到 f_s）展开的幅度谱密度，供 f_d 估计器使用
+    ///
+    /// 返回 (频率轴, 幅度)，后一半为前一半的镜像
+    pub fn two_sided_amplitude(&self) -> (Vec<f64>, Vec<f64>) {
+        let bins = self.density.len();
+        let n = 2 * (bins - 1);
+        let df = self.bin_width();
+        let amplitude = self.amplitude();
+        let frequencies = (0..n).map(|k| k as f64 * df).collect();
+        let magnitude = (0..n).map(|k| amplitude[k.min(n - k)]).collect();
+        (frequencies, magnitude)
+    }
+
+    /// dB 刻度的功率谱密度 10·log10(S)，下限 -300 dB
+    pub fn density_db(&self) -> Vec<f64> {
+        self.density
+            .iter()
+            .map(|&p| if p > 1e-30 { 10.0 * p.log10() } else { -300.0 })
+            .collect()
+    }
+}
+
+/// 估计功率谱密度
+pub fn compute_psd(
+    samples: &[f64],
+    sample_rate: f64,
+    method: &PsdMethod,
+) -> Result<PowerSpectralDensity, String> {
+    match *method {
+        PsdMethod::Bartlett { segment_length } => {
+            let (density, segments, enbw) =
+                averaged_periodogram(samples, sample_rate, segment_length, 0, Window::Rectangular)?;
+            Ok(PowerSpectralDensity {
+                method: *method,
+                sample_rate,
+                frequencies: frequency_axis(segment_length, sample_rate),
+                density,
+                segments,
+                dof: 2.0 * segments as f64,
+                enbw,
+            })
+        }
+        PsdMethod::Welch {
+            segment_length,
+            overlap,
+            window,
+        } => {
+            let (density, segments, enbw) =
+                averaged_periodogram(samples, sample_rate, segment_length, overlap, window)?;
+            let coefficients = window.coefficients(segment_length);
+            Ok(PowerSpectralDensity {
+                method: *method,
+                sample_rate,
+                frequencies: frequency_axis(segment_length, sample_rate),
+                density,
+                segments,
+                dof: welch_dof(&coefficients, segment_length - overlap, segments),
+                enbw,
+            })
+        }
+        PsdMethod::Multitaper { nw, tapers } => {
+            let n = samples.len();
+            let sequences = dpss(n, nw, tapers)?;
+            let bins = n / 2 + 1;
+            let mut density = vec![0.0; bins];
+            for taper in &sequences {
+                let tapered: Vec<f64> = samples.iter().zip(taper).map(|(x, w)| x * w).collect();
+                accumulate_one_sided(&mut density, &compute_rfft(&tapered), n);
+            }
+            // 每阶 DPSS 的 Σw² = 1
+            let scale = 1.0 / (tapers as f64 * sample_rate);
+            density.iter_mut().for_each(|p| *p *= scale);
+            Ok(PowerSpectralDensity {
+                method: *method,
+                sample_rate,
+                frequencies: frequency_axis(n, sample_rate),
+                density,
+                segments: tapers,
+                dof: 2.0 * tapers as f64,
+                enbw: 2.0 * nw * sample_rate / n as f64,
+            })
+        }
+    }
+}
+
+/// 长度为 n 的 FFT 的单边频率轴
+fn frequency_axis(n: usize, sample_rate: f64) -> Vec<f64> {
+    (0..=n / 2).map(|k| k as f64 * sample_rate / n as f64).collect()
+}
+
+/// 把 |X(k)|² 按单边谱累加：除直流和 Nyquist 外的频点计入负频率的一半，乘以 2
+fn accumulate_one_sided(density: &mut [f64], spectrum: &[rustfft::num_complex::Complex<f64>], n: usize) {
+    for (k, (p, x)) in density.iter_mut().zip(spectrum).enumerate() {
+        let both_sides = k != 0 && !(n.is_multiple_of(2) && k == n / 2);
+        *p += x.norm_sqr() * if both_sides { 2.0 } else { 1.0 };
+    }
+}
+
+/// 分段加窗平均周期图，返回 (单边功率谱密度, 分段数, 等效噪声带宽)
+///
+/// 每段 S(k) = |Σ w[n]x[n]e^{-j2πkn/L}|² / (f_s·Σw²)，末尾不足一段的采样点丢弃
+fn averaged_periodogram(
+    samples: &[f64],
+    sample_rate: f64,
+    segment_length: usize,
+    overlap: usize,
+    window: Window,
+) -> Result<(Vec<f64>, usize, f64), String> {
+    if segment_length < 2 || overlap >= segment_length {
+        return Err(format!(
+            "分段长度 {} / 重叠 {} 无效：须 L ≥ 2 且重叠小于 L",
+            segment_length, overlap
+        ));
+    }
+    if samples.len() < segment_length {
+        return Err(format!(
+            "信号长度 {} 小于分段长度 {}",
+            samples.len(),
+            segment_length
+        ));
+    }
+    window.validate(segment_length)?;
+
+    let coefficients = window.coefficients(segment_length);
+    let gains = WindowGains::from_coefficients(&coefficients);
+    let power: f64 = coefficients.iter().map(|w| w * w).sum();
+    let step = segment_length - overlap;
+    let segments = (samples.len() - segment_length) / step + 1;
+
+    let mut density = vec![0.0; segment_length / 2 + 1];
+    for start in (0..segments).map(|s| s * step) {
+        let frame: Vec<f64> = samples[start..start + segment_length]
+            .iter()
+            .zip(&coefficients)
+            .map(|(x, w)| x * w)
+            .collect();
+        accumulate_one_sided(&mut density, &compute_rfft(&frame), segment_length);
+    }
+    let scale = 1.0 / (segments as f64 * sample_rate * power);
+    density.iter_mut().for_each(|p| *p *= scale);
+
+    Ok((density, segments, gains.enbw(sample_rate)))
+}
+
+/// Welch 估计的等效自由度（Welch 1967）
+///
+/// ν = 2K / (1 + 2·Σ_{l=1}^{K-1} (1 - l/K)·ρ²(l·D))，ρ(s) 为窗在错开 s 个采样点时的归一化相关
+fn welch_dof(window: &[f64], step: usize, segments: usize) -> f64 {
+    let power: f64 = window.iter().map(|w| w * w).sum();
+    let k = segments as f64;
+    let correlation: f64 = (1..segments)
+        .map(|l| l * step)
+        .take_while(|&shift| shift < window.len())
+        .enumerate()
+        .map(|(i, shift)| {
+            let rho = window.iter().zip(&window[shift..]).map(|(a, b)| a * b).sum::<f64>() / power;
+            (1.0 - (i + 1) as f64 / k) * rho * rho
+        })
+        .sum();
+    2.0 * k / (1.0 + 2.0 * correlation)
+}
+
+/// 标准正态分布的分位数（Acklam 有理逼近，相对误差约 1e-9）
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    let p_low = 0.02425;
+    if p < p_low {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - p_low {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// χ²(ν) 分布的分位数（Wilson–Hilferty 近似，ν ≥ 2 时相对误差在 1% 以内）
+fn chi_square_quantile(p: f64, nu: f64) -> f64 {
+    let h = 2.0 / (9.0 * nu);
+    let z = normal_quantile(p);
+    (nu * (1.0 - h + z * h.sqrt()).powi(3)).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    /// 均匀分布构造的零均值、方差为 variance 的确定性噪声
+    fn noise(n: usize, variance: f64) -> Vec<f64> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..n)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                let u = (state >> 11) as f64 / (1u64 << 53) as f64;
+                (u - 0.5) * (12.0 * variance).sqrt()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_white_noise_density_and_parseval() {
+        let (fs, variance) = (8000.0, 0.25);
+        let samples = noise(65536, variance);
+        let mean_square = samples.iter().map(|x| x * x).sum::<f64>() / samples.len() as f64;
+
+        let methods = [
+            PsdMethod::Bartlett { segment_length: 1024 },
+            PsdMethod::default(),
+            PsdMethod::Multitaper { nw: 4.0, tapers: 7 },
+        ];
+        for method in &methods {
+            let psd = compute_psd(&samples, fs, method).unwrap();
+            // 白噪声的单边谱密度为 2σ²/f_s
+            let expected = 2.0 * variance / fs;
+            let mid = &psd.density[psd.density.len() / 4..3 * psd.density.len() / 4];
+            let mean = mid.iter().sum::<f64>() / mid.len() as f64;
+            assert!((mean / expected - 1.0).abs() < 0.05, "{}: mean density {:e}", method, mean);
+            assert!((psd.total_power() / mean_square - 1.0).abs() < 0.02, "{}", method);
+        }
+    }
+
+    #[test]
+    fn test_degrees_of_freedom_and_confidence() {
+        let samples = noise(16384, 1.0);
+        let bartlett = compute_psd(&samples, 1.0, &PsdMethod::Bartlett { segment_length: 1024 }).unwrap();
+        assert_eq!(bartlett.segments, 16);
+        assert_eq!(bartlett.dof, 32.0);
+
+        // 50% 重叠的 Hann 窗：相邻段相关系数 ρ ≈ 1/6，只有相邻段相关
+        let welch = compute_psd(&samples, 1.0, &PsdMethod::default()).unwrap();
+        assert_eq!(welch.segments, 7);
+        let ratio = welch.dof / (2.0 * welch.segments as f64);
+        let expected = 1.0 / (1.0 + 2.0 * (6.0 / 7.0) / 36.0);
+        assert!((ratio - expected).abs() < 1e-3, "ratio = {}", ratio);
+
+        // 约 95% 的频点落在 95% 置信区间内（真值 2σ²/f_s = 2）
+        let (lower, upper) = bartlett.confidence_interval(0.95);
+        let inside = lower
+            .iter()
+            .zip(&upper)
+            .skip(1)
+            .filter(|(&lo, &hi)| lo <= 2.0 && 2.0 <= hi)
+            .count();
+        let coverage = inside as f64 / (lower.len() - 1) as f64;
+        assert!(coverage > 0.9 && coverage < 0.99, "coverage = {}", coverage);
+
+        assert!((chi_square_quantile(0.975, 10.0) - 20.483).abs() < 0.1);
+        assert!((chi_square_quantile(0.025, 10.0) - 3.247).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_tone_power_and_two_sided_layout() {
+        let fs = 22050.0;
+        let amplitude = 0.8;
+        let samples: Vec<f64> = (0..22050)
+            .map(|i| amplitude * (2.0 * PI * 3000.3 * i as f64 / fs).cos())
+            .collect();
+        for method in [PsdMethod::default(), PsdMethod::Multitaper { nw: 3.0, tapers: 5 }] {
+            let psd = compute_psd(&samples, fs, &method).unwrap();
+            // 正弦的功率 A²/2 集中在 f_0 附近几个 ENBW 内
+            let power = psd.band_power(3000.3 - 5.0 * psd.enbw, 3000.3 + 5.0 * psd.enbw);
+            assert!((power / (amplitude * amplitude / 2.0) - 1.0).abs() < 0.02, "{}: {}", method, power);
+        }
+
+        let psd = compute_psd(&samples, fs, &PsdMethod::default()).unwrap();
+        let (frequencies, magnitude) = psd.two_sided_amplitude();
+        assert_eq!(frequencies.len(), 4096);
+        assert_eq!(magnitude[1], magnitude[4095]);
+        assert_eq!(magnitude[2048], psd.density[2048].sqrt());
+
+        assert!(compute_psd(&samples[..100], fs, &PsdMethod::default()).is_err());
+    }
+}
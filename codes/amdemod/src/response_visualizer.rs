@@ -0,0 +1,607 @@
+use num_complex::Complex;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::f64::consts::PI;
+use crate::filter_response;
+use crate::group_delay::DelayResponse;
+use crate::stability::group_roots;
+use crate::zpk::Zpk;
+
+const PLOT_WIDTH: u32 = 1200;
+const PLOT_HEIGHT: u32 = 600;
+
+/// Plot magnitude response (linear scale)
+pub fn plot_magnitude_response(
+    frequencies: &[f64],
+    magnitude: &[f64],
+    output_path: &str,
+    title: &str,
+    max_freq: Option<f64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (PLOT_WIDTH, PLOT_HEIGHT)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let max_freq_val = max_freq.unwrap_or(*frequencies.last().unwrap_or(&10000.0));
+    let max_mag = magnitude.iter()
+        .zip(frequencies.iter())
+        .filter(|(_, &f)| f <= max_freq_val)
+        .map(|(&m, _)| m)
+        .fold(0.0, f64::max)
+        .max(1.1);
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 30).into_font())
+        .margin(15)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, 0.0..max_mag)?;
+
+    chart.configure_mesh()
+        .x_desc("Frequency (Hz)")
+        .y_desc("Magnitude")
+        .draw()?;
+
+    chart.draw_series(LineSeries::new(
+        frequencies.iter()
+            .zip(magnitude.iter())
+            .filter(|(&f, _)| f <= max_freq_val)
+            .map(|(&f, &m)| (f, m)),
+        &BLUE,
+    ))?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Plot magnitude response in dB scale
+pub fn plot_magnitude_response_db(
+    frequencies: &[f64],
+    magnitude: &[f64],
+    output_path: &str,
+    title: &str,
+    max_freq: Option<f64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (PLOT_WIDTH, PLOT_HEIGHT)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let max_freq_val = max_freq.unwrap_or(*frequencies.last().unwrap_or(&10000.0));
+    
+    let magnitude_db: Vec<f64> = magnitude.iter()
+        .map(|&m| filter_response::magnitude_to_db(m))
+        .collect();
+
+    let min_db = magnitude_db.iter()
+        .zip(frequencies.iter())
+        .filter(|(_, &f)| f <= max_freq_val)
+        .map(|(&db, _)| db)
+        .fold(f64::INFINITY, f64::min)
+        .max(-80.0);
+    
+    let max_db = 10.0;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 30).into_font())
+        .margin(15)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, min_db..max_db)?;
+
+    chart.configure_mesh()
+        .x_desc("Frequency (Hz)")
+        .y_desc("Magnitude (dB)")
+        .draw()?;
+
+    chart.draw_series(LineSeries::new(
+        frequencies.iter()
+            .zip(magnitude_db.iter())
+            .filter(|(&f, _)| f <= max_freq_val)
+            .map(|(&f, &db)| (f, db)),
+        &BLUE,
+    ))?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Plot phase response
+pub fn plot_phase_response(
+    frequencies: &[f64],
+    phase: &[f64],
+    output_path: &str,
+    title: &str,
+    max_freq: Option<f64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (PLOT_WIDTH, PLOT_HEIGHT)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let max_freq_val = max_freq.unwrap_or(*frequencies.last().unwrap_or(&10000.0));
+
+    let phase_deg: Vec<f64> = phase.iter()
+        .map(|&p| filter_response::phase_to_degrees(p))
+        .collect();
+
+    let min_phase = phase_deg.iter()
+        .zip(frequencies.iter())
+        .filter(|(_, &f)| f <= max_freq_val)
+        .map(|(&p, _)| p)
+        .fold(f64::INFINITY, f64::min) - 10.0;
+    
+    let max_phase = phase_deg.iter()
+        .zip(frequencies.iter())
+        .filter(|(_, &f)| f <= max_freq_val)
+        .map(|(&p, _)| p)
+        .fold(f64::NEG_INFINITY, f64::max) + 10.0;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 30).into_font())
+        .margin(15)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, min_phase..max_phase)?;
+
+    chart.configure_mesh()
+        .x_desc("Frequency (Hz)")
+        .y_desc("Phase (degrees)")
+        .draw()?;
+
+    chart.draw_series(LineSeries::new(
+        frequencies.iter()
+            .zip(phase_deg.iter())
+            .filter(|(&f, _)| f <= max_freq_val)
+            .map(|(&f, &p)| (f, p)),
+        &RED,
+    ))?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Plot combined magnitude responses of high-pass and low-pass filters
+pub fn plot_combined_magnitude(
+    frequencies: &[f64],
+    hp_magnitude: &[f64],
+    lp_magnitude: &[f64],
+    output_path: &str,
+    title: &str,
+    max_freq: Option<f64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (PLOT_WIDTH, PLOT_HEIGHT)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let max_freq_val = max_freq.unwrap_or(*frequencies.last().unwrap_or(&10000.0));
+    
+    let max_mag = hp_magnitude.iter()
+        .chain(lp_magnitude.iter())
+        .zip(frequencies.iter().cycle())
+        .filter(|(_, &f)| f <= max_freq_val)
+        .map(|(&m, _)| m)
+        .fold(0.0, f64::max)
+        .max(1.1);
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 30).into_font())
+        .margin(15)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, 0.0..max_mag)?;
+
+    chart.configure_mesh()
+        .x_desc("Frequency (Hz)")
+        .y_desc("Magnitude")
+        .draw()?;
+
+    // Draw high-pass filter
+    chart.draw_series(LineSeries::new(
+        frequencies.iter()
+            .zip(hp_magnitude.iter())
+            .filter(|(&f, _)| f <= max_freq_val)
+            .map(|(&f, &m)| (f, m)),
+        &BLUE,
+    ))?.label("High-pass")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    // Draw low-pass filter
+    chart.draw_series(LineSeries::new(
+        frequencies.iter()
+            .zip(lp_magnitude.iter())
+            .filter(|(&f, _)| f <= max_freq_val)
+            .map(|(&f, &m)| (f, m)),
+        &RED,
+    ))?.label("Low-pass")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart.configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Overlay magnitude responses (dB) of several filters, e.g. different filter families
+pub fn plot_overlaid_magnitude_db(
+    frequencies: &[f64],
+    responses: &[(String, Vec<f64>)],
+    output_path: &str,
+    title: &str,
+    max_freq: Option<f64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (PLOT_WIDTH, PLOT_HEIGHT)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let max_freq_val = max_freq.unwrap_or(*frequencies.last().unwrap_or(&10000.0));
+    let min_db = -100.0;
+    let max_db = 5.0;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 30).into_font())
+        .margin(15)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, min_db..max_db)?;
+
+    chart.configure_mesh()
+        .x_desc("Frequency (Hz)")
+        .y_desc("Magnitude (dB)")
+        .draw()?;
+
+    for (i, (label, magnitude)) in responses.iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        chart.draw_series(LineSeries::new(
+            frequencies.iter()
+                .zip(magnitude.iter())
+                .filter(|(&f, _)| f <= max_freq_val)
+                .map(|(&f, &m)| (f, filter_response::magnitude_to_db(m).clamp(min_db, max_db))),
+            color.stroke_width(2),
+        ))?.label(label.as_str())
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(3)));
+    }
+
+    chart.configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .position(SeriesLabelPosition::LowerRight)
+        .draw()?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Overlay the analog H(j2πf) and digital H(e^(jω)) magnitudes (dB) with their difference in a lower panel
+///
+/// Used to show the frequency warping of the bilinear transform on the full DFT grid
+pub fn plot_analog_digital_overlay(
+    frequencies: &[f64],
+    analog_magnitude: &[f64],
+    digital_magnitude: &[f64],
+    output_path: &str,
+    title: &str,
+    max_freq: Option<f64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (PLOT_WIDTH, PLOT_HEIGHT * 3 / 2)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let (upper, lower) = root.split_vertically(PLOT_HEIGHT);
+
+    let max_freq_val = max_freq.unwrap_or(*frequencies.last().unwrap_or(&10000.0));
+    let min_db = -100.0;
+    let max_db = 5.0;
+
+    let mut chart = ChartBuilder::on(&upper)
+        .caption(title, ("sans-serif", 30).into_font())
+        .margin(15)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, min_db..max_db)?;
+
+    chart.configure_mesh()
+        .x_desc("Frequency (Hz)")
+        .y_desc("Magnitude (dB)")
+        .draw()?;
+
+    for (label, magnitude, color) in [
+        ("Analog prototype", analog_magnitude, BLUE),
+        ("Digital (bilinear)", digital_magnitude, RED),
+    ] {
+        chart.draw_series(LineSeries::new(
+            frequencies.iter()
+                .zip(magnitude.iter())
+                .filter(|(&f, _)| f <= max_freq_val)
+                .map(|(&f, &m)| (f, filter_response::magnitude_to_db(m).clamp(min_db, max_db))),
+            color.stroke_width(2),
+        ))?.label(label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(3)));
+    }
+
+    chart.configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .position(SeriesLabelPosition::LowerRight)
+        .draw()?;
+
+    // Difference only where both responses are above the plotted floor, otherwise it is dominated by clamping
+    let error: Vec<(f64, f64)> = frequencies.iter()
+        .zip(analog_magnitude.iter().zip(digital_magnitude.iter()))
+        .filter(|(&f, (&a, &d))| {
+            f <= max_freq_val
+                && filter_response::magnitude_to_db(a) > min_db
+                && filter_response::magnitude_to_db(d) > min_db
+        })
+        .map(|(&f, (&a, &d))| (f, filter_response::magnitude_to_db(d) - filter_response::magnitude_to_db(a)))
+        .collect();
+    let limit = error.iter().map(|&(_, e)| e.abs()).fold(0.0, f64::max).clamp(0.1, 100.0) * 1.1;
+
+    let mut chart = ChartBuilder::on(&lower)
+        .caption("Bilinear Warping Error (digital - analog)", ("sans-serif", 24).into_font())
+        .margin(15)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, -limit..limit)?;
+
+    chart.configure_mesh()
+        .x_desc("Frequency (Hz)")
+        .y_desc("Error (dB)")
+        .draw()?;
+
+    chart.draw_series(
+        error.into_iter().map(|(f, e)| Circle::new((f, e.clamp(-limit, limit)), 1, BLACK.filled())),
+    )?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Plot magnitude (dB), unwrapped phase and group / phase delay (ms) in three stacked panels
+///
+/// `passband` marks the band used for the delay-variation summary with vertical lines
+pub fn plot_delay_response(
+    response: &filter_response::FilterResponse,
+    delay: &DelayResponse,
+    output_path: &str,
+    title: &str,
+    max_freq: Option<f64>,
+    passband: Option<(f64, f64)>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (PLOT_WIDTH, PLOT_HEIGHT * 2)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let root = root.titled(title, ("sans-serif", 30).into_font())?;
+    let panels = root.split_evenly((3, 1));
+
+    let max_freq_val = max_freq.unwrap_or(*delay.frequencies.last().unwrap_or(&10000.0));
+    let visible = |f: f64| f <= max_freq_val;
+    let markers: Vec<f64> = passband.map(|(low, high)| vec![low, high]).unwrap_or_default();
+
+    // Magnitude
+    let (min_db, max_db) = (-100.0, 5.0);
+    let mut chart = ChartBuilder::on(&panels[0])
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, min_db..max_db)?;
+    chart.configure_mesh().y_desc("Magnitude (dB)").draw()?;
+    chart.draw_series(LineSeries::new(
+        response.frequencies.iter()
+            .zip(response.magnitude.iter())
+            .filter(|(&f, _)| visible(f))
+            .map(|(&f, &m)| (f, filter_response::magnitude_to_db(m).clamp(min_db, max_db))),
+        BLUE.stroke_width(2),
+    ))?;
+    for &f in &markers {
+        chart.draw_series(LineSeries::new(vec![(f, min_db), (f, max_db)], MAGENTA.stroke_width(2)))?;
+    }
+
+    // Unwrapped phase
+    let phase: Vec<(f64, f64)> = delay.frequencies.iter()
+        .zip(delay.unwrapped_phase.iter())
+        .filter(|(&f, p)| visible(f) && p.is_finite())
+        .map(|(&f, &p)| (f, p))
+        .collect();
+    let (min_phase, max_phase) = value_range(phase.iter().map(|&(_, p)| p));
+    let mut chart = ChartBuilder::on(&panels[1])
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, min_phase..max_phase)?;
+    chart.configure_mesh().y_desc("Unwrapped Phase (rad)").draw()?;
+    chart.draw_series(LineSeries::new(phase, RED.stroke_width(2)))?;
+    for &f in &markers {
+        chart.draw_series(LineSeries::new(vec![(f, min_phase), (f, max_phase)], MAGENTA.stroke_width(2)))?;
+    }
+
+    // Group and phase delay; the axis covers the group delay and the phase delay inside the passband,
+    // since the phase delay of a high-pass diverges towards DC
+    let to_ms = |values: &[f64]| -> Vec<(f64, f64)> {
+        delay.frequencies.iter()
+            .zip(values.iter())
+            .filter(|(&f, v)| visible(f) && v.is_finite())
+            .map(|(&f, &v)| (f, v * 1000.0))
+            .collect()
+    };
+    let group = to_ms(&delay.group_delay);
+    let phase_delay = to_ms(&delay.phase_delay);
+    let in_band = |f: f64| passband.is_none_or(|(low, high)| f >= low && f <= high);
+    let (min_delay, max_delay) = value_range(
+        group.iter()
+            .map(|&(_, d)| d)
+            .chain(phase_delay.iter().filter(|&&(f, _)| in_band(f)).map(|&(_, d)| d))
+            .chain(std::iter::once(0.0)),
+    );
+    let mut chart = ChartBuilder::on(&panels[2])
+        .margin(15)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0..max_freq_val, min_delay..max_delay)?;
+    chart.configure_mesh()
+        .x_desc("Frequency (Hz)")
+        .y_desc("Delay (ms)")
+        .draw()?;
+    chart.draw_series(LineSeries::new(group, BLUE.stroke_width(2)))?
+        .label("Group delay")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE.stroke_width(3)));
+    chart.draw_series(LineSeries::new(
+        phase_delay.into_iter().map(|(f, d)| (f, d.clamp(min_delay, max_delay))),
+        GREEN.stroke_width(2),
+    ))?
+        .label("Phase delay")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN.stroke_width(3)));
+    for &f in &markers {
+        chart.draw_series(LineSeries::new(vec![(f, min_delay), (f, max_delay)], MAGENTA.stroke_width(2)))?;
+    }
+    chart.configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .position(SeriesLabelPosition::UpperRight)
+        .draw()?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Axis range covering all values with a 5% margin
+fn value_range(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    if !min.is_finite() || !max.is_finite() {
+        return (-1.0, 1.0);
+    }
+    let margin = ((max - min) * 0.05).max(1e-9);
+    (min - margin, max + margin)
+}
+
+/// Relative tolerance for drawing coincident roots as one marker with a multiplicity
+const ROOT_GROUP_TOL: f64 = 1e-6;
+
+/// Plot the z-plane pole-zero diagram of a digital filter
+///
+/// The left panel shows the whole plane with the unit circle; the right panel zooms onto the poles
+/// in the upper half plane together with the nearest arc of the unit circle and nearby zeros
+pub fn plot_pole_zero_z(zpk: &Zpk, output_path: &str, title: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (PLOT_WIDTH, PLOT_HEIGHT)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let root = root.titled(title, ("sans-serif", 30).into_font())?;
+    let panels = root.split_evenly((1, 2));
+
+    let zeros = group_roots(&zpk.zeros, ROOT_GROUP_TOL);
+    let poles = group_roots(&zpk.poles, ROOT_GROUP_TOL);
+    let extent = zpk.zeros.iter().chain(&zpk.poles).map(|r| r.norm()).fold(1.2f64, |m, r| m.max(1.1 * r));
+    draw_pole_zero_panel(&panels[0], "z-plane", square(0.0, 0.0, extent), &zeros, &poles, true)?;
+
+    // Zoom: bounding box of the upper-half poles, the unit-circle point closest to the outermost pole
+    // and any upper-half zeros right next to it (the high-pass zeros at z = 1 for a low cutoff)
+    let outermost = zpk.poles.iter().copied().max_by(|a, b| a.norm().total_cmp(&b.norm()));
+    if let Some(outermost) = outermost {
+        let edge = Complex::from_polar(1.0, outermost.arg().abs());
+        let nearby_zeros = zpk.zeros.iter().filter(|z| z.im >= 0.0 && (*z - edge).norm() < 0.25);
+        let upper: Vec<Complex<f64>> = zpk.poles.iter()
+            .filter(|p| p.im >= 0.0)
+            .chain(nearby_zeros)
+            .copied()
+            .chain([edge])
+            .collect();
+        let (re_min, re_max) = upper.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.re), hi.max(p.re)));
+        let (im_min, im_max) = upper.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.im), hi.max(p.im)));
+        let half = ((re_max - re_min).max(im_max - im_min) * 0.6).max(1e-3);
+        draw_pole_zero_panel(
+            &panels[1],
+            &format!("Zoom near the unit circle (max |p| = {:.6})", outermost.norm()),
+            square((re_min + re_max) / 2.0, (im_min + im_max) / 2.0, half),
+            &zeros,
+            &poles,
+            true,
+        )?;
+    }
+
+    root.present()?;
+    Ok(())
+}
+
+/// Plot the s-plane pole-zero diagram of an analog filter, with both axes in Hz (s / 2π)
+pub fn plot_pole_zero_s(zpk: &Zpk, output_path: &str, title: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (PLOT_HEIGHT + 100, PLOT_HEIGHT + 50)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let root = root.titled(title, ("sans-serif", 30).into_font())?;
+
+    let to_hz = |roots: &[Complex<f64>]| -> Vec<Complex<f64>> { roots.iter().map(|r| r / (2.0 * PI)).collect() };
+    let (zeros, poles) = (to_hz(&zpk.zeros), to_hz(&zpk.poles));
+    let extent = zeros.iter().chain(&poles).map(|r| r.norm()).fold(1.0f64, |m, r| m.max(1.2 * r));
+    draw_pole_zero_panel(
+        &root,
+        "s-plane (Hz)",
+        square(0.0, 0.0, extent),
+        &group_roots(&zeros, ROOT_GROUP_TOL),
+        &group_roots(&poles, ROOT_GROUP_TOL),
+        false,
+    )?;
+
+    root.present()?;
+    Ok(())
+}
+
+/// Square axis ranges centred on (x, y)
+fn square(x: f64, y: f64, half: f64) -> ((f64, f64), (f64, f64)) {
+    ((x - half, x + half), (y - half, y + half))
+}
+
+/// One pole-zero panel: zeros as circles, poles as crosses, the multiplicity next to repeated roots,
+/// the axes through the origin and (for the z-plane) the unit circle
+fn draw_pole_zero_panel(
+    area: &DrawingArea<BitMapBackend<'_>, Shift>,
+    caption: &str,
+    ((x_min, x_max), (y_min, y_max)): ((f64, f64), (f64, f64)),
+    zeros: &[(Complex<f64>, usize)],
+    poles: &[(Complex<f64>, usize)],
+    unit_circle: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut chart = ChartBuilder::on(area)
+        .caption(caption, ("sans-serif", 20).into_font())
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(x_min..x_max, y_min..y_max)?;
+    chart.configure_mesh()
+        .x_desc("Real")
+        .y_desc("Imaginary")
+        .draw()?;
+
+    let inside = |x: f64, y: f64| x >= x_min && x <= x_max && y >= y_min && y <= y_max;
+    if x_min <= 0.0 && x_max >= 0.0 {
+        chart.draw_series(LineSeries::new(vec![(0.0, y_min), (0.0, y_max)], BLACK.mix(0.6)))?;
+    }
+    if y_min <= 0.0 && y_max >= 0.0 {
+        chart.draw_series(LineSeries::new(vec![(x_min, 0.0), (x_max, 0.0)], BLACK.mix(0.6)))?;
+    }
+    if unit_circle {
+        // Split the circle wherever it leaves the window so the zoomed panel is not crossed by chords
+        let points = (0..=4096).map(|k| Complex::from_polar(1.0, 2.0 * PI * k as f64 / 4096.0));
+        let mut arc: Vec<(f64, f64)> = Vec::new();
+        for p in points.chain([Complex::new(f64::NAN, f64::NAN)]) {
+            if inside(p.re, p.im) {
+                arc.push((p.re, p.im));
+            } else if !arc.is_empty() {
+                chart.draw_series(LineSeries::new(std::mem::take(&mut arc), GREEN.stroke_width(2)))?;
+            }
+        }
+    }
+
+    let visible = |roots: &[(Complex<f64>, usize)]| -> Vec<(Complex<f64>, usize)> {
+        roots.iter().filter(|(r, _)| inside(r.re, r.im)).copied().collect()
+    };
+    let (zeros, poles) = (visible(zeros), visible(poles));
+    chart.draw_series(zeros.iter().map(|(z, _)| Circle::new((z.re, z.im), 7, BLUE.stroke_width(2))))?
+        .label("Zeros")
+        .legend(|(x, y)| Circle::new((x + 10, y), 6, BLUE.stroke_width(2)));
+    chart.draw_series(poles.iter().map(|(p, _)| Cross::new((p.re, p.im), 7, RED.stroke_width(2))))?
+        .label("Poles")
+        .legend(|(x, y)| Cross::new((x + 10, y), 6, RED.stroke_width(2)));
+    for &(r, count) in zeros.iter().chain(&poles).filter(|(_, count)| *count > 1) {
+        chart.draw_series(std::iter::once(
+            EmptyElement::at((r.re, r.im)) + Text::new(format!("{}", count), (8, -20), ("sans-serif", 16).into_font()),
+        ))?;
+    }
+
+    chart.configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .position(SeriesLabelPosition::UpperLeft)
+        .draw()?;
+    Ok(())
+}
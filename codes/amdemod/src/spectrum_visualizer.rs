@@ -0,0 +1,874 @@
+// 3. 频谱可视化模块
+// 使用 plotters 库绘制频谱图
+
+use crate::carrier_recovery::CarrierRecovery;
+use crate::fft_processor::{compute_fft, FftResult, ZoomSpectrum};
+use crate::offset_tracker::OffsetTrack;
+use crate::psd::PowerSpectralDensity;
+use crate::stft::Spectrogram;
+use crate::sub_bin::BenchmarkRow;
+use crate::window::Window;
+use plotters::prelude::*;
+use plotters::style::colors::colormaps::ViridisRGB;
+use std::path::Path;
+
+/// 频谱可视化器
+pub struct SpectrumVisualizer;
+
+impl SpectrumVisualizer {
+    /// 绘制频谱图（幅度谱）
+    pub fn plot_spectrum<P: AsRef<Path>>(
+        frequencies: &[f64],
+        magnitude: &[f64],
+        output_path: P,
+        title: &str,
+        max_freq: Option<f64>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // 只显示到指定频率或 Nyquist 频率
+        let nyquist = frequencies.last().copied().unwrap_or(0.0) / 2.0;
+        let max_f = max_freq.unwrap_or(nyquist);
+        
+        // 过滤数据点
+        let data: Vec<(f64, f64)> = frequencies
+            .iter()
+            .zip(magnitude.iter())
+            .filter(|(&f, _)| f <= max_f)
+            .map(|(&f, &m)| (f, m))
+            .collect();
+
+        if data.is_empty() {
+            return Err("没有数据可以绘制".into());
+        }
+
+        // 找出幅度范围
+        let max_magnitude = data.iter().map(|(_, m)| m).fold(0.0f64, |a, &b| a.max(b));
+        let y_max = max_magnitude * 1.1;
+
+        // 创建绘图区域 - 使用文件路径
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 600))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("Arial", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(0.0..max_f, 0.0..y_max)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Frequency (Hz)")
+            .y_desc("Magnitude")
+            .draw()?;
+
+        // 绘制频谱曲线
+        chart.draw_series(LineSeries::new(
+            data.iter().map(|&(f, m)| (f, m)),
+            &BLUE,
+        ))?;
+
+        root.present()?;
+        println!("频谱图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制 FFT 结果的单边幅度谱（0 到 Nyquist 频率）
+    pub fn plot_fft_result<P: AsRef<Path>>(
+        result: &FftResult,
+        output_path: P,
+        title: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Self::plot_spectrum(
+            &result.frequencies,
+            &result.magnitude,
+            output_path,
+            title,
+            Some(result.sample_rate / 2.0),
+        )
+    }
+
+    /// 绘制细化频谱：CZT 曲线，叠加同一频段内的 DFT 频点（圆点）和标注频率（竖虚线，如 f_d）
+    pub fn plot_zoom_spectrum<P: AsRef<Path>>(
+        zoom: &ZoomSpectrum,
+        dft: Option<&FftResult>,
+        markers: &[(f64, &str)],
+        output_path: P,
+        title: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (Some(&f1), Some(&f2)) = (zoom.frequencies.first(), zoom.frequencies.last()) else {
+            return Err("没有数据可以绘制".into());
+        };
+        let bins: Vec<(f64, f64)> = dft
+            .map(|dft| {
+                dft.get_single_sided_pairs()
+                    .into_iter()
+                    .filter(|&(f, _)| f >= f1 && f <= f2)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let max_magnitude = zoom
+            .magnitude
+            .iter()
+            .chain(bins.iter().map(|(_, m)| m))
+            .fold(0.0f64, |a, &b| a.max(b));
+        let y_max = if max_magnitude > 0.0 { max_magnitude * 1.1 } else { 1.0 };
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 600))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("Arial", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(f1..f2, 0.0..y_max)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Frequency (Hz)")
+            .y_desc("Magnitude")
+            .y_label_formatter(&|m| format!("{:.1e}", m))
+            .draw()?;
+
+        chart
+            .draw_series(LineSeries::new(
+                zoom.frequencies.iter().copied().zip(zoom.magnitude.iter().copied()),
+                &BLUE,
+            ))?
+            .label(format!("Zoom spectrum ({:.4} Hz step)", zoom.resolution()))
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE.stroke_width(3)));
+
+        if let Some(dft) = dft {
+            chart
+                .draw_series(bins.iter().map(|&p| Circle::new(p, 4, RED.filled())))?
+                .label(format!("DFT bins ({:.4} Hz step)", dft.sample_rate / dft.num_samples as f64))
+                .legend(|(x, y)| Circle::new((x + 10, y), 4, RED.filled()));
+        }
+
+        for &(frequency, label) in markers.iter().filter(|(f, _)| *f >= f1 && *f <= f2) {
+            chart
+                .draw_series(DashedLineSeries::new(
+                    [(frequency, 0.0), (frequency, y_max)],
+                    8,
+                    4,
+                    BLACK.into(),
+                ))?
+                .label(format!("{} = {:.4} Hz", label, frequency))
+                .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK));
+        }
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        root.present()?;
+        println!("细化频谱图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制窗函数的时域形状（左）与归一化幅频响应（右，±16 个频点内，16 倍补零）
+    pub fn plot_windows<P: AsRef<Path>>(
+        windows: &[(Window, Vec<f64>)],
+        output_path: P,
+        title: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        const PAD: usize = 16;
+        const SPAN_BINS: f64 = 16.0;
+        if windows.is_empty() {
+            return Err("没有数据可以绘制".into());
+        }
+        if let Some((window, w)) = windows.iter().find(|(_, w)| w.len() < 2) {
+            return Err(format!("窗 {} 只有 {} 个点，至少需要 2 个", window, w.len()).into());
+        }
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1600, 700))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+        let root = root.titled(title, ("Arial", 30).into_font())?;
+        let (left, right) = root.split_horizontally(700);
+
+        let mut shape_chart = ChartBuilder::on(&left)
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(0.0..1.0, -0.1..1.05)?;
+        shape_chart
+            .configure_mesh()
+            .x_desc("n / N")
+            .y_desc("w[n]")
+            .draw()?;
+
+        let mut response_chart = ChartBuilder::on(&right)
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(-SPAN_BINS..SPAN_BINS, -160.0..5.0)?;
+        response_chart
+            .configure_mesh()
+            .x_desc("Frequency (bins)")
+            .y_desc("Magnitude (dB)")
+            .draw()?;
+
+        for (i, (window, w)) in windows.iter().enumerate() {
+            let color = Palette99::pick(i).to_rgba();
+            let n = w.len() as f64;
+            shape_chart
+                .draw_series(LineSeries::new(
+                    w.iter().enumerate().map(|(k, &x)| (k as f64 / n, x)),
+                    color.stroke_width(2),
+                ))?
+                .label(window.to_string())
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(3)));
+
+            let mut padded = w.clone();
+            padded.resize(w.len() * PAD, 0.0);
+            let spectrum = compute_fft(&padded);
+            let dc = spectrum[0].norm();
+            let m = spectrum.len();
+            // 短窗的补零长度不足 ±SPAN_BINS 时只画半个周期
+            let half_span = ((SPAN_BINS * PAD as f64) as usize).min(m / 2);
+            let to_db = |k: usize| {
+                let v = spectrum[k].norm() / dc;
+                if v > 1e-8 { 20.0 * v.log10() } else { -160.0 }
+            };
+            let points: Vec<(f64, f64)> = (m - half_span..m)
+                .map(|k| ((k as f64 - m as f64) / PAD as f64, to_db(k)))
+                .chain((0..=half_span).map(|k| (k as f64 / PAD as f64, to_db(k))))
+                .collect();
+            response_chart
+                .draw_series(LineSeries::new(points, color.stroke_width(1)))?
+                .label(window.to_string())
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(3)));
+        }
+
+        response_chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .position(SeriesLabelPosition::LowerRight)
+            .draw()?;
+
+        root.present()?;
+        println!("窗函数图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制功率谱密度（dB 刻度，10·log10 V²/Hz），可叠加置信区间
+    pub fn plot_psd<P: AsRef<Path>>(
+        psd: &PowerSpectralDensity,
+        confidence: Option<f64>,
+        output_path: P,
+        title: &str,
+        max_freq: Option<f64>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let max_f = max_freq.unwrap_or(psd.sample_rate / 2.0);
+        let to_db = |p: f64| if p > 1e-30 { 10.0 * p.log10() } else { -300.0 };
+        let visible = |f: &f64| *f <= max_f;
+
+        let data: Vec<(f64, f64)> = psd
+            .frequencies
+            .iter()
+            .zip(&psd.density)
+            .filter(|(f, _)| visible(f))
+            .map(|(&f, &p)| (f, to_db(p)))
+            .collect();
+        if data.is_empty() {
+            return Err("没有数据可以绘制".into());
+        }
+        let band = confidence.map(|level| (level, psd.confidence_interval(level)));
+
+        let max_db = band
+            .as_ref()
+            .map_or(&psd.density, |(_, (_, upper))| upper)
+            .iter()
+            .zip(&psd.frequencies)
+            .filter(|(_, f)| visible(f))
+            .map(|(&p, _)| to_db(p))
+            .fold(-300.0f64, f64::max);
+        let min_db = max_db - 100.0;
+        let clip = |db: f64| db.max(min_db);
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 600))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("Arial", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(0.0..max_f, min_db..max_db + 5.0)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Frequency (Hz)")
+            .y_desc("PSD (dB re 1 V²/Hz)")
+            .draw()?;
+
+        if let Some((level, (lower, upper))) = &band {
+            let points = |values: &[f64]| -> Vec<(f64, f64)> {
+                psd.frequencies
+                    .iter()
+                    .zip(values)
+                    .filter(|(f, _)| visible(f))
+                    .map(|(&f, &p)| (f, clip(to_db(p))))
+                    .collect()
+            };
+            let mut outline = points(upper);
+            outline.extend(points(lower).into_iter().rev());
+            chart
+                .draw_series(std::iter::once(Polygon::new(outline, BLUE.mix(0.2).filled())))?
+                .label(format!("{:.0}% confidence interval (ν = {:.1})", level * 100.0, psd.dof))
+                .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], BLUE.mix(0.2).filled()));
+        }
+
+        chart
+            .draw_series(LineSeries::new(data.into_iter().map(|(f, db)| (f, clip(db))), &BLUE))?
+            .label(format!("{} (ENBW {:.2} Hz)", psd.method, psd.enbw))
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE.stroke_width(3)));
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        root.present()?;
+        println!("功率谱密度图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制时频谱图（dB 刻度，viridis 配色）
+    ///
+    /// 颜色范围为 [最大值 - dynamic_range_db, 最大值]，右侧附色标
+    pub fn plot_spectrogram<P: AsRef<Path>>(
+        spectrogram: &Spectrogram,
+        output_path: P,
+        title: &str,
+        max_freq: Option<f64>,
+        dynamic_range_db: f64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (Some(&t_last), Some(&f_last)) = (spectrogram.times.last(), spectrogram.frequencies.last()) else {
+            return Err("没有数据可以绘制".into());
+        };
+        let max_f = max_freq.unwrap_or(f_last).min(f_last);
+        let hop = spectrogram.config.hop as f64 / spectrogram.sample_rate;
+        let df = spectrogram.sample_rate / spectrogram.config.fft_size as f64;
+        let t_min = spectrogram.times[0] - hop / 2.0;
+        let t_max = t_last + hop / 2.0;
+
+        let db = spectrogram.magnitude_db();
+        let max_db = db.iter().flatten().fold(-200.0f64, |a, &b| a.max(b));
+        let min_db = max_db - dynamic_range_db;
+        let color = |v: f64| ViridisRGB::get_color_normalized(v.clamp(min_db, max_db), min_db, max_db);
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 600))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+        let (main, bar) = root.split_horizontally(1080);
+
+        let mut chart = ChartBuilder::on(&main)
+            .caption(title, ("Arial", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(t_min..t_max, 0.0..max_f)?;
+
+        chart
+            .configure_mesh()
+            .disable_mesh()
+            .x_desc("Time (s)")
+            .y_desc("Frequency (Hz)")
+            .y_label_formatter(&|f| format!("{:.0}", f))
+            .draw()?;
+
+        chart.draw_series(spectrogram.times.iter().zip(&db).flat_map(|(&t, frame)| {
+            spectrogram
+                .frequencies
+                .iter()
+                .zip(frame)
+                .filter(|(&f, _)| f <= max_f)
+                .map(move |(&f, &v)| {
+                    Rectangle::new(
+                        [(t - hop / 2.0, (f - df / 2.0).max(0.0)), (t + hop / 2.0, (f + df / 2.0).min(max_f))],
+                        color(v).filled(),
+                    )
+                })
+        }))?;
+
+        let mut colorbar = ChartBuilder::on(&bar)
+            .margin_top(50)
+            .margin_bottom(50)
+            .margin_right(10)
+            .y_label_area_size(70)
+            .build_cartesian_2d(0.0..1.0, min_db..max_db)?;
+
+        colorbar
+            .configure_mesh()
+            .disable_mesh()
+            .disable_x_axis()
+            .y_desc("Magnitude (dB)")
+            .y_label_formatter(&|v| format!("{:.0}", v))
+            .label_style(("Arial", 14))
+            .axis_desc_style(("Arial", 14))
+            .draw()?;
+
+        let steps = 100;
+        let step = dynamic_range_db / steps as f64;
+        colorbar.draw_series((0..steps).map(|i| {
+            let v = min_db + i as f64 * step;
+            Rectangle::new([(0.0, v), (1.0, v + step)], color(v + step / 2.0).filled())
+        }))?;
+
+        root.present()?;
+        println!("时频谱图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制频谱图（dB 刻度）
+    pub fn plot_spectrum_db<P: AsRef<Path>>(
+        frequencies: &[f64],
+        magnitude_db: &[f64],
+        output_path: P,
+        title: &str,
+        max_freq: Option<f64>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let nyquist = frequencies.last().copied().unwrap_or(0.0) / 2.0;
+        let max_f = max_freq.unwrap_or(nyquist);
+        
+        let data: Vec<(f64, f64)> = frequencies
+            .iter()
+            .zip(magnitude_db.iter())
+            .filter(|(&f, _)| f <= max_f)
+            .map(|(&f, &m)| (f, m))
+            .collect();
+
+        if data.is_empty() {
+            return Err("没有数据可以绘制".into());
+        }
+
+        let max_db = data.iter().map(|(_, m)| m).fold(-200.0f64, |a, &b| a.max(b));
+        let min_db = -100.0;
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 600))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("Arial", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(0.0..max_f, min_db..max_db)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Frequency (Hz)")
+            .y_desc("Magnitude (dB)")
+            .draw()?;
+
+        chart.draw_series(LineSeries::new(
+            data.iter().map(|&(f, m)| (f, m)),
+            &RED,
+        ))?;
+
+        root.present()?;
+        println!("频谱图（dB）已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制时域波形
+    pub fn plot_waveform<P: AsRef<Path>>(
+        samples: &[f64],
+        sample_rate: f64,
+        output_path: P,
+        title: &str,
+        max_samples: Option<usize>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // 限制显示的采样点数量
+        let n = max_samples.unwrap_or(samples.len()).min(samples.len());
+        let time: Vec<f64> = (0..n)
+            .map(|i| i as f64 / sample_rate)
+            .collect();
+        
+        let data: Vec<(f64, f64)> = time
+            .iter()
+            .zip(samples.iter())
+            .take(n)
+            .map(|(&t, &s)| (t, s))
+            .collect();
+
+        let max_amplitude = samples.iter().map(|&x| x.abs()).fold(0.0f64, f64::max);
+        let y_range = max_amplitude * 1.2;
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 600))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("Arial", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(0.0..time[n-1], -y_range..y_range)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Time (seconds)")
+            .y_desc("Amplitude")
+            .draw()?;
+
+        chart.draw_series(LineSeries::new(
+            data.iter().map(|&(t, s)| (t, s)),
+            &GREEN,
+        ))?;
+
+        root.present()?;
+        println!("时域波形图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制 f_d(t) 跟踪轨迹：逐帧估计值（点）、平滑后的轨迹（线）和标称 f_d（虚线）
+    pub fn plot_offset_track<P: AsRef<Path>>(
+        track: &OffsetTrack,
+        output_path: P,
+        title: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if track.frames.is_empty() {
+            return Err("没有数据可以绘制".into());
+        }
+
+        let t_max = track.frames.last().map_or(0.0, |f| f.time) + track.frames[0].time;
+        let values = track
+            .frames
+            .iter()
+            .flat_map(|f| f.raw.into_iter().chain([f.f_d]))
+            .chain([track.nominal]);
+        let (lo, hi) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        let margin = ((hi - lo) * 0.1).max(1.0);
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 600))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("Arial", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(80)
+            .build_cartesian_2d(0.0..t_max, (lo - margin)..(hi + margin))?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Time (seconds)")
+            .y_desc("Frequency offset f_d (Hz)")
+            .draw()?;
+
+        chart
+            .draw_series(DashedLineSeries::new(
+                [(0.0, track.nominal), (t_max, track.nominal)],
+                10,
+                5,
+                BLACK.into(),
+            ))?
+            .label("Nominal f_d")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK));
+
+        chart
+            .draw_series(
+                track
+                    .frames
+                    .iter()
+                    .filter_map(|f| f.raw.map(|raw| Circle::new((f.time, raw), 3, RED.filled()))),
+            )?
+            .label("Per-frame estimate")
+            .legend(|(x, y)| Circle::new((x + 10, y), 3, RED.filled()));
+
+        chart
+            .draw_series(LineSeries::new(
+                track.frames.iter().map(|f| (f.time, f.f_d)),
+                BLUE.stroke_width(2),
+            ))?
+            .label("Tracked f_d(t)")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE.stroke_width(2)));
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        root.present()?;
+        println!("f_d 跟踪轨迹图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制载波恢复环路的轨迹：NCO 频率、相位误差、锁定指示器，上下三幅子图共用时间轴
+    pub fn plot_carrier_recovery<P: AsRef<Path>>(
+        recovery: &CarrierRecovery,
+        output_path: P,
+        title: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let n = recovery.frequency.len();
+        if n < 2 {
+            return Err("没有数据可以绘制".into());
+        }
+        // 每条曲线最多绘制约 4000 个点
+        let step = n.div_ceil(4000);
+        let t_max = (n - 1) as f64 / recovery.sample_rate;
+        let series = |values: &[f64]| -> Vec<(f64, f64)> {
+            values
+                .iter()
+                .enumerate()
+                .step_by(step)
+                .map(|(i, &v)| (i as f64 / recovery.sample_rate, v))
+                .collect()
+        };
+        let range = |data: &[(f64, f64)]| {
+            let (lo, hi) = data
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, v)| (lo.min(v), hi.max(v)));
+            let margin = ((hi - lo) * 0.1).max(1e-3);
+            (lo - margin)..(hi + margin)
+        };
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 900))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+        let root = root.titled(title, ("Arial", 30).into_font())?;
+        let panels = root.split_evenly((3, 1));
+
+        let frequency = series(&recovery.frequency);
+        let phase_error = series(&recovery.phase_error);
+        let lock = series(&recovery.lock);
+        let traces = [
+            (&frequency, range(&frequency), "NCO frequency (Hz)", BLUE, false),
+            (&phase_error, range(&phase_error), "Phase error (rad)", RED, false),
+            (&lock, -1.05..1.05, "Lock indicator", GREEN, true),
+        ];
+        for (panel, (data, y_range, label, color, is_lock)) in panels.iter().zip(traces) {
+            let (y_lo, y_hi) = (y_range.start, y_range.end);
+            let mut chart = ChartBuilder::on(panel)
+                .margin(10)
+                .x_label_area_size(35)
+                .y_label_area_size(80)
+                .build_cartesian_2d(0.0..t_max, y_range)?;
+            chart
+                .configure_mesh()
+                .x_desc("Time (seconds)")
+                .y_desc(label)
+                .draw()?;
+            chart.draw_series(LineSeries::new(data.iter().copied(), &color))?;
+
+            // 锁定时刻（竖线），锁定指示器子图另画出阈值（横线）
+            if let Some(lock_time) = recovery.lock_time {
+                chart.draw_series(DashedLineSeries::new(
+                    [(lock_time, y_lo), (lock_time, y_hi)],
+                    8,
+                    4,
+                    BLACK.into(),
+                ))?;
+            }
+            if is_lock {
+                let threshold = recovery.config.lock_threshold;
+                chart.draw_series(DashedLineSeries::new(
+                    [(0.0, threshold), (t_max, threshold)],
+                    8,
+                    4,
+                    BLACK.mix(0.5).into(),
+                ))?;
+            }
+        }
+
+        root.present()?;
+        println!("载波恢复轨迹图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制亚频点估计方法的均方根误差随信噪比的变化（对数纵轴），虚线为 Cramér–Rao 下界
+    pub fn plot_estimator_benchmark<P: AsRef<Path>>(
+        rows: &[BenchmarkRow],
+        output_path: P,
+        title: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if rows.is_empty() {
+            return Err("没有数据可以绘制".into());
+        }
+
+        let (snr_lo, snr_hi) = rows
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), r| (lo.min(r.snr_db), hi.max(r.snr_db)));
+        let (err_lo, err_hi) = rows
+            .iter()
+            .flat_map(|r| [r.rms, r.crb])
+            .filter(|&v| v > 0.0)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        let snr_hi = if snr_hi > snr_lo { snr_hi } else { snr_lo + 1.0 };
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 600))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("Arial", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(80)
+            .build_cartesian_2d(snr_lo..snr_hi, (err_lo * 0.5..err_hi * 2.0).log_scale())?;
+
+        chart
+            .configure_mesh()
+            .x_desc("SNR (dB)")
+            .y_desc("RMS error (Hz)")
+            .draw()?;
+
+        // 按首次出现的顺序列出方法
+        let mut methods = Vec::new();
+        for row in rows {
+            if !methods.contains(&row.method) {
+                methods.push(row.method);
+            }
+        }
+
+        let colors = [&BLUE, &RED, &GREEN, &CYAN, &MAGENTA, &BLACK];
+        for (idx, method) in methods.iter().enumerate() {
+            let color = colors[idx % colors.len()];
+            let data: Vec<(f64, f64)> = rows
+                .iter()
+                .filter(|r| r.method == *method)
+                .map(|r| (r.snr_db, r.rms))
+                .collect();
+            chart
+                .draw_series(LineSeries::new(data.iter().copied(), color))?
+                .label(method.to_string())
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(3)));
+            chart.draw_series(data.iter().map(|&p| Circle::new(p, 3, color.filled())))?;
+        }
+
+        let crb: Vec<(f64, f64)> = rows
+            .iter()
+            .filter(|r| r.method == methods[0])
+            .map(|r| (r.snr_db, r.crb))
+            .collect();
+        chart
+            .draw_series(DashedLineSeries::new(crb, 10, 5, BLACK.mix(0.6).into()))?
+            .label("Cramer-Rao bound")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK.mix(0.6)));
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        root.present()?;
+        println!("估计误差对比图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+
+    /// 绘制多个频谱对比图
+    pub fn plot_spectrum_comparison<P: AsRef<Path>>(
+        datasets: Vec<(&[f64], &[f64], &str)>, // (frequencies, magnitude, label)
+        output_path: P,
+        title: &str,
+        max_freq: Option<f64>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if datasets.is_empty() {
+            return Err("没有数据可以绘制".into());
+        }
+
+        let nyquist = datasets[0].0.last().copied().unwrap_or(0.0) / 2.0;
+        let max_f = max_freq.unwrap_or(nyquist);
+
+        // 找出最大幅度
+        let max_magnitude = datasets
+            .iter()
+            .flat_map(|(_, mag, _)| mag.iter())
+            .copied()
+            .fold(0.0f64, f64::max);
+        let y_max = max_magnitude * 1.1;
+
+        let root = BitMapBackend::new(output_path.as_ref(), (1200, 600))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(title, ("Arial", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(0.0..max_f, 0.0..y_max)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Frequency (Hz)")
+            .y_desc("Magnitude")
+            .draw()?;
+
+        let colors = [&BLUE, &RED, &GREEN, &CYAN, &MAGENTA];
+
+        for (idx, (freqs, mags, label)) in datasets.iter().enumerate() {
+            let data: Vec<(f64, f64)> = freqs
+                .iter()
+                .zip(mags.iter())
+                .filter(|(&f, _)| f <= max_f)
+                .map(|(&f, &m)| (f, m))
+                .collect();
+
+            let color = colors[idx % colors.len()];
+            chart
+                .draw_series(LineSeries::new(data.iter().map(|&(f, m)| (f, m)), color))?
+                .label(*label)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        }
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        root.present()?;
+        println!("对比频谱图已保存到: {:?}", output_path.as_ref());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plot_spectrum() {
+        // 生成测试数据
+        let n = 1000;
+        let sample_rate = 1000.0;
+        let frequencies: Vec<f64> = (0..n).map(|i| i as f64 * sample_rate / n as f64).collect();
+        
+        // 模拟频谱
+        let magnitude: Vec<f64> = frequencies
+            .iter()
+            .map(|&f| {
+                if (f - 100.0).abs() < 10.0 {
+                    1.0
+                } else {
+                    0.1 * (-((f - 100.0) / 50.0).powi(2)).exp()
+                }
+            })
+            .collect();
+
+        let result = SpectrumVisualizer::plot_spectrum(
+            &frequencies,
+            &magnitude,
+            "/tmp/test_spectrum.png",
+            "测试频谱",
+            Some(500.0),
+        );
+        
+        assert!(result.is_ok());
+    }
+}
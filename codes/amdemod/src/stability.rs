@@ -0,0 +1,245 @@
+// 稳定性与数值条件分析模块
+// 数字滤波器：极点最大半径与离单位圆的裕量、极点对 b/a 及二阶节系数的灵敏度与条件数、
+// 由 b/a 重新求根的实际误差，以及由此得出的警告；模拟原型：极点最大实部与最小阻尼比
+
+use crate::analog_system::AnalogSystem;
+use crate::butterworth_filter::{FilterType, IirDesign};
+use crate::zpk::roots;
+use num_complex::Complex;
+use serde::Serialize;
+
+/// 条件数与 ε 之积超过该值（丢失约 8 位有效数字）时认为 b/a 形式病态
+const ILL_CONDITIONED: f64 = 1e-8;
+
+/// 数字滤波器的稳定性报告
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StabilityReport {
+    /// 极点的最大模
+    pub max_pole_radius: f64,
+    /// 稳定裕量 1 - max|p|
+    pub stability_margin: f64,
+    pub stable: bool,
+    /// 系数相对扰动为 δ 时极点的最大位移约为 δ 乘以该灵敏度：b/a 直接型
+    pub tf_sensitivity: f64,
+    /// 同上，二阶节级联（每个极点只受所在节的系数影响）
+    pub sos_sensitivity: f64,
+    /// 极点对系数的相对条件数 max_i Σ_k |a_k||p_i|^{N-k} / (|p_i||A'(p_i)|)：b/a 直接型
+    pub tf_condition_number: f64,
+    /// 同上，二阶节级联
+    pub sos_condition_number: f64,
+    /// 由 b/a 的分母重新求根，与设计极点的最大距离
+    pub tf_pole_error: f64,
+    /// 为使系数舍入（相对误差 2^-bits）不把极点推出单位圆所需的小数位数：b/a 直接型
+    pub tf_required_bits: f64,
+    /// 同上，二阶节级联
+    pub sos_required_bits: f64,
+    pub warnings: Vec<String>,
+}
+
+/// 模拟原型的稳定性：极点全部位于左半平面时稳定
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnalogStability {
+    /// 极点的最大实部 (rad/s)，为负时稳定
+    pub max_real_part: f64,
+    /// 最小阻尼比 -Re(p)/|p|，越接近 0 极点越靠近虚轴、谐振越尖锐
+    pub min_damping_ratio: f64,
+    pub stable: bool,
+}
+
+impl StabilityReport {
+    /// 以设计时的零极点为准，分析 b/a 与二阶节两种实现
+    pub fn digital(filter: &IirDesign) -> Result<Self, String> {
+        let zpk = filter.zpk()?;
+        if zpk.poles.is_empty() {
+            return Err("滤波器没有极点".to_string());
+        }
+        let max_pole_radius = zpk.poles.iter().map(|p| p.norm()).fold(0.0, f64::max);
+        let stability_margin = 1.0 - max_pole_radius;
+        let stable = stability_margin > 0.0;
+
+        let (tf_sensitivity, tf_condition_number) = root_sensitivity(&filter.a, &zpk.poles);
+        let sections = if filter.sos.is_empty() { zpk.to_sos()? } else { filter.sos.clone() };
+        let (sos_sensitivity, sos_condition_number) = sections
+            .iter()
+            .map(|s| {
+                let a = trim_trailing_zeros(&s[3..]);
+                root_sensitivity(a, &roots(a))
+            })
+            .fold((0.0, 0.0), |(s, c), (si, ci)| (f64::max(s, si), f64::max(c, ci)));
+
+        let recovered = roots(&filter.a);
+        let tf_pole_error = nearest_distance(&zpk.poles, &recovered);
+        let required_bits = |sensitivity: f64| (sensitivity / stability_margin).log2().max(0.0);
+
+        let mut report = Self {
+            max_pole_radius,
+            stability_margin,
+            stable,
+            tf_sensitivity,
+            sos_sensitivity,
+            tf_condition_number,
+            sos_condition_number,
+            tf_pole_error,
+            tf_required_bits: if stable { required_bits(tf_sensitivity) } else { f64::INFINITY },
+            sos_required_bits: if stable { required_bits(sos_sensitivity) } else { f64::INFINITY },
+            warnings: Vec::new(),
+        };
+        report.warnings = report.collect_warnings(filter, &recovered);
+        Ok(report)
+    }
+
+    fn collect_warnings(&self, filter: &IirDesign, recovered: &[Complex<f64>]) -> Vec<String> {
+        let kind = match filter.filter_type {
+            FilterType::Lowpass => "低通",
+            FilterType::Highpass => "高通",
+            FilterType::Bandpass => "带通",
+            FilterType::Bandstop => "带阻",
+        };
+        let name = format!("{} 阶{}（{}）", filter.order, kind, filter.family);
+        let mut warnings = Vec::new();
+        if !self.stable {
+            warnings.push(format!("{}：极点最大模 {:.12} ≥ 1，滤波器不稳定", name, self.max_pole_radius));
+            return warnings;
+        }
+        if self.tf_condition_number * f64::EPSILON > ILL_CONDITIONED {
+            warnings.push(format!(
+                "{}：b/a 形式病态，极点条件数 {:.3e}，双精度下约丢失 {:.0} 位有效数字；应使用二阶节（条件数 {:.3e}）",
+                name,
+                self.tf_condition_number,
+                self.tf_condition_number.log10(),
+                self.sos_condition_number
+            ));
+        }
+        if self.tf_sensitivity * f64::EPSILON >= self.stability_margin {
+            warnings.push(format!(
+                "{}：稳定裕量 {:.3e} 小于 b/a 系数舍入引起的极点位移（约 {:.3e}），直接型可能不稳定",
+                name,
+                self.stability_margin,
+                self.tf_sensitivity * f64::EPSILON
+            ));
+        }
+        if let Some(radius) = recovered.iter().map(|p| p.norm()).reduce(f64::max).filter(|&r| r >= 1.0) {
+            warnings.push(format!("{}：由 b/a 求出的极点最大模为 {:.12}，已落在单位圆上或外", name, radius));
+        }
+        warnings
+    }
+}
+
+impl AnalogStability {
+    pub fn of(system: &AnalogSystem) -> Self {
+        let poles = &system.zpk().poles;
+        let max_real_part = poles.iter().map(|p| p.re).fold(f64::NEG_INFINITY, f64::max);
+        let min_damping_ratio = poles
+            .iter()
+            .filter(|p| p.norm() > 0.0)
+            .map(|p| -p.re / p.norm())
+            .fold(f64::INFINITY, f64::min);
+        Self {
+            max_real_part,
+            min_damping_ratio,
+            stable: max_real_part < 0.0,
+        }
+    }
+}
+
+/// 多项式 Σ a_k z^{N-k} 的根对系数相对扰动的灵敏度与相对条件数（取所有根的最大值）
+///
+/// ∂p_i/∂a_k = -p_i^{N-k} / A'(p_i)，A'(p_i) = a_0·Π_{j≠i}(p_i - p_j)；重根处为无穷大
+pub fn root_sensitivity(coefficients: &[f64], roots: &[Complex<f64>]) -> (f64, f64) {
+    let degree = coefficients.len().saturating_sub(1);
+    let mut sensitivity = 0.0f64;
+    let mut condition = 0.0f64;
+    for (i, p) in roots.iter().enumerate() {
+        let derivative = roots
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .fold(Complex::new(coefficients[0], 0.0), |acc, (_, q)| acc * (p - q));
+        let numerator: f64 = coefficients
+            .iter()
+            .enumerate()
+            .map(|(k, a)| a.abs() * p.norm().powi((degree - k) as i32))
+            .sum();
+        let s = numerator / derivative.norm();
+        sensitivity = sensitivity.max(s);
+        if p.norm() > 0.0 {
+            condition = condition.max(s / p.norm());
+        }
+    }
+    (sensitivity, condition)
+}
+
+/// 每个设计极点到最近的求出根的距离的最大值
+fn nearest_distance(poles: &[Complex<f64>], recovered: &[Complex<f64>]) -> f64 {
+    poles
+        .iter()
+        .map(|p| recovered.iter().map(|r| (r - p).norm()).fold(f64::INFINITY, f64::min))
+        .fold(0.0, f64::max)
+}
+
+/// 一阶节的分母 [1, a1, 0] 只有一个极点，去掉末尾的零系数
+fn trim_trailing_zeros(coefficients: &[f64]) -> &[f64] {
+    let len = coefficients.iter().rposition(|&c| c != 0.0).map_or(1, |i| i + 1);
+    &coefficients[..len]
+}
+
+/// 零点、极点按位置合并后的 (位置, 重数)，用于零极点图上标注重根
+pub fn group_roots(roots: &[Complex<f64>], tolerance: f64) -> Vec<(Complex<f64>, usize)> {
+    let mut groups: Vec<(Complex<f64>, usize)> = Vec::new();
+    for r in roots {
+        match groups.iter_mut().find(|(g, _)| (g - r).norm() <= tolerance * g.norm().max(1.0)) {
+            Some((_, count)) => *count += 1,
+            None => groups.push((*r, 1)),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analog_prototype::FilterFamily;
+
+    #[test]
+    fn test_root_sensitivity() {
+        // (z - 0.5)(z - 0.25) = z² - 0.75z + 0.125：∂p/∂a_k = -p^{2-k}/(p - q)
+        let (sensitivity, condition) =
+            root_sensitivity(&[1.0, -0.75, 0.125], &[Complex::new(0.5, 0.0), Complex::new(0.25, 0.0)]);
+        let at = |p: f64, q: f64| (p * p + 0.75 * p + 0.125) / (p - q).abs();
+        assert!((sensitivity - at(0.5, 0.25).max(at(0.25, 0.5))).abs() < 1e-12);
+        assert!((condition - (at(0.5, 0.25) / 0.5).max(at(0.25, 0.5) / 0.25)).abs() < 1e-12);
+
+        let grouped = group_roots(&[Complex::new(1.0, 0.0); 8], 1e-6);
+        assert_eq!(grouped, vec![(Complex::new(1.0, 0.0), 8)]);
+    }
+
+    #[test]
+    fn test_low_cutoff_highpass_is_ill_conditioned_as_tf() {
+        // f_d 越低，极点越靠近 z = 1，b/a 的条件数迅速变大，二阶节则保持良好
+        let mild = StabilityReport::digital(&IirDesign::highpass(8, 3000.0, 22050.0)).unwrap();
+        let severe = StabilityReport::digital(&IirDesign::highpass(8, 50.0, 22050.0)).unwrap();
+        for report in [&mild, &severe] {
+            assert!(report.stable && report.max_pole_radius < 1.0);
+            assert!((report.stability_margin - (1.0 - report.max_pole_radius)).abs() < 1e-15);
+            assert!(report.sos_condition_number < report.tf_condition_number);
+            assert!(report.sos_required_bits < report.tf_required_bits);
+        }
+        assert!(severe.stability_margin < 0.01 && severe.tf_condition_number > 1e3 * mild.tf_condition_number);
+        assert!(severe.tf_pole_error > mild.tf_pole_error);
+        assert!(severe.warnings.iter().any(|w| w.contains("b/a 形式病态")));
+        assert!(mild.warnings.is_empty(), "{:?}", mild.warnings);
+    }
+
+    #[test]
+    fn test_analog_stability() {
+        let filter = IirDesign::with_family(FilterFamily::Chebyshev1 { ripple_db: 1.0 }, 8, 4000.0, 22050.0, FilterType::Lowpass)
+            .unwrap();
+        let analog = AnalogStability::of(&filter.analog().unwrap());
+        assert!(analog.stable && analog.max_real_part < 0.0);
+        // 1 dB 波纹的 8 阶 Chebyshev I 最靠近虚轴的极点阻尼比很小
+        assert!(analog.min_damping_ratio > 0.0 && analog.min_damping_ratio < 0.1);
+        let butterworth = AnalogStability::of(&IirDesign::lowpass(8, 4000.0, 22050.0).analog().unwrap());
+        // Butterworth 极点角度为 π(2k + 9)/16，最小阻尼比 cos(7π/16)
+        assert!((butterworth.min_damping_ratio - (7.0 * std::f64::consts::PI / 16.0).cos()).abs() < 1e-9);
+    }
+}
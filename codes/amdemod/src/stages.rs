@@ -0,0 +1,370 @@
+// 流水线各阶段的计算
+// 频率偏差估计 → 滤波器设计 → 时域解调 → 频域解调，供 Q1–Q4 与命令行工具共用
+
+use crate::analog_prototype::FilterFamily;
+use crate::butterworth_filter::{FilterType, IirDesign};
+use crate::carrier_recovery::CarrierRecovery;
+use crate::filter_order::TransitionSpec;
+use crate::fft_processor::{compute_fft, compute_ifft, FftResult};
+use crate::frequency_estimator::{FrequencyEstimator, SymmetricAxisConfig, SymmetricAxisEstimate};
+use crate::offset_tracker::OffsetTrack;
+use crate::params::{EstimatorMethod, FilterDesigns, FrequencyOffset, PipelineParams};
+use crate::psd::PowerSpectralDensity;
+use crate::window::{Correction, Window};
+use crate::{demodulator, frequency_shifter, ideal_filter};
+use serde::{Deserialize, Serialize};
+
+/// 默认滤波器阶数
+pub const DEFAULT_FILTER_ORDER: usize = 8;
+
+/// 默认基带带宽 f_B (Hz)，由题目给定
+pub const DEFAULT_BANDWIDTH: f64 = 4000.0;
+
+/// 估计 f_d 所用输入频谱的窗与修正方式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpectrumWindow {
+    pub window: Window,
+    pub correction: Correction,
+}
+
+impl Default for SpectrumWindow {
+    /// 矩形窗、幅度修正：修正系数为 1，与不加窗的 `FftResult::compute` 相同
+    fn default() -> Self {
+        Self {
+            window: Window::Rectangular,
+            correction: Correction::Amplitude,
+        }
+    }
+}
+
+impl SpectrumWindow {
+    /// 加窗计算输入频谱（[`FftResult::compute_windowed`]）
+    pub fn compute(&self, samples: &[f64], sample_rate: f64) -> Result<FftResult, String> {
+        FftResult::compute_windowed(samples, sample_rate, self.window, self.correction)
+    }
+}
+
+/// 频率偏差估计的中间结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffsetAnalysis {
+    /// 峰值搜索得到的频率 (Hz)
+    pub peak_frequency: f64,
+    /// 抛物线插值后的峰值频率 (Hz)
+    pub refined_frequency: f64,
+    /// 幅度谱对称轴估计（含排序后的候选与选择理由）
+    pub symmetric_axis: SymmetricAxisEstimate,
+    /// 最终采用的频率偏差
+    pub offset: FrequencyOffset,
+}
+
+/// 估计频率偏差 f_d
+///
+/// 先在 10 Hz–10 kHz 内搜索峰值并做抛物线插值，再用默认参数估计幅度谱的对称轴，
+/// 以对称轴作为 f_d，置信度取对称轴估计的置信度。找不到对称轴时退回插值结果，置信度记为 0。
+pub fn estimate_offset(fft: &FftResult) -> OffsetAnalysis {
+    let (frequencies, magnitude) = fft.two_sided_magnitude();
+    estimate_offset_from_spectrum(&frequencies, &magnitude)
+}
+
+/// 在功率谱密度估计上估计频率偏差 f_d，步骤同 [`estimate_offset`]
+///
+/// 分段或多窗平均后的谱方差更小，峰值搜索与镜像比较不易受噪声频点影响，代价是频率分辨率降为 ENBW
+pub fn estimate_offset_psd(psd: &PowerSpectralDensity) -> OffsetAnalysis {
+    println!("在功率谱密度 {} 上估计（ENBW {:.2} Hz）", psd.method, psd.enbw);
+    let (frequencies, amplitude) = psd.two_sided_amplitude();
+    estimate_offset_from_spectrum(&frequencies, &amplitude)
+}
+
+/// 在双边布局（频率 0 到 f_s，见 `FftResult::two_sided_magnitude`）的幅度谱上估计频率偏差
+fn estimate_offset_from_spectrum(frequencies: &[f64], magnitude: &[f64]) -> OffsetAnalysis {
+    // 基本频率估计（排除直流，搜索 10 Hz 到 10 kHz）
+    let (peak_frequency, _peak_mag, peak_idx) = FrequencyEstimator::estimate_frequency_offset(
+        frequencies,
+        magnitude,
+        (10.0, 10000.0),
+        true, // 排除直流分量
+    );
+
+    // 精确频率估计（使用抛物线插值）
+    let refined_frequency = FrequencyEstimator::refined_frequency_estimate(
+        frequencies,
+        magnitude,
+        peak_idx,
+    );
+
+    // 通过幅度谱的对称轴确定真实的频率偏差
+    println!("\n=== 对称轴分析 ===");
+    let symmetric_axis = FrequencyEstimator::estimate_symmetric_axis(
+        frequencies,
+        magnitude,
+        &SymmetricAxisConfig::default(),
+    );
+
+    let offset = match symmetric_axis.axis {
+        Some(axis) => {
+            println!("  频谱对称轴: {:.2} Hz ← 真实的频率偏差 f_d", axis);
+            FrequencyOffset {
+                f_d: axis,
+                method: EstimatorMethod::SymmetricAxis,
+                confidence: symmetric_axis.confidence,
+            }
+        }
+        None => {
+            println!("警告：未找到频谱对称轴（{}），使用峰值搜索结果", symmetric_axis.reason);
+            FrequencyOffset {
+                f_d: refined_frequency,
+                method: EstimatorMethod::ParabolicInterpolation,
+                confidence: 0.0,
+            }
+        }
+    };
+
+    OffsetAnalysis {
+        peak_frequency,
+        refined_frequency,
+        symmetric_axis,
+        offset,
+    }
+}
+
+/// 设计高通（截止 f_d）与低通（截止 f_B）Butterworth 滤波器
+pub fn design_filters(params: &PipelineParams, order: usize) -> FilterDesigns {
+    FilterDesigns {
+        highpass: IirDesign::highpass(order, params.f_d(), params.sample_rate),
+        lowpass: IirDesign::lowpass(order, params.bandwidth, params.sample_rate),
+    }
+}
+
+/// 按指定的族设计高通与低通滤波器，截止频率同 `design_filters`
+pub fn design_filters_with(
+    params: &PipelineParams,
+    order: usize,
+    family: FilterFamily,
+) -> Result<FilterDesigns, String> {
+    let design = |cutoff, filter_type| {
+        IirDesign::with_family(family, order, cutoff, params.sample_rate, filter_type)
+    };
+    Ok(FilterDesigns {
+        highpass: design(params.f_d(), FilterType::Highpass)?,
+        lowpass: design(params.bandwidth, FilterType::Lowpass)?,
+    })
+}
+
+/// 按过渡带指标设计高通与低通：截止频率仍为 f_d 与 f_B，阶数取满足指标的最小值（两者可以不同）
+pub fn design_filters_for_transition(
+    params: &PipelineParams,
+    family: FilterFamily,
+    transition: &TransitionSpec,
+) -> Result<FilterDesigns, String> {
+    let design = |cutoff, filter_type| {
+        let (order, family) = transition.order_at(&family, filter_type, cutoff, params.sample_rate)?;
+        IirDesign::with_family(family, order, cutoff, params.sample_rate, filter_type)
+    };
+    Ok(FilterDesigns {
+        highpass: design(params.f_d(), FilterType::Highpass)?,
+        lowpass: design(params.bandwidth, FilterType::Lowpass)?,
+    })
+}
+
+/// 覆盖上边带区域 [f_d, f_d + f_B] 的带通滤波器，可代替时域解调中的高通，
+/// 同时滤掉 f_d 以下的干扰和 f_d + f_B 以上的噪声
+pub fn design_sideband_bandpass(
+    params: &PipelineParams,
+    order: usize,
+    family: FilterFamily,
+) -> Result<IirDesign, String> {
+    IirDesign::band_with_family(
+        family,
+        order,
+        params.f_d(),
+        params.f_d() + params.bandwidth,
+        params.sample_rate,
+        FilterType::Bandpass,
+    )
+}
+
+/// 以二阶节级联（旧参数文件中没有二阶节时用直接 I 型）对整段信号滤波
+fn apply(filter: &IirDesign, samples: &[f64]) -> Result<Vec<f64>, String> {
+    Ok(filter.to_iir_filter()?.process_block(samples))
+}
+
+/// 时域解调各阶段信号
+#[derive(Debug, Clone)]
+pub struct TimeDomainDemod {
+    /// 高通滤波后 x_h
+    pub x_h: Vec<f64>,
+    /// 乘以载波后 x_b
+    pub x_b: Vec<f64>,
+    /// 低通滤波后 x_l（解调输出）
+    pub x_l: Vec<f64>,
+}
+
+/// 时域解调：高通 → 乘载波 cos(2πf_d t) → 低通
+pub fn demodulate_time_domain(
+    samples: &[f64],
+    params: &PipelineParams,
+) -> Result<TimeDomainDemod, String> {
+    let filters = params.require_filters()?;
+    let x_h = apply(&filters.highpass, samples)?;
+    let x_b = demodulator::multiply_with_carrier(&x_h, params.f_d(), params.sample_rate);
+    let x_l = apply(&filters.lowpass, &x_b)?;
+    Ok(TimeDomainDemod { x_h, x_b, x_l })
+}
+
+/// 跟踪载波漂移的时域解调：高通 → 乘以跟随 f_d(t) 的 NCO → 低通
+///
+/// 高通、低通仍使用参数文件中的滤波器（截止频率为标称 f_d 与 f_B），只有载波随轨迹变化
+pub fn demodulate_tracked(
+    samples: &[f64],
+    params: &PipelineParams,
+    track: &OffsetTrack,
+) -> Result<TimeDomainDemod, String> {
+    let filters = params.require_filters()?;
+    let f_d = track.per_sample(samples.len());
+    let x_h = apply(&filters.highpass, samples)?;
+    let x_b = demodulator::multiply_with_nco(&x_h, &f_d, params.sample_rate);
+    let x_l = apply(&filters.lowpass, &x_b)?;
+    Ok(TimeDomainDemod { x_h, x_b, x_l })
+}
+
+/// 以载波恢复环路锁定的相位做时域解调：高通 → 乘以 cos θ[n] → 低通
+///
+/// 环路的支路带宽须低于 f_d，只用于锁定载波；解调本身仍走 Q3 的滤波器，保留完整的 f_B 带宽
+pub fn demodulate_recovered(
+    samples: &[f64],
+    params: &PipelineParams,
+    recovery: &CarrierRecovery,
+) -> Result<TimeDomainDemod, String> {
+    let filters = params.require_filters()?;
+    if recovery.phase.len() != samples.len() {
+        return Err(format!(
+            "载波相位长度 {} 与信号长度 {} 不一致",
+            recovery.phase.len(),
+            samples.len()
+        ));
+    }
+    let x_h = apply(&filters.highpass, samples)?;
+    let x_b = demodulator::multiply_with_phase(&x_h, &recovery.phase);
+    let x_l = apply(&filters.lowpass, &x_b)?;
+    Ok(TimeDomainDemod { x_h, x_b, x_l })
+}
+
+/// 频域解调各阶段频谱
+#[derive(Debug, Clone)]
+pub struct FrequencyDomainDemod {
+    /// 输入频谱 X(f)
+    pub x: FftResult,
+    /// 理想高通后 X_h(f)
+    pub x_h: FftResult,
+    /// 频谱搬移后 X_b(f)
+    pub x_b: FftResult,
+    /// 理想低通后 X_l(f)
+    pub x_l: FftResult,
+    /// 解调输出（IFFT 并乘以 2，与时域方法幅度一致）
+    pub output: Vec<f64>,
+}
+
+/// 频域解调：FFT → 理想高通 → 频谱搬移 ±f_d → 理想低通 → IFFT
+pub fn demodulate_frequency_domain(samples: &[f64], params: &PipelineParams) -> FrequencyDomainDemod {
+    let (f_d, f_s, f_b) = (params.f_d(), params.sample_rate, params.bandwidth);
+    let n = samples.len();
+
+    let x_fft = compute_fft(samples);
+    let xh_fft = ideal_filter::apply_highpass(&x_fft, f_d, f_s, n);
+    let xb_fft = frequency_shifter::frequency_shift(&xh_fft, f_d, f_s, n);
+    let xl_fft = ideal_filter::apply_lowpass(&xb_fft, f_b, f_s, n);
+
+    // Apply gain compensation (multiply by 2 to match time-domain method)
+    let output = compute_ifft(&xl_fft).into_iter().map(|x| x * 2.0).collect();
+
+    FrequencyDomainDemod {
+        x: FftResult::from_spectrum(x_fft, f_s),
+        x_h: FftResult::from_spectrum(xh_fft, f_s),
+        x_b: FftResult::from_spectrum(xb_fft, f_s),
+        x_l: FftResult::from_spectrum(xl_fft, f_s),
+        output,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_time_and_frequency_domain_agree() {
+        // 基带 500 Hz 正弦被 f_d = 3000 Hz 的余弦调制
+        let f_s = 22050.0;
+        let n = 22050;
+        let samples: Vec<f64> = (0..n)
+            .map(|i| {
+                let t = i as f64 / f_s;
+                (2.0 * PI * 500.0 * t).sin() * (2.0 * PI * 3000.0 * t).cos()
+            })
+            .collect();
+
+        let offset = FrequencyOffset::manual(3000.0);
+        let mut params = PipelineParams::new(f_s, n, offset, DEFAULT_BANDWIDTH);
+        params.filters = Some(design_filters(&params, DEFAULT_FILTER_ORDER));
+
+        let time = demodulate_time_domain(&samples, &params).unwrap();
+        let freq = demodulate_frequency_domain(&samples, &params);
+        assert_eq!(time.x_l.len(), n);
+        assert_eq!(freq.output.len(), n);
+
+        // 两种方法都应恢复 500 Hz 基带分量
+        let spectrum = FftResult::from_spectrum(compute_fft(&freq.output), f_s);
+        let peak = spectrum.get_single_sided_pairs()
+            .into_iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        assert!((peak.0 - 500.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_tracked_demodulation_follows_drift() {
+        // 500 Hz 基带，载波偏差在 1 s 内从 2980 Hz 漂移到 3020 Hz
+        let f_s = 22050.0;
+        let n = 22050;
+        let baseband: Vec<f64> = (0..n).map(|i| (2.0 * PI * 500.0 * i as f64 / f_s).sin()).collect();
+        let samples: Vec<f64> = baseband
+            .iter()
+            .enumerate()
+            .map(|(i, &m)| {
+                let t = i as f64 / f_s;
+                m * (2.0 * PI * (2980.0 * t + 20.0 * t * t)).cos()
+            })
+            .collect();
+
+        let offset = FrequencyOffset::manual(3000.0);
+        let mut params = PipelineParams::new(f_s, n, offset, DEFAULT_BANDWIDTH);
+        params.filters = Some(design_filters(&params, DEFAULT_FILTER_ORDER));
+        let track = crate::offset_tracker::track_frequency_offset(
+            &samples,
+            f_s,
+            3000.0,
+            &crate::offset_tracker::TrackerConfig::default(),
+        )
+        .unwrap();
+
+        // 跳过滤波器暂态，比较与基带的误差。只跟踪频率不锁定相位，残余相位误差仍会造成一定衰落，
+        // 但固定载波的输出已与基带几乎不相关
+        let error = |output: &[f64]| {
+            // 在 0–200 个样本内搜索滤波器群时延
+            (0..200)
+                .map(|d| (4000..n - 4000).map(|i| (output[i] - baseband[i - d]).powi(2)).sum::<f64>())
+                .fold(f64::INFINITY, f64::min)
+                / (n - 8000) as f64
+        };
+        let tracked = error(&demodulate_tracked(&samples, &params, &track).unwrap().x_l);
+        let fixed = error(&demodulate_time_domain(&samples, &params).unwrap().x_l);
+        assert!(tracked < 0.25 * fixed, "tracked {} vs fixed {}", tracked, fixed);
+    }
+
+    #[test]
+    fn test_time_domain_requires_filters() {
+        let offset = FrequencyOffset::manual(3000.0);
+        let params = PipelineParams::new(22050.0, 16, offset, DEFAULT_BANDWIDTH);
+        assert!(demodulate_time_domain(&[0.0; 16], &params).is_err());
+    }
+}
@@ -0,0 +1,708 @@
+// 状态空间模型模块
+// 单输入单输出系统 ẋ = Ax + Bu, y = Cx + Du（连续）或 x[k+1] = Ax[k] + Bu[k], y[k] = Cx[k] + Du[k]（离散）。
+// 与传递函数、零极点增益互相转换（能控 / 能观标准型、二阶节级联、平衡实现），
+// 连续模型按 ZOH、FOH、Tustin、冲激响应不变法离散化（与 scipy cont2discrete 一致），离散模型在样本序列上仿真，
+// 矩阵可导出为 JSON 或 MATLAB 脚本
+
+use crate::iir_filter::SosSection;
+use crate::matrix::Matrix;
+use crate::zpk::{roots, Zpk};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 连续模型离散化（或模拟系统仿真）的方法，对应 MATLAB c2d 的 method 参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Discretization {
+    /// 零阶保持：输入在采样间隔内保持不变，阶跃输入下采样点处的输出精确
+    Zoh,
+    /// 一阶保持（三角保持）：输入在相邻样本间线性插值，斜坡输入下精确
+    Foh,
+    /// 双线性变换 s = 2f_s·(z - 1)/(z + 1)，与滤波器设计使用的变换相同（不做预畸变）
+    Tustin,
+    /// 零极点匹配：z = e^{sT}，s = ∞ 处的零点放在 z = -1，增益在直流或通带内匹配
+    Matched,
+    /// 冲激响应不变：离散冲激响应为 T·h(kT)，只适用于没有直通项的严格真系统
+    Impulse,
+}
+
+impl fmt::Display for Discretization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Discretization::Zoh => "ZOH",
+            Discretization::Foh => "FOH",
+            Discretization::Tustin => "Tustin",
+            Discretization::Matched => "matched-z",
+            Discretization::Impulse => "impulse-invariant",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 求 Gramian 时 Smith 迭代的最大次数；每次迭代相当于级数项数翻倍
+const MAX_SMITH_ITERATIONS: usize = 64;
+
+/// 单输入单输出状态空间模型 {A, B, C, D}
+///
+/// 序列化时各矩阵写成按行嵌套的数组，连续模型没有 `sample_rate` 字段
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawStateSpace")]
+pub struct StateSpace {
+    /// n×n 状态矩阵
+    pub a: Matrix,
+    /// n×1 输入矩阵
+    pub b: Matrix,
+    /// 1×n 输出矩阵
+    pub c: Matrix,
+    /// 1×1 直通项
+    pub d: Matrix,
+    /// 离散模型的采样率 (Hz)；连续模型为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<f64>,
+}
+
+/// 反序列化时先读入各字段，再经 `StateSpace::new` 检查维数
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawStateSpace {
+    a: Matrix,
+    b: Matrix,
+    c: Matrix,
+    d: Matrix,
+    #[serde(default)]
+    sample_rate: Option<f64>,
+}
+
+impl TryFrom<RawStateSpace> for StateSpace {
+    type Error = String;
+
+    fn try_from(raw: RawStateSpace) -> Result<Self, String> {
+        StateSpace::new(raw.a, raw.b, raw.c, raw.d, raw.sample_rate)
+    }
+}
+
+/// 平衡实现及其 Hankel 奇异值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalancedRealization {
+    /// 能控、能观 Gramian 相等且为对角阵 diag(σ) 的实现
+    pub system: StateSpace,
+    /// Hankel 奇异值，降序；很小的值对应可以截断的状态
+    pub hankel_singular_values: Vec<f64>,
+}
+
+impl StateSpace {
+    /// 检查各矩阵的维数是否构成一个单输入单输出系统
+    pub fn new(a: Matrix, b: Matrix, c: Matrix, d: Matrix, sample_rate: Option<f64>) -> Result<Self, String> {
+        let n = a.rows();
+        // 没有状态时 B、C 按行嵌套写出后丢失了列数 / 行数
+        let (a, b, c) = if n == 0 && b.rows() == 0 && c.cols() == 0 {
+            (Matrix::zeros(0, 0), Matrix::zeros(0, 1), Matrix::zeros(1, 0))
+        } else {
+            (a, b, c)
+        };
+        if !a.is_square() || (b.rows(), b.cols()) != (n, 1) || (c.rows(), c.cols()) != (1, n) || (d.rows(), d.cols()) != (1, 1) {
+            return Err(format!(
+                "状态空间维数不匹配：A {}×{}，B {}×{}，C {}×{}，D {}×{}",
+                a.rows(), a.cols(), b.rows(), b.cols(), c.rows(), c.cols(), d.rows(), d.cols()
+            ));
+        }
+        if let Some(fs) = sample_rate {
+            if !(fs.is_finite() && fs > 0.0) {
+                return Err(format!("采样率必须为正数，得到 {}", fs));
+            }
+        }
+        Ok(Self { a, b, c, d, sample_rate })
+    }
+
+    /// 纯增益（没有状态）
+    pub fn gain(value: f64, sample_rate: Option<f64>) -> Self {
+        Self {
+            a: Matrix::zeros(0, 0),
+            b: Matrix::zeros(0, 1),
+            c: Matrix::zeros(1, 0),
+            d: Matrix::scalar(value),
+            sample_rate,
+        }
+    }
+
+    /// 由传递函数构造能控标准型（MATLAB / scipy `tf2ss`）
+    ///
+    /// 连续系统的 num、den 为 s 的降幂系数，num 较短时在前面补零；离散系统（sample_rate 为 Some）的 b、a
+    /// 为 z^{-1} 的升幂系数，较短的在后面补零，补齐后即 z 的降幂系数。
+    /// A 的第一行为 -den[1..]，次对角线为 1，B = e_1。高阶时系数跨越很多个数量级，病态，
+    /// 分析设计好的滤波器应使用 `from_zpk` 或 `balanced`
+    pub fn controllable(num: &[f64], den: &[f64], sample_rate: Option<f64>) -> Result<Self, String> {
+        let (num, den) = normalize_tf(num, den, sample_rate.is_some())?;
+        let n = den.len() - 1;
+        let mut a = Matrix::zeros(n, n);
+        for j in 0..n {
+            a[(0, j)] = -den[j + 1];
+        }
+        for i in 1..n {
+            a[(i, i - 1)] = 1.0;
+        }
+        let mut b = Matrix::zeros(n, 1);
+        if n > 0 {
+            b[(0, 0)] = 1.0;
+        }
+        let c: Vec<f64> = (0..n).map(|j| num[j + 1] - num[0] * den[j + 1]).collect();
+        Self::new(a, b, Matrix::row(&c), Matrix::scalar(num[0]), sample_rate)
+    }
+
+    /// 由传递函数构造能观标准型：能控标准型的对偶（A → Aᵀ，B ↔ Cᵀ）
+    pub fn observable(num: &[f64], den: &[f64], sample_rate: Option<f64>) -> Result<Self, String> {
+        Ok(Self::controllable(num, den, sample_rate)?.dual())
+    }
+
+    /// 由零极点增益构造：分组为二阶节（连续系统用 `Zpk::to_analog_sos`，离散系统用 `Zpk::to_sos`）后逐节串联，
+    /// 每节只涉及一对极点，高阶时依然稳定
+    pub fn from_zpk(zpk: &Zpk, sample_rate: Option<f64>) -> Result<Self, String> {
+        let sections = match sample_rate {
+            Some(_) => zpk.to_sos()?,
+            None => zpk.to_analog_sos()?,
+        };
+        Self::from_sections(&sections, sample_rate)
+    }
+
+    /// 由二阶节级联构造：每节 [b0, b1, b2, a0, a1, a2] 为变量（s 或 z）的降幂系数
+    ///
+    /// 模拟节来自 `Zpk::to_analog_sos`，数字节来自 `Zpk::to_sos`（z^{-1} 的升幂系数即 z 的降幂系数）。
+    /// 每个二次节用按 √|a0|（a2 归一化为 1）缩放的能控标准型实现，状态量级与输入相当，离散化时矩阵指数不会病态
+    pub fn from_sections(sections: &[SosSection], sample_rate: Option<f64>) -> Result<Self, String> {
+        let mut system = Self::gain(1.0, sample_rate);
+        for (i, section) in sections.iter().enumerate() {
+            let part = section_realization(section, sample_rate).map_err(|e| format!("第 {} 节：{}", i + 1, e))?;
+            system = system.series(&part);
+        }
+        Ok(system)
+    }
+
+    /// 传递函数系数 (num, den)，den 首一，两者等长（scipy `ss2tf`）
+    ///
+    /// 连续系统为 s 的降幂系数；离散系统为 z 的降幂系数，也就是 z^{-1} 的升幂系数 b、a。
+    /// den = det(λI - A)，num = det(λI - A + BC) + (D - 1)·den
+    pub fn to_tf(&self) -> Result<(Vec<f64>, Vec<f64>), String> {
+        let den = self.a.characteristic_polynomial()?;
+        let closed = &self.a - &(&self.b * &self.c);
+        let d = self.d[(0, 0)];
+        let num = closed
+            .characteristic_polynomial()?
+            .iter()
+            .zip(&den)
+            .map(|(p, q)| p + (d - 1.0) * q)
+            .collect();
+        Ok((num, den))
+    }
+
+    /// 零极点增益：对传递函数的分子、分母求根（`ss2zp`），多重根与高阶时精度有限
+    pub fn to_zpk(&self) -> Result<Zpk, String> {
+        let (num, den) = self.to_tf()?;
+        let lead = num.iter().copied().find(|&v| v != 0.0).unwrap_or(0.0);
+        let zeros = if lead == 0.0 { Vec::new() } else { roots(&num) };
+        Ok(Zpk::new(zeros, roots(&den), lead))
+    }
+
+    /// 同一传递函数的能控标准型
+    pub fn controllable_form(&self) -> Result<StateSpace, String> {
+        let (num, den) = self.to_tf()?;
+        Self::controllable(&num, &den, self.sample_rate)
+    }
+
+    /// 同一传递函数的能观标准型
+    pub fn observable_form(&self) -> Result<StateSpace, String> {
+        let (num, den) = self.to_tf()?;
+        Self::observable(&num, &den, self.sample_rate)
+    }
+
+    /// 能控 Gramian P 与能观 Gramian Q
+    ///
+    /// 连续系统：AP + PAᵀ + BBᵀ = 0，AᵀQ + QA + CᵀC = 0；离散系统：APAᵀ - P + BBᵀ = 0，AᵀQA - Q + CᵀC = 0。
+    /// 离散方程用平方 Smith 迭代求解，连续系统先经 Cayley 变换化为 Gramian 相同的离散系统。系统必须稳定
+    pub fn gramians(&self) -> Result<(Matrix, Matrix), String> {
+        let (a, b, c) = match self.sample_rate {
+            Some(_) => (self.a.clone(), self.b.clone(), self.c.clone()),
+            None => {
+                // A_d = (αI + A)(αI - A)^{-1}，B_d = √(2α)(αI - A)^{-1}B，C_d = √(2α)C(αI - A)^{-1}；
+                // α 取极点模的几何平均，使变换后的极点远离单位圆
+                let n = self.order();
+                let constant = self.a.characteristic_polynomial()?.last().copied().unwrap_or(1.0).abs();
+                let alpha = if constant > 0.0 && n > 0 {
+                    constant.powf(1.0 / n as f64)
+                } else {
+                    self.a.norm_inf().max(1.0)
+                };
+                let identity = Matrix::identity(n);
+                let left = &identity.scale(alpha) - &self.a;
+                let root = (2.0 * alpha).sqrt();
+                let a = left.transpose().solve(&(&identity.scale(alpha) + &self.a).transpose())?.transpose();
+                let b = left.solve(&self.b)?.scale(root);
+                let c = left.transpose().solve(&self.c.transpose())?.transpose().scale(root);
+                (a, b, c)
+            }
+        };
+        let stability = "系统不稳定（或有极点在稳定边界上），Gramian 不存在";
+        let p = stein(&a, &(&b * &b.transpose())).ok_or(stability)?;
+        let q = stein(&a.transpose(), &(&c.transpose() * &c)).ok_or(stability)?;
+        Ok((p, q))
+    }
+
+    /// 平衡实现（平方根法）：P = LcLcᵀ、Q = LoLoᵀ，LoᵀLc = UΣVᵀ，T = LcVΣ^{-1/2}
+    ///
+    /// 变换后两个 Gramian 都等于 diag(σ)，状态按对输入输出的贡献从大到小排列，各状态的量级相当，
+    /// 是高阶滤波器数值最好的实现之一。系统必须稳定且最小（没有零极点对消）
+    pub fn balanced(&self) -> Result<BalancedRealization, String> {
+        let (p, q) = self.gramians()?;
+        let factor = |gramian: &Matrix| -> Result<Matrix, String> {
+            let (values, vectors) = gramian.symmetric_eigen()?;
+            let mut root = vectors;
+            for (j, value) in values.iter().enumerate() {
+                let scale = value.max(0.0).sqrt();
+                for i in 0..root.rows() {
+                    root[(i, j)] *= scale;
+                }
+            }
+            Ok(root)
+        };
+        let (lc, lo) = (factor(&p)?, factor(&q)?);
+        let (u, sigma, v) = (&lo.transpose() * &lc).svd()?;
+        let largest = sigma.first().copied().unwrap_or(0.0);
+        if let Some(small) = sigma.iter().find(|&&s| s <= 1e-13 * largest) {
+            return Err(format!(
+                "Hankel 奇异值 {:e} 近似为零，系统不是最小实现（存在零极点对消），无法平衡",
+                small
+            ));
+        }
+        let n = self.order();
+        let mut t = &lc * &v;
+        let mut t_inv = (&lo * &u).transpose();
+        for (j, s) in sigma.iter().enumerate() {
+            let scale = s.sqrt();
+            for i in 0..n {
+                t[(i, j)] /= scale;
+                t_inv[(j, i)] /= scale;
+            }
+        }
+        Ok(BalancedRealization {
+            system: self.similarity(&t, &t_inv),
+            hankel_singular_values: sigma,
+        })
+    }
+
+    /// 相似变换 x = T·x'：{T⁻¹AT, T⁻¹B, CT, D}，传递函数不变
+    pub fn similarity(&self, t: &Matrix, t_inv: &Matrix) -> StateSpace {
+        StateSpace {
+            a: &(t_inv * &self.a) * t,
+            b: t_inv * &self.b,
+            c: &self.c * t,
+            d: self.d.clone(),
+            sample_rate: self.sample_rate,
+        }
+    }
+
+    /// 对偶系统 {Aᵀ, Cᵀ, Bᵀ, D}，传递函数不变
+    fn dual(&self) -> StateSpace {
+        StateSpace {
+            a: self.a.transpose(),
+            b: self.c.transpose(),
+            c: self.b.transpose(),
+            d: self.d.clone(),
+            sample_rate: self.sample_rate,
+        }
+    }
+
+    /// 导出为 MATLAB / Octave 脚本：矩阵按最短的精确十进制表示写出，末行用 `ss` 构造模型
+    pub fn to_matlab(&self, name: &str) -> String {
+        let literal = |m: &Matrix| {
+            let rows: Vec<String> = m
+                .to_rows()
+                .iter()
+                .map(|row| row.iter().map(|v| format!("{:e}", v)).collect::<Vec<_>>().join(", "))
+                .collect();
+            format!("[{}]", rows.join(";\n     "))
+        };
+        let mut script = format!("% {}: {}-state {} system\n", name, self.order(), if self.is_discrete() { "discrete" } else { "continuous" });
+        for (label, m) in [("A", &self.a), ("B", &self.b), ("C", &self.c), ("D", &self.d)] {
+            script.push_str(&format!("{} = {};\n", label, literal(m)));
+        }
+        match self.sample_rate {
+            Some(fs) => script.push_str(&format!("{} = ss(A, B, C, D, 1 / {:e});\n", name, fs)),
+            None => script.push_str(&format!("{} = ss(A, B, C, D);\n", name)),
+        }
+        script
+    }
+
+    /// 状态数
+    pub fn order(&self) -> usize {
+        self.a.rows()
+    }
+
+    pub fn is_discrete(&self) -> bool {
+        self.sample_rate.is_some()
+    }
+
+    /// 串联：self 的输出作为 next 的输入
+    pub fn series(&self, next: &StateSpace) -> StateSpace {
+        let (n1, n2) = (self.order(), next.order());
+        let coupling = &next.b * &self.c;
+        let a = Matrix::from_blocks(&[&[&self.a, &Matrix::zeros(n1, n2)], &[&coupling, &next.a]]).expect("维数一致");
+        let b = Matrix::from_blocks(&[&[&self.b], &[&(&next.b * &self.d)]]).expect("维数一致");
+        let c = Matrix::from_blocks(&[&[&(&next.d * &self.c), &next.c]]).expect("维数一致");
+        StateSpace {
+            a,
+            b,
+            c,
+            d: &next.d * &self.d,
+            sample_rate: self.sample_rate,
+        }
+    }
+
+    /// 连续模型离散化（scipy cont2discrete）；零极点匹配需要零极点，见 `AnalogSystem::discretize`
+    pub fn discretize(&self, sample_rate: f64, method: Discretization) -> Result<StateSpace, String> {
+        if self.is_discrete() {
+            return Err("模型已经是离散的".to_string());
+        }
+        if !(sample_rate.is_finite() && sample_rate > 0.0) {
+            return Err(format!("采样率必须为正数，得到 {}", sample_rate));
+        }
+        let n = self.order();
+        let dt = 1.0 / sample_rate;
+        let (a, b, c, d) = match method {
+            Discretization::Zoh => {
+                // exp([[A, B], [0, 0]]·T) = [[Φ, Γ], [0, I]]
+                let augmented = Matrix::from_blocks(&[&[&self.a, &self.b], &[&Matrix::zeros(1, n), &Matrix::zeros(1, 1)]])?;
+                let exp = augmented.scale(dt).expm()?;
+                (exp.block(0, 0, n, n), exp.block(0, n, n, 1), self.c.clone(), self.d.clone())
+            }
+            Discretization::Foh => {
+                // exp([[A·T, B·T, 0], [0, 0, I], [0, 0, 0]]) 的第一块行给出 Φ、Γ1、Γ2
+                let mut augmented = Matrix::zeros(n + 2, n + 2);
+                augmented.set_block(0, 0, &self.a.scale(dt));
+                augmented.set_block(0, n, &self.b.scale(dt));
+                augmented[(n, n + 1)] = 1.0;
+                let exp = augmented.expm()?;
+                let phi = exp.block(0, 0, n, n);
+                let gamma1 = exp.block(0, n, n, 1);
+                let gamma2 = exp.block(0, n + 1, n, 1);
+                let b = &(&gamma1 - &gamma2) + &(&phi * &gamma2);
+                let d = &self.d + &(&self.c * &gamma2);
+                (phi, b, self.c.clone(), d)
+            }
+            Discretization::Tustin => {
+                // 广义双线性变换 α = 1/2
+                let identity = Matrix::identity(n);
+                let left = &identity - &self.a.scale(0.5 * dt);
+                let a = left.solve(&(&identity + &self.a.scale(0.5 * dt)))?;
+                let b = left.solve(&self.b.scale(dt))?;
+                let c = left.transpose().solve(&self.c.transpose())?.transpose();
+                let d = &self.d + &(&self.c * &b).scale(0.5);
+                (a, b, c, d)
+            }
+            Discretization::Impulse => {
+                let scale = self.c.norm_inf() * self.b.norm_inf();
+                if self.d[(0, 0)].abs() > 1e-12 * scale.max(1.0) {
+                    return Err("冲激响应不变法只适用于严格真系统（零点少于极点、没有直通项）".to_string());
+                }
+                let phi = self.a.scale(dt).expm()?;
+                let b = (&phi * &self.b).scale(dt);
+                let d = (&self.c * &self.b).scale(dt);
+                (phi, b, self.c.clone(), d)
+            }
+            Discretization::Matched => {
+                return Err("零极点匹配需要零极点，请对 AnalogSystem 离散化".to_string());
+            }
+        };
+        Self::new(a, b, c, d, Some(sample_rate))
+    }
+
+    /// 离散模型在样本序列上仿真，初始状态为零
+    pub fn simulate(&self, input: &[f64]) -> Result<Vec<f64>, String> {
+        self.simulate_from(&mut vec![0.0; self.order()], input)
+    }
+
+    /// 从给定状态开始仿真，结束时 state 更新为最后的状态；逐块调用与一次处理整段信号的结果相同
+    pub fn simulate_from(&self, state: &mut Vec<f64>, input: &[f64]) -> Result<Vec<f64>, String> {
+        if !self.is_discrete() {
+            return Err("连续模型需要先离散化才能在样本序列上仿真".to_string());
+        }
+        let n = self.order();
+        if state.len() != n {
+            return Err(format!("状态向量长度 {} 与状态数 {} 不符", state.len(), n));
+        }
+        let a = self.a.to_rows();
+        let b: Vec<f64> = (0..n).map(|i| self.b[(i, 0)]).collect();
+        let c: Vec<f64> = (0..n).map(|i| self.c[(0, i)]).collect();
+        let d = self.d[(0, 0)];
+        let mut next = vec![0.0; n];
+        Ok(input
+            .iter()
+            .map(|&u| {
+                let y = c.iter().zip(state.iter()).map(|(c, x)| c * x).sum::<f64>() + d * u;
+                for (i, value) in next.iter_mut().enumerate() {
+                    *value = a[i].iter().zip(state.iter()).map(|(a, x)| a * x).sum::<f64>() + b[i] * u;
+                }
+                std::mem::swap(state, &mut next);
+                y
+            })
+            .collect())
+    }
+}
+
+/// 补齐并归一化传递函数系数，返回等长的 (num, den)，den 首一
+fn normalize_tf(num: &[f64], den: &[f64], discrete: bool) -> Result<(Vec<f64>, Vec<f64>), String> {
+    if num.iter().chain(den).any(|v| !v.is_finite()) {
+        return Err("传递函数系数含有 NaN 或无穷大".to_string());
+    }
+    let (mut num, mut den) = (num.to_vec(), den.to_vec());
+    if discrete {
+        if den.first().copied().unwrap_or(0.0) == 0.0 {
+            return Err("a[0] 不能为零".to_string());
+        }
+        let len = num.len().max(den.len());
+        num.resize(len, 0.0);
+        den.resize(len, 0.0);
+    } else {
+        let start = den.iter().position(|&v| v != 0.0).ok_or("分母 den 全为零")?;
+        den.drain(..start);
+        let start = num.iter().position(|&v| v != 0.0).unwrap_or(num.len());
+        num.drain(..start);
+        if num.len() > den.len() {
+            return Err("分子次数高于分母，H(s) 不是真有理函数".to_string());
+        }
+        let mut padded = vec![0.0; den.len() - num.len()];
+        padded.extend(num);
+        num = padded;
+    }
+    let lead = den[0];
+    Ok((num.iter().map(|v| v / lead).collect(), den.iter().map(|v| v / lead).collect()))
+}
+
+/// Stein 方程（离散 Lyapunov 方程）X = A·X·Aᵀ + W 的解（平方 Smith 迭代），A 的谱半径不小于 1 时返回 None
+fn stein(a: &Matrix, w: &Matrix) -> Option<Matrix> {
+    let mut x = w.clone();
+    let mut power = a.clone();
+    for _ in 0..MAX_SMITH_ITERATIONS {
+        let increment = &(&power * &x) * &power.transpose();
+        x = &x + &increment;
+        power = &power * &power;
+        if !x.norm_inf().is_finite() || power.norm_inf() > 1e100 {
+            return None;
+        }
+        if increment.norm_inf() <= f64::EPSILON * x.norm_inf() && power.norm_inf() < 1.0 {
+            return Some(x);
+        }
+    }
+    None
+}
+
+/// 单节的状态空间实现：二次节用缩放后的能控标准型，一阶节一个状态，常数节只有直通项
+///
+/// 分子、分母常数项同时为零（如 `Zpk::to_sos` 为奇数阶补出的原点零极点）时先约去公因子，不产生多余的状态
+fn section_realization(section: &SosSection, sample_rate: Option<f64>) -> Result<StateSpace, String> {
+    let [mut n2, mut n1, mut n0, mut d2, mut d1, mut d0] = *section;
+    while n0 == 0.0 && d0 == 0.0 && (d1 != 0.0 || d2 != 0.0) {
+        (n0, n1, n2, d0, d1, d2) = (n1, n2, 0.0, d1, d2, 0.0);
+    }
+    if d2 != 0.0 {
+        // (n2 s² + n1 s + n0)/(s² + a1 s + a0)
+        let (n2, n1, n0, a1, a0) = (n2 / d2, n1 / d2, n0 / d2, d1 / d2, d0 / d2);
+        let w = if a0 == 0.0 { 1.0 } else { a0.abs().sqrt() };
+        return StateSpace::new(
+            Matrix::from_rows(&[vec![0.0, w], vec![-a0 / w, -a1]])?,
+            Matrix::column(&[0.0, 1.0]),
+            Matrix::row(&[(n0 - a0 * n2) / w, n1 - a1 * n2]),
+            Matrix::scalar(n2),
+            sample_rate,
+        );
+    }
+    if n2 != 0.0 {
+        return Err("分子次数高于分母".to_string());
+    }
+    if d1 != 0.0 {
+        let (n1, n0, a0) = (n1 / d1, n0 / d1, d0 / d1);
+        return StateSpace::new(
+            Matrix::scalar(-a0),
+            Matrix::scalar(1.0),
+            Matrix::scalar(n0 - a0 * n1),
+            Matrix::scalar(n1),
+            sample_rate,
+        );
+    }
+    if n1 != 0.0 {
+        return Err("分子次数高于分母".to_string());
+    }
+    if d0 == 0.0 {
+        return Err("分母全为零".to_string());
+    }
+    Ok(StateSpace::gain(n0 / d0, sample_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::butterworth_filter::IirDesign;
+
+    fn max_difference(x: &[f64], y: &[f64]) -> f64 {
+        x.iter().zip(y).fold(0.0f64, |m, (a, b)| m.max((a - b).abs()))
+    }
+
+    fn test_signal(len: usize) -> Vec<f64> {
+        (0..len).map(|i| (i as f64 * 0.37).sin() + ((i * 7) % 11) as f64 * 0.05).collect()
+    }
+
+    /// a/(s + a)：直流增益 1 的一阶低通
+    fn first_order(a: f64) -> StateSpace {
+        StateSpace::from_sections(&[[0.0, 0.0, a, 0.0, 1.0, a]], None).unwrap()
+    }
+
+    #[test]
+    fn test_hold_methods_are_exact_for_their_inputs() {
+        let (a, fs) = (300.0, 8000.0);
+        let system = first_order(a);
+        let t = |k: usize| k as f64 / fs;
+
+        // ZOH：阶跃响应 1 - e^{-at}
+        let y = system.discretize(fs, Discretization::Zoh).unwrap().simulate(&[1.0; 200]).unwrap();
+        assert!(y.iter().enumerate().all(|(k, v)| (v - (1.0 - (-a * t(k)).exp())).abs() < 1e-12));
+
+        // FOH：斜坡响应 t - (1 - e^{-at})/a
+        let ramp: Vec<f64> = (0..200).map(t).collect();
+        let y = system.discretize(fs, Discretization::Foh).unwrap().simulate(&ramp).unwrap();
+        assert!(y.iter().enumerate().all(|(k, v)| (v - (t(k) - (1.0 - (-a * t(k)).exp()) / a)).abs() < 1e-12));
+
+        // 冲激响应不变：离散冲激响应为 T·a·e^{-akT}
+        let mut impulse = vec![0.0; 200];
+        impulse[0] = 1.0;
+        let y = system.discretize(fs, Discretization::Impulse).unwrap().simulate(&impulse).unwrap();
+        assert!(y.iter().enumerate().all(|(k, v)| (v - a * (-a * t(k)).exp() / fs).abs() < 1e-12));
+
+        // Tustin：极点 (1 + aT/2)/(1 - aT/2) 的倒数形式
+        let tustin = system.discretize(fs, Discretization::Tustin).unwrap();
+        let pole = (1.0 - a / (2.0 * fs)) / (1.0 + a / (2.0 * fs));
+        assert!((tustin.a[(0, 0)] - pole).abs() < 1e-12);
+
+        assert!(tustin.discretize(fs, Discretization::Zoh).is_err());
+        assert!(system.simulate(&ramp).is_err());
+        assert!(system.discretize(fs, Discretization::Matched).is_err());
+    }
+
+    #[test]
+    fn test_series_connection() {
+        // 两个一阶节串联等于二阶系统 a·b/((s + a)(s + b))，直流增益 1
+        let cascade = first_order(100.0).series(&first_order(400.0));
+        assert_eq!(cascade.order(), 2);
+        let y = cascade.discretize(1000.0, Discretization::Zoh).unwrap().simulate(&[1.0; 400]).unwrap();
+        assert!((y[399] - 1.0).abs() < 1e-9);
+        let expected = |t: f64| 1.0 - (400.0 * (-100.0 * t).exp() - 100.0 * (-400.0 * t).exp()) / 300.0;
+        assert!(y.iter().enumerate().all(|(k, v)| (v - expected(k as f64 / 1000.0)).abs() < 1e-12));
+
+        assert!(StateSpace::from_sections(&[[1.0, 0.0, 0.0, 0.0, 1.0, 1.0]], None).is_err());
+        assert!(StateSpace::new(Matrix::identity(2), Matrix::column(&[1.0]), Matrix::row(&[1.0, 0.0]), Matrix::scalar(0.0), None).is_err());
+    }
+
+    #[test]
+    fn test_canonical_forms_and_tf_round_trip() {
+        // (2s + 3)/(s³ + 4s² + 6s + 4)
+        let controllable = StateSpace::controllable(&[2.0, 3.0], &[1.0, 4.0, 6.0, 4.0], None).unwrap();
+        assert_eq!(controllable.a.to_rows()[0], vec![-4.0, -6.0, -4.0]);
+        let observable = StateSpace::observable(&[2.0, 3.0], &[1.0, 4.0, 6.0, 4.0], None).unwrap();
+        assert_eq!(observable.a, controllable.a.transpose());
+        for system in [&controllable, &observable] {
+            let (num, den) = system.to_tf().unwrap();
+            for (x, y) in num.iter().zip(&[0.0, 0.0, 2.0, 3.0]).chain(den.iter().zip(&[1.0, 4.0, 6.0, 4.0])) {
+                assert!((x - y).abs() < 1e-12, "{:?} {:?}", num, den);
+            }
+        }
+        assert!(StateSpace::controllable(&[1.0, 0.0, 0.0], &[1.0, 1.0], None).is_err());
+
+        // 数字低通：各种实现给出相同的传递函数和输出
+        let filter = IirDesign::lowpass(6, 4000.0, 22050.0);
+        let system = filter.state_space().unwrap();
+        assert_eq!((system.order(), system.sample_rate), (6, Some(22050.0)));
+        let (b, a) = system.to_tf().unwrap();
+        assert!(b.iter().zip(&filter.b).chain(a.iter().zip(&filter.a)).all(|(x, y)| (x - y).abs() < 1e-10));
+        let zpk = system.to_zpk().unwrap();
+        assert!(zpk.zeros.iter().all(|z| (z + 1.0).norm() < 1e-2));
+
+        let input = test_signal(3000);
+        let expected = filter.to_iir_filter().unwrap().process_block(&input);
+        for realization in [system.clone(), system.controllable_form().unwrap(), system.observable_form().unwrap()] {
+            assert!(max_difference(&realization.simulate(&input).unwrap(), &expected) < 1e-9);
+        }
+
+        // 分块仿真与整段相同
+        let mut state = vec![0.0; system.order()];
+        let mut blocks = Vec::new();
+        for block in input.chunks(701) {
+            blocks.extend(system.simulate_from(&mut state, block).unwrap());
+        }
+        assert_eq!(blocks, system.simulate(&input).unwrap());
+        assert!(system.simulate_from(&mut vec![0.0; 2], &input).is_err());
+    }
+
+    #[test]
+    fn test_balanced_realization() {
+        let input = test_signal(3000);
+        let filter = IirDesign::highpass(8, 3000.0, 22050.0);
+        let analog = IirDesign::lowpass(4, 400.0, 22050.0).analog().unwrap().state_space().unwrap();
+        for system in [filter.state_space().unwrap(), analog] {
+            // 原实现的 Gramian 满足 Lyapunov / Stein 方程
+            let (p, q) = system.gramians().unwrap();
+            let (residual_p, residual_q) = if system.is_discrete() {
+                (
+                    &(&(&(&system.a * &p) * &system.a.transpose()) - &p) + &(&system.b * &system.b.transpose()),
+                    &(&(&(&system.a.transpose() * &q) * &system.a) - &q) + &(&system.c.transpose() * &system.c),
+                )
+            } else {
+                (
+                    &(&(&system.a * &p) + &(&p * &system.a.transpose())) + &(&system.b * &system.b.transpose()),
+                    &(&(&system.a.transpose() * &q) + &(&q * &system.a)) + &(&system.c.transpose() * &system.c),
+                )
+            };
+            assert!(residual_p.norm_inf() < 1e-9 * p.norm_inf() * system.a.norm_inf().max(1.0));
+            assert!(residual_q.norm_inf() < 1e-9 * q.norm_inf() * system.a.norm_inf().max(1.0));
+
+            // 平衡后两个 Gramian 都等于 diag(σ)
+            let BalancedRealization { system: balanced, hankel_singular_values: sigma } = system.balanced().unwrap();
+            assert!(sigma.windows(2).all(|w| w[0] >= w[1]) && sigma[sigma.len() - 1] > 0.0);
+            let (p, q) = balanced.gramians().unwrap();
+            for i in 0..sigma.len() {
+                for j in 0..sigma.len() {
+                    let expected = if i == j { sigma[i] } else { 0.0 };
+                    assert!((p[(i, j)] - expected).abs() < 1e-8 * sigma[0] && (q[(i, j)] - expected).abs() < 1e-8 * sigma[0]);
+                }
+            }
+            let (reference, output) = match system.sample_rate {
+                Some(_) => (system.simulate(&input).unwrap(), balanced.simulate(&input).unwrap()),
+                None => (
+                    system.discretize(22050.0, Discretization::Zoh).unwrap().simulate(&input).unwrap(),
+                    balanced.discretize(22050.0, Discretization::Zoh).unwrap().simulate(&input).unwrap(),
+                ),
+            };
+            assert!(max_difference(&reference, &output) < 1e-9);
+        }
+
+        // 零极点对消（不可观的状态）无法平衡，不稳定系统没有 Gramian
+        let cancelled = StateSpace::from_sections(&[[1.0, -0.5, 0.0, 1.0, -0.5, 0.0], [0.0, 1.0, 0.0, 0.0, 1.0, -0.3]], Some(1.0)).unwrap();
+        assert_eq!(cancelled.order(), 2);
+        assert!(cancelled.balanced().unwrap_err().contains("最小实现"));
+        let unstable = StateSpace::from_sections(&[[0.0, 1.0, 0.0, 0.0, 1.0, -1.1]], Some(1.0)).unwrap();
+        assert!(unstable.gramians().is_err());
+    }
+
+    #[test]
+    fn test_export() {
+        let system = IirDesign::lowpass(4, 4000.0, 22050.0).state_space().unwrap();
+        let json = serde_json::to_string(&system).unwrap();
+        assert!(json.contains("\"a\":[["));
+        assert_eq!(serde_json::from_str::<StateSpace>(&json).unwrap(), system);
+        assert!(serde_json::from_str::<StateSpace>(r#"{"a":[[1.0]],"b":[[1.0],[2.0]],"c":[[1.0]],"d":[[0.0]]}"#).is_err());
+
+        // 没有状态的纯增益也能往返
+        let gain = StateSpace::gain(2.5, None);
+        assert_eq!(serde_json::from_str::<StateSpace>(&serde_json::to_string(&gain).unwrap()).unwrap(), gain);
+
+        let script = system.to_matlab("lowpass");
+        assert!(script.starts_with("% lowpass: 4-state discrete system"));
+        assert!(script.contains("A = [") && script.contains("lowpass = ss(A, B, C, D, 1 / 2.205e4);"));
+    }
+}
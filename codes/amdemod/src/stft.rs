@@ -0,0 +1,209 @@
+// 短时傅里叶变换模块
+// 语音信号是非平稳的：分帧加窗做 FFT，观察错误解调后的能量如何随时间在频率上移动
+
+use crate::fft_processor::compute_rfft;
+use crate::window::Window;
+use rustfft::num_complex::Complex;
+use serde::{Deserialize, Serialize};
+
+/// STFT 参数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StftConfig {
+    pub window: Window,
+    /// 窗长（采样点数）
+    pub window_length: usize,
+    /// 帧移（采样点数）
+    pub hop: usize,
+    /// 每帧的 FFT 点数，不小于窗长，多出的部分补零
+    pub fft_size: usize,
+}
+
+impl Default for StftConfig {
+    fn default() -> Self {
+        Self {
+            window: Window::Hann,
+            window_length: 1024,
+            hop: 256,
+            fft_size: 1024,
+        }
+    }
+}
+
+impl StftConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.window_length == 0 || self.hop == 0 {
+            return Err(format!(
+                "window_length = {} / hop = {} 必须为正",
+                self.window_length, self.hop
+            ));
+        }
+        if self.fft_size < self.window_length {
+            return Err(format!(
+                "fft_size = {} 小于窗长 {}",
+                self.fft_size, self.window_length
+            ));
+        }
+        self.window.validate(self.window_length)
+    }
+}
+
+/// 时频谱：每帧保留 0 到 Nyquist 频率的单边复数频谱
+#[derive(Debug, Clone)]
+pub struct Spectrogram {
+    pub config: StftConfig,
+    pub sample_rate: f64,
+    /// 各帧中心时刻 (s)
+    pub times: Vec<f64>,
+    /// 频率轴 (Hz)，共 fft_size / 2 + 1 个频点
+    pub frequencies: Vec<f64>,
+    /// frames[t][k]：第 t 帧、第 k 个频点的复数值（已除以窗系数之和）
+    pub frames: Vec<Vec<Complex<f64>>>,
+}
+
+impl Spectrogram {
+    /// 幅度 |X(t, f)|，幅度为 A 的正弦在其频点上约为 A/2
+    pub fn magnitude(&self) -> Vec<Vec<f64>> {
+        self.frames
+            .iter()
+            .map(|frame| frame.iter().map(|c| c.norm()).collect())
+            .collect()
+    }
+
+    /// dB 刻度的幅度 20·log10|X(t, f)|，下限 -200 dB
+    pub fn magnitude_db(&self) -> Vec<Vec<f64>> {
+        self.frames
+            .iter()
+            .map(|frame| {
+                frame
+                    .iter()
+                    .map(|c| {
+                        let m = c.norm();
+                        if m > 1e-10 { 20.0 * m.log10() } else { -200.0 }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// 每帧能量最大的频点频率 (Hz)，频率限制在 [f_min, f_max] 内
+    pub fn peak_track(&self, f_min: f64, f_max: f64) -> Vec<f64> {
+        self.frames
+            .iter()
+            .map(|frame| {
+                frame
+                    .iter()
+                    .zip(&self.frequencies)
+                    .filter(|(_, &f)| f >= f_min && f <= f_max)
+                    .max_by(|a, b| a.0.norm().total_cmp(&b.0.norm()))
+                    .map_or(f64::NAN, |(_, &f)| f)
+            })
+            .collect()
+    }
+}
+
+/// 计算 STFT
+///
+/// 第 t 帧取 samples[t·hop .. t·hop + window_length]，末尾不足一帧的部分补零，
+/// 保证每个采样点都至少落在一帧内。
+pub fn compute_stft(samples: &[f64], sample_rate: f64, config: &StftConfig) -> Result<Spectrogram, String> {
+    config.validate()?;
+    if samples.is_empty() {
+        return Err("信号为空".to_string());
+    }
+
+    let window = config.window.coefficients(config.window_length);
+    // 幅度修正：除以 Σw = N·相干增益
+    let gain: f64 = window.iter().sum();
+    let bins = config.fft_size / 2 + 1;
+    let num_frames = if samples.len() > config.window_length {
+        (samples.len() - config.window_length).div_ceil(config.hop) + 1
+    } else {
+        1
+    };
+
+    let mut buffer = vec![0.0; config.fft_size];
+    let frames: Vec<Vec<Complex<f64>>> = (0..num_frames)
+        .map(|t| {
+            let start = t * config.hop;
+            buffer.iter_mut().for_each(|x| *x = 0.0);
+            for (i, (value, w)) in buffer.iter_mut().zip(&window).enumerate() {
+                *value = samples.get(start + i).copied().unwrap_or(0.0) * w;
+            }
+            compute_rfft(&buffer)
+                .into_iter()
+                .map(|c| c / gain)
+                .collect()
+        })
+        .collect();
+
+    let times = (0..num_frames)
+        .map(|t| (t * config.hop) as f64 / sample_rate + config.window_length as f64 / (2.0 * sample_rate))
+        .collect();
+    let frequencies = (0..bins)
+        .map(|k| k as f64 * sample_rate / config.fft_size as f64)
+        .collect();
+
+    Ok(Spectrogram {
+        config: *config,
+        sample_rate,
+        times,
+        frequencies,
+        frames,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_stft_follows_frequency_step() {
+        // 前 0.5 s 为 1000 Hz，后 0.5 s 为 3000 Hz
+        let fs = 8000.0;
+        let samples: Vec<f64> = (0..8000)
+            .map(|i| {
+                let f = if i < 4000 { 1000.0 } else { 3000.0 };
+                (2.0 * PI * f * i as f64 / fs).cos()
+            })
+            .collect();
+        let config = StftConfig {
+            window_length: 256,
+            hop: 128,
+            fft_size: 512,
+            ..StftConfig::default()
+        };
+        let spec = compute_stft(&samples, fs, &config).unwrap();
+
+        assert_eq!(spec.frequencies.len(), 257);
+        assert_eq!(spec.frames.len(), spec.times.len());
+        // 最后一帧覆盖到信号末尾
+        let last_start = (spec.frames.len() - 1) * config.hop;
+        assert!(last_start + config.window_length >= samples.len());
+
+        let track = spec.peak_track(0.0, fs / 2.0);
+        for (&t, &f) in spec.times.iter().zip(&track) {
+            if t < 0.45 {
+                assert!((f - 1000.0).abs() < 20.0);
+            } else if t > 0.55 {
+                assert!((f - 3000.0).abs() < 20.0);
+            }
+        }
+
+        // 正弦幅度 1，窗增益修正后频点上的幅度约为 1/2
+        let magnitude = spec.magnitude();
+        let peak = magnitude[2].iter().cloned().fold(0.0, f64::max);
+        assert!((peak - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = StftConfig {
+            fft_size: 512,
+            window_length: 1024,
+            ..StftConfig::default()
+        };
+        assert!(compute_stft(&[0.0; 2048], 8000.0, &config).is_err());
+        assert!(compute_stft(&[], 8000.0, &StftConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,573 @@
+// 窗函数模块
+// 分帧分析（STFT、功率谱估计等）使用的窗函数、窗的增益与修正系数，以及多窗谱估计的离散长球序列（DPSS）
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+/// 窗函数类型
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Window {
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+    /// 四项 Blackman-Harris 窗（旁瓣 -92 dB）
+    BlackmanHarris,
+    /// 五项平顶窗，幅度测量的扇贝损失最小
+    FlatTop,
+    /// Kaiser 窗，参数为 β
+    Kaiser(f64),
+    /// Tukey（余弦锥削）窗，参数为锥削部分占比 α ∈ [0, 1]：0 为矩形窗，1 为 Hann 窗
+    Tukey(f64),
+    /// Gaussian 窗，参数为标准差 σ 与半窗长 (N-1)/2 之比
+    Gaussian(f64),
+    /// 0 阶 DPSS 窗（峰值归一化为 1），参数为时间半带宽积 NW
+    Dpss(f64),
+}
+
+/// 窗的对称性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Symmetry {
+    /// w[n] = w[N-1-n]，用于滤波器设计与分段平均
+    Symmetric,
+    /// 取长 N+1 的对称窗的前 N 点，N 点周期延拓后无重复端点，用于频谱分析
+    Periodic,
+}
+
+/// 窗的增益与修正系数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGains {
+    /// 窗长 N
+    pub length: usize,
+    /// 相干增益 Σw / N：正弦的幅度被乘以该值
+    pub coherent_gain: f64,
+    /// 功率增益 Σw² / N：宽带信号的功率被乘以该值
+    pub power_gain: f64,
+    /// 等效噪声带宽 N·Σw² / (Σw)²（频点数）
+    pub enbw_bins: f64,
+    /// 频率恰在两个频点正中时的幅度损失 (dB)
+    pub scalloping_loss_db: f64,
+    /// 幅度修正系数 1 / 相干增益：修正后正弦的峰值幅度正确
+    pub amplitude_correction: f64,
+    /// 能量修正系数 1 / √功率增益：修正后宽带信号的总能量正确
+    pub energy_correction: f64,
+}
+
+impl WindowGains {
+    /// 由窗系数计算增益
+    pub fn from_coefficients(w: &[f64]) -> Self {
+        let n = w.len() as f64;
+        let sum: f64 = w.iter().sum();
+        let sum_sq: f64 = w.iter().map(|x| x * x).sum();
+        let coherent_gain = sum / n;
+        let power_gain = sum_sq / n;
+        // 半个频点处的 DTFT：|Σ w[k]·e^{-jπk/N}| / Σw
+        let (re, im) = w.iter().enumerate().fold((0.0, 0.0), |(re, im), (k, &x)| {
+            let phase = PI * k as f64 / n;
+            (re + x * phase.cos(), im - x * phase.sin())
+        });
+        WindowGains {
+            length: w.len(),
+            coherent_gain,
+            power_gain,
+            enbw_bins: n * sum_sq / (sum * sum),
+            scalloping_loss_db: -20.0 * ((re * re + im * im).sqrt() / sum).log10(),
+            amplitude_correction: 1.0 / coherent_gain,
+            energy_correction: 1.0 / power_gain.sqrt(),
+        }
+    }
+
+    /// 等效噪声带宽 (Hz)
+    pub fn enbw(&self, sample_rate: f64) -> f64 {
+        self.enbw_bins * sample_rate / self.length as f64
+    }
+}
+
+/// 频谱的修正方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Correction {
+    /// 除以相干增益：读出正弦分量的幅度
+    Amplitude,
+    /// 除以 √功率增益：满足 Parseval 关系，读出能量与功率
+    Energy,
+}
+
+impl Correction {
+    /// 对给定窗的修正系数
+    pub fn factor(&self, gains: &WindowGains) -> f64 {
+        match self {
+            Correction::Amplitude => gains.amplitude_correction,
+            Correction::Energy => gains.energy_correction,
+        }
+    }
+}
+
+impl Window {
+    /// 全部窗类型，参数取常用值
+    pub fn all() -> Vec<Window> {
+        vec![
+            Window::Rectangular,
+            Window::Hann,
+            Window::Hamming,
+            Window::Blackman,
+            Window::BlackmanHarris,
+            Window::FlatTop,
+            Window::Kaiser(8.6),
+            Window::Tukey(0.5),
+            Window::Gaussian(0.4),
+            Window::Dpss(3.0),
+        ]
+    }
+
+    /// 检查窗参数对长度 len 是否有效
+    pub fn validate(&self, len: usize) -> Result<(), String> {
+        let valid = match *self {
+            Window::Kaiser(beta) => beta.is_finite() && beta >= 0.0,
+            Window::Tukey(alpha) => (0.0..=1.0).contains(&alpha),
+            Window::Gaussian(sigma) => sigma.is_finite() && sigma > 0.0,
+            Window::Dpss(nw) => len < 2 || (nw > 0.0 && nw < len as f64 / 2.0),
+            _ => true,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("窗 {} 的参数对长度 {} 无效", self, len))
+        }
+    }
+
+    /// 长度为 len 的对称窗系数，Hann、Hamming 与 `FftResult::apply_hanning_window` 等的定义一致
+    ///
+    /// 参数无效时 panic，调用前先用 [`Self::validate`] 检查
+    pub fn coefficients(&self, len: usize) -> Vec<f64> {
+        if len <= 1 {
+            return vec![1.0; len];
+        }
+        if let Err(e) = self.validate(len) {
+            panic!("{}", e);
+        }
+        let denom = (len - 1) as f64;
+        match *self {
+            Window::Dpss(nw) => {
+                let taper = dpss(len, nw, 1).expect("参数已检查").remove(0);
+                let peak = taper.iter().fold(0.0f64, |a, &b| a.max(b));
+                taper.into_iter().map(|x| x / peak).collect()
+            }
+            Window::Kaiser(beta) => {
+                let norm = bessel_i0(beta);
+                (0..len)
+                    .map(|i| {
+                        let r = 2.0 * i as f64 / denom - 1.0;
+                        bessel_i0(beta * (1.0 - r * r).max(0.0).sqrt()) / norm
+                    })
+                    .collect()
+            }
+            Window::Tukey(alpha) => {
+                let taper = alpha * denom / 2.0;
+                (0..len)
+                    .map(|i| {
+                        // 到最近端点的距离
+                        let edge = (i as f64).min(denom - i as f64);
+                        if edge >= taper {
+                            1.0
+                        } else {
+                            0.5 * (1.0 + (PI * (edge / taper - 1.0)).cos())
+                        }
+                    })
+                    .collect()
+            }
+            Window::Gaussian(sigma) => {
+                let half = denom / 2.0;
+                (0..len)
+                    .map(|i| (-0.5 * ((i as f64 - half) / (sigma * half)).powi(2)).exp())
+                    .collect()
+            }
+            _ => (0..len)
+                .map(|i| {
+                    let x = 2.0 * PI * i as f64 / denom;
+                    match self {
+                        Window::Hann => 0.5 * (1.0 - x.cos()),
+                        Window::Hamming => 0.54 - 0.46 * x.cos(),
+                        Window::Blackman => 0.42 - 0.5 * x.cos() + 0.08 * (2.0 * x).cos(),
+                        Window::BlackmanHarris => {
+                            0.35875 - 0.48829 * x.cos() + 0.14128 * (2.0 * x).cos() - 0.01168 * (3.0 * x).cos()
+                        }
+                        Window::FlatTop => {
+                            0.21557895 - 0.41663158 * x.cos() + 0.277263158 * (2.0 * x).cos()
+                                - 0.083578947 * (3.0 * x).cos()
+                                + 0.006947368 * (4.0 * x).cos()
+                        }
+                        _ => 1.0,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// 指定对称性的窗系数
+    pub fn generate(&self, len: usize, symmetry: Symmetry) -> Vec<f64> {
+        match symmetry {
+            Symmetry::Symmetric => self.coefficients(len),
+            Symmetry::Periodic => {
+                let mut w = self.coefficients(len + 1);
+                w.truncate(len);
+                w
+            }
+        }
+    }
+
+    /// 按 `generate(len, symmetry)` 实际计算的长度检查参数：周期窗由长 len + 1 的对称窗截取
+    pub fn validate_generated(&self, len: usize, symmetry: Symmetry) -> Result<(), String> {
+        match symmetry {
+            Symmetry::Symmetric => self.validate(len),
+            Symmetry::Periodic => self.validate(len + 1),
+        }
+    }
+
+    /// 指定长度与对称性时窗的增益
+    pub fn gains(&self, len: usize, symmetry: Symmetry) -> WindowGains {
+        WindowGains::from_coefficients(&self.generate(len, symmetry))
+    }
+}
+
+impl fmt::Display for Window {
+    /// 与 [`FromStr`] 互逆，如 `hann`、`kaiser:8.6`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Window::Rectangular => write!(f, "rectangular"),
+            Window::Hann => write!(f, "hann"),
+            Window::Hamming => write!(f, "hamming"),
+            Window::Blackman => write!(f, "blackman"),
+            Window::BlackmanHarris => write!(f, "blackman_harris"),
+            Window::FlatTop => write!(f, "flat_top"),
+            Window::Kaiser(beta) => write!(f, "kaiser:{}", beta),
+            Window::Tukey(alpha) => write!(f, "tukey:{}", alpha),
+            Window::Gaussian(sigma) => write!(f, "gaussian:{}", sigma),
+            Window::Dpss(nw) => write!(f, "dpss:{}", nw),
+        }
+    }
+}
+
+impl FromStr for Window {
+    type Err = String;
+
+    /// 解析窗名，带参数的窗写作 `名称:参数`（如 `tukey:0.25`），省略参数时取 [`Window::all`] 中的常用值；
+    /// 名称中的 `-` 与 `_` 等价
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, parameter) = match s.split_once(':') {
+            Some((name, value)) => {
+                let value = value
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|e| format!("窗参数 '{}' 无效: {}", value, e))?;
+                (name, Some(value))
+            }
+            None => (s, None),
+        };
+        let name = name.trim().to_ascii_lowercase().replace('-', "_");
+        let window = match (name.as_str(), parameter) {
+            ("rectangular" | "rect" | "boxcar", None) => Window::Rectangular,
+            ("hann" | "hanning", None) => Window::Hann,
+            ("hamming", None) => Window::Hamming,
+            ("blackman", None) => Window::Blackman,
+            ("blackman_harris", None) => Window::BlackmanHarris,
+            ("flat_top" | "flattop", None) => Window::FlatTop,
+            ("kaiser", beta) => Window::Kaiser(beta.unwrap_or(8.6)),
+            ("tukey", alpha) => Window::Tukey(alpha.unwrap_or(0.5)),
+            ("gaussian", sigma) => Window::Gaussian(sigma.unwrap_or(0.4)),
+            ("dpss", nw) => Window::Dpss(nw.unwrap_or(3.0)),
+            (_, Some(_)) => return Err(format!("窗 '{}' 不带参数", name)),
+            _ => return Err(format!("未知的窗 '{}'", name)),
+        };
+        window.validate(usize::MAX)?;
+        Ok(window)
+    }
+}
+
+/// 第一类零阶修正 Bessel 函数 I₀(x)，级数求和
+fn bessel_i0(x: f64) -> f64 {
+    let q = x * x / 4.0;
+    let (mut sum, mut term, mut k) = (1.0, 1.0, 1.0);
+    while term > 1e-17 * sum {
+        term *= q / (k * k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
+
+/// 离散长球序列（Slepian 序列）的前 k 阶，每阶长 len、能量归一化为 1
+///
+/// 时间半带宽积 nw = N·W：序列是带宽 |f| ≤ W（归一化频率）内能量集中度最高的 k 个正交序列，
+/// 等价于三对角矩阵 T 的最大 k 个特征向量，其中
+/// T[n][n] = ((N-1-2n)/2)²·cos(2πW)，T[n][n-1] = T[n-1][n] = n(N-n)/2。
+/// 用 Sturm 序列二分求特征值，再做反迭代求特征向量。
+/// 符号约定同 scipy：偶数阶序列之和为正，奇数阶序列第一个显著的值为正。
+pub fn dpss(len: usize, nw: f64, k: usize) -> Result<Vec<Vec<f64>>, String> {
+    if len < 2 || k == 0 || k > len {
+        return Err(format!("DPSS 参数无效：len = {}，k = {}", len, k));
+    }
+    if !(nw > 0.0 && nw < len as f64 / 2.0) {
+        return Err(format!("时间半带宽积 NW = {} 须在 (0, {}) 内", nw, len as f64 / 2.0));
+    }
+
+    let n = len as f64;
+    let w = nw / n;
+    let diag: Vec<f64> = (0..len)
+        .map(|i| ((n - 1.0 - 2.0 * i as f64) / 2.0).powi(2) * (2.0 * PI * w).cos())
+        .collect();
+    // off[i] 为 T[i][i+1]
+    let off: Vec<f64> = (1..len).map(|i| i as f64 * (n - i as f64) / 2.0).collect();
+
+    // Gershgorin 圆盘给出特征值的范围
+    let (mut lower, mut upper) = (f64::INFINITY, f64::NEG_INFINITY);
+    for (i, &d) in diag.iter().enumerate() {
+        let radius = i.checked_sub(1).map_or(0.0, |j| off[j].abs()) + off.get(i).map_or(0.0, |e| e.abs());
+        lower = lower.min(d - radius);
+        upper = upper.max(d + radius);
+    }
+    let scale = lower.abs().max(upper.abs()).max(1.0);
+
+    // 小于 x 的特征值个数
+    let count_below = |x: f64| -> usize {
+        let mut count = 0;
+        let mut q = 1.0;
+        for i in 0..len {
+            let coupling = if i == 0 { 0.0 } else { off[i - 1] * off[i - 1] / q };
+            q = diag[i] - x - coupling;
+            if q == 0.0 {
+                q = -f64::EPSILON * scale;
+            }
+            if q < 0.0 {
+                count += 1;
+            }
+        }
+        count
+    };
+
+    let tapers = (0..k)
+        .map(|order| {
+            // 升序第 index 个特征值
+            let index = len - 1 - order;
+            let (mut lo, mut hi) = (lower, upper);
+            for _ in 0..200 {
+                let mid = 0.5 * (lo + hi);
+                if mid <= lo || mid >= hi {
+                    break;
+                }
+                if count_below(mid) > index {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            let lambda = 0.5 * (lo + hi);
+
+            let mut v = inverse_iteration(&diag, &off, lambda, scale);
+            let sign = if order % 2 == 0 {
+                v.iter().sum::<f64>()
+            } else {
+                let threshold = (1.0 / n).max(1e-7);
+                v.iter().copied().find(|x| x.abs() > threshold).unwrap_or(1.0)
+            };
+            if sign < 0.0 {
+                v.iter_mut().for_each(|x| *x = -*x);
+            }
+            v
+        })
+        .collect();
+    Ok(tapers)
+}
+
+/// 对称三对角矩阵（对角 diag、次对角 off）关于特征值 lambda 的反迭代
+///
+/// (T - λI) 用部分选主元的 LU 分解（同 LAPACK dgttrf / dgttrs）求解，迭代三次
+fn inverse_iteration(diag: &[f64], off: &[f64], lambda: f64, scale: f64) -> Vec<f64> {
+    let len = diag.len();
+    let tiny = f64::EPSILON * scale;
+
+    // 分解：U 的对角 d、第一、第二上对角 du、du2，乘子 dl，swap[i] 表示第 i、i+1 行互换
+    let mut d: Vec<f64> = diag.iter().map(|x| x - lambda).collect();
+    let mut du = off.to_vec();
+    let mut du2 = vec![0.0; len.saturating_sub(2)];
+    let mut dl = off.to_vec();
+    let mut swap = vec![false; len - 1];
+    for i in 0..len - 1 {
+        if d[i].abs() >= dl[i].abs() {
+            if d[i] == 0.0 {
+                d[i] = tiny;
+            }
+            let factor = dl[i] / d[i];
+            dl[i] = factor;
+            d[i + 1] -= factor * du[i];
+        } else {
+            let factor = d[i] / dl[i];
+            d[i] = dl[i];
+            dl[i] = factor;
+            let temp = du[i];
+            du[i] = d[i + 1];
+            d[i + 1] = temp - factor * d[i + 1];
+            if i + 2 < len {
+                du2[i] = du[i + 1];
+                du[i + 1] *= -factor;
+            }
+            swap[i] = true;
+        }
+    }
+    if d[len - 1] == 0.0 {
+        d[len - 1] = tiny;
+    }
+
+    // 确定性的初始向量，同时含对称与反对称分量
+    let mut v: Vec<f64> = (0..len).map(|i| 1.0 + ((i * 7919) % 104_729) as f64 / 104_729.0).collect();
+    for _ in 0..3 {
+        for i in 0..len - 1 {
+            if swap[i] {
+                let temp = v[i];
+                v[i] = v[i + 1];
+                v[i + 1] = temp - dl[i] * v[i];
+            } else {
+                v[i + 1] -= dl[i] * v[i];
+            }
+        }
+        for i in (0..len).rev() {
+            let mut x = v[i];
+            if i + 1 < len {
+                x -= du[i] * v[i + 1];
+            }
+            if i + 2 < len {
+                x -= du2[i] * v[i + 2];
+            }
+            v[i] = x / d[i];
+        }
+        let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft_processor::FftResult;
+
+    #[test]
+    fn test_matches_fft_processor_windows() {
+        let ones = vec![1.0; 16];
+        assert_eq!(Window::Hann.coefficients(16), FftResult::apply_hanning_window(&ones));
+        assert_eq!(Window::Hamming.coefficients(16), FftResult::apply_hamming_window(&ones));
+        assert_eq!(Window::Rectangular.coefficients(3), vec![1.0; 3]);
+    }
+
+    #[test]
+    fn test_dpss_orthonormal_eigenvectors() {
+        let (len, nw, k) = (128, 4.0, 7);
+        let tapers = dpss(len, nw, k).unwrap();
+        assert_eq!(tapers.len(), k);
+
+        for (i, a) in tapers.iter().enumerate() {
+            for (j, b) in tapers.iter().enumerate() {
+                let dot: f64 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((dot - expected).abs() < 1e-9, "<v{}, v{}> = {}", i, j, dot);
+            }
+            // 偶数阶对称、奇数阶反对称
+            let parity = if i % 2 == 0 { 1.0 } else { -1.0 };
+            for n in 0..len {
+                assert!((a[n] - parity * a[len - 1 - n]).abs() < 1e-9);
+            }
+        }
+
+        // 第 0 阶是 T 的特征向量：T·v 与 v 平行
+        let w = nw / len as f64;
+        let v = &tapers[0];
+        let tv: Vec<f64> = (0..len)
+            .map(|n| {
+                let nf = n as f64;
+                let mut x = ((len as f64 - 1.0 - 2.0 * nf) / 2.0).powi(2) * (2.0 * PI * w).cos() * v[n];
+                if n > 0 {
+                    x += nf * (len as f64 - nf) / 2.0 * v[n - 1];
+                }
+                if n + 1 < len {
+                    x += (nf + 1.0) * (len as f64 - nf - 1.0) / 2.0 * v[n + 1];
+                }
+                x
+            })
+            .collect();
+        let lambda: f64 = tv.iter().zip(v).map(|(x, y)| x * y).sum();
+        let residual = tv.iter().zip(v).map(|(x, y)| (x - lambda * y).powi(2)).sum::<f64>().sqrt();
+        assert!(residual < 1e-9 * lambda.abs());
+        assert!(v.iter().sum::<f64>() > 0.0);
+
+        assert!(dpss(128, 70.0, 3).is_err());
+    }
+
+    #[test]
+    fn test_gains_of_standard_windows() {
+        // (窗, ENBW 频点数, 扇贝损失 dB)，N 较大时的周期窗理论值
+        let expected = [
+            (Window::Rectangular, 1.0, 3.92),
+            (Window::Hann, 1.5, 1.42),
+            (Window::Hamming, 1.363, 1.75),
+            (Window::Blackman, 1.727, 1.10),
+            (Window::BlackmanHarris, 2.004, 0.83),
+            (Window::FlatTop, 3.770, 0.0),
+        ];
+        for (window, enbw, scalloping) in expected {
+            let gains = window.gains(4096, Symmetry::Periodic);
+            assert!((gains.enbw_bins - enbw).abs() < 5e-3, "{}: ENBW {}", window, gains.enbw_bins);
+            assert!(
+                (gains.scalloping_loss_db - scalloping).abs() < 0.03,
+                "{}: scalloping {}",
+                window,
+                gains.scalloping_loss_db
+            );
+            assert!((gains.amplitude_correction * gains.coherent_gain - 1.0).abs() < 1e-12);
+        }
+        assert!((Window::Hann.gains(4096, Symmetry::Periodic).coherent_gain - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_symmetry_and_limiting_cases() {
+        for window in Window::all() {
+            let w = window.coefficients(65);
+            for n in 0..65 {
+                assert!((w[n] - w[64 - n]).abs() < 1e-9, "{} 不对称", window);
+            }
+            let peak = w.iter().fold(0.0f64, |a, &b| a.max(b));
+            assert!((peak - 1.0).abs() < 1e-3, "{}: peak {}", window, peak);
+
+            // 周期窗 = 长 N+1 对称窗的前 N 点
+            let periodic = window.generate(64, Symmetry::Periodic);
+            assert_eq!(periodic[..], w[..64]);
+        }
+
+        let rect = Window::Rectangular.coefficients(33);
+        let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-12);
+        assert!(close(&Window::Kaiser(0.0).coefficients(33), &rect));
+        assert!(close(&Window::Tukey(0.0).coefficients(33), &rect));
+        assert!(close(&Window::Tukey(1.0).coefficients(33), &Window::Hann.coefficients(33)));
+        assert!(close(&Window::Hann.coefficients(5), &[0.0, 0.5, 1.0, 0.5, 0.0]));
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for window in Window::all() {
+            assert_eq!(window.to_string().parse::<Window>().unwrap(), window);
+        }
+        assert_eq!("Blackman-Harris".parse::<Window>().unwrap(), Window::BlackmanHarris);
+        assert_eq!("kaiser".parse::<Window>().unwrap(), Window::Kaiser(8.6));
+        assert_eq!("tukey:0.25".parse::<Window>().unwrap(), Window::Tukey(0.25));
+        assert!("tukey:1.5".parse::<Window>().is_err());
+        assert!("hann:2".parse::<Window>().is_err());
+        assert!("parzen".parse::<Window>().is_err());
+        assert!(Window::Dpss(40.0).validate(64).is_err());
+        // 长 1 的周期窗由长 2 的对称窗截取，NW 须小于 1
+        assert!(Window::Dpss(2.5).validate(1).is_ok());
+        assert!(Window::Dpss(2.5).validate_generated(1, Symmetry::Periodic).is_err());
+    }
+}
@@ -0,0 +1,655 @@
+// 零极点增益表示模块
+// 传递函数 (b, a)、零极点增益 (z, p, k) 与二阶节 (sos) 三种表示之间的转换，
+// 以及模拟原型到数字滤波器的双线性变换。与 MATLAB tf2zp / zp2tf / zp2sos / sos2zp 对应
+
+use crate::iir_filter::SosSection;
+use num_complex::Complex;
+
+/// 判定根为实数、两根互为共轭时使用的相对容差
+const CONJUGATE_TOL: f64 = 1e-8;
+
+/// 零极点增益表示 H(z) = k·Π(z - z_i) / Π(z - p_i)
+///
+/// 数字滤波器的零点少于极点时，缺少的零点位于 z = ∞（对应 z^{-1} 的纯时延）；
+/// 模拟滤波器同样以 s 为变量，零点少于极点即在 s = ∞ 处有零点
+#[derive(Debug, Clone, PartialEq)]
+pub struct Zpk {
+    pub zeros: Vec<Complex<f64>>,
+    pub poles: Vec<Complex<f64>>,
+    pub gain: f64,
+}
+
+impl Zpk {
+    pub fn new(zeros: Vec<Complex<f64>>, poles: Vec<Complex<f64>>, gain: f64) -> Self {
+        Self { zeros, poles, gain }
+    }
+
+    /// 由 z^{-1} 的升幂系数 b、a 求零极点（tf2zp）
+    ///
+    /// 对多重根（如 Butterworth 在 z = -1 处的 N 重零点）求根本身是病态的，
+    /// 误差约为 ε^{1/N}；已知零极点时应直接构造 `Zpk`，不要经过传递函数
+    pub fn from_tf(b: &[f64], a: &[f64]) -> Result<Self, String> {
+        let a0 = a.first().copied().unwrap_or(0.0);
+        if a0 == 0.0 {
+            return Err("a[0] 不能为零".to_string());
+        }
+        let b_lead = b.iter().copied().find(|&v| v != 0.0).ok_or("分子 b 全为零")?;
+        // 补齐到同样长度后 b、a 都是 z 的降幂系数
+        let len = b.len().max(a.len());
+        let mut b = b.to_vec();
+        let mut a = a.to_vec();
+        b.resize(len, 0.0);
+        a.resize(len, 0.0);
+        Ok(Self {
+            zeros: roots(&b),
+            poles: roots(&a),
+            gain: b_lead / a0,
+        })
+    }
+
+    /// 展开为 z^{-1} 的升幂系数 (b, a)（zp2tf），两者等长
+    pub fn to_tf(&self) -> (Vec<f64>, Vec<f64>) {
+        let len = self.zeros.len().max(self.poles.len()) + 1;
+        let expand = |roots: &[Complex<f64>], scale: f64| {
+            let p = poly(roots);
+            let mut coefficients = vec![0.0; len - p.len()];
+            coefficients.extend(p.iter().map(|c| c.re * scale));
+            coefficients
+        };
+        (expand(&self.zeros, self.gain), expand(&self.poles, 1.0))
+    }
+
+    /// 由二阶节级联求零极点（sos2zp），各节的零极点合并，增益相乘
+    pub fn from_sos(sections: &[SosSection]) -> Result<Self, String> {
+        if sections.is_empty() {
+            return Err("二阶节列表不能为空".to_string());
+        }
+        let mut zpk = Self::new(Vec::new(), Vec::new(), 1.0);
+        for (i, section) in sections.iter().enumerate() {
+            let part = Self::from_tf(&section[..3], &section[3..]).map_err(|e| format!("第 {} 节：{}", i + 1, e))?;
+            zpk.zeros.extend(part.zeros);
+            zpk.poles.extend(part.poles);
+            zpk.gain *= part.gain;
+        }
+        // 一阶节补出的原点零极点相互抵消
+        while let (Some(zi), Some(pi)) = (
+            zpk.zeros.iter().position(|z| *z == Complex::new(0.0, 0.0)),
+            zpk.poles.iter().position(|p| *p == Complex::new(0.0, 0.0)),
+        ) {
+            zpk.zeros.swap_remove(zi);
+            zpk.poles.swap_remove(pi);
+        }
+        Ok(zpk)
+    }
+
+    /// 分组为二阶节（zp2sos，"nearest" 配对）
+    ///
+    /// 每次取离单位圆最近的极点（及其共轭或最近的另一个实极点），与离它最近的零点配成一节；
+    /// 离单位圆越近的极点所在的节排得越靠后，增益放在第一节的分子上。
+    /// 零点、极点数为奇数时在原点补一个零点 / 极点，复根必须成对共轭
+    pub fn to_sos(&self) -> Result<Vec<SosSection>, String> {
+        let count = self.zeros.len().max(self.poles.len());
+        if count == 0 {
+            return Ok(vec![[self.gain, 0.0, 0.0, 1.0, 0.0, 0.0]]);
+        }
+        let count = count + count % 2;
+        let pad = |roots: &[Complex<f64>]| {
+            let mut padded = roots.to_vec();
+            padded.resize(count, Complex::new(0.0, 0.0));
+            padded
+        };
+        let mut poles = split_conjugates(&pad(&self.poles)).map_err(|e| format!("极点{}", e))?;
+        let mut zeros = split_conjugates(&pad(&self.zeros)).map_err(|e| format!("零点{}", e))?;
+
+        let mut sections = Vec::with_capacity(count / 2);
+        while !poles.is_empty() {
+            // 离单位圆最近的极点
+            let (index, _) = poles
+                .iter()
+                .enumerate()
+                .min_by(|a, b| distance_to_unit_circle(a.1.root()).total_cmp(&distance_to_unit_circle(b.1.root())))
+                .expect("极点非空");
+            let first = poles.swap_remove(index);
+            let pole_pair = match first {
+                Root::Pair(p) => [p, p.conj()],
+                Root::Real(p) => {
+                    let other = take_nearest_real(&mut poles, p).expect("实极点个数为偶数");
+                    [Complex::new(p, 0.0), other]
+                }
+            };
+            let anchor = pole_pair[0];
+            let zero_pair = match take_nearest_root(&mut zeros, anchor) {
+                Root::Pair(z) => [z, z.conj()],
+                Root::Real(z) => {
+                    let other = take_nearest_real(&mut zeros, pole_pair[1].re).expect("实零点个数为偶数");
+                    [Complex::new(z, 0.0), other]
+                }
+            };
+            sections.push(quadratic_section(zero_pair, pole_pair));
+        }
+
+        sections.reverse();
+        for v in &mut sections[0][..3] {
+            *v *= self.gain;
+        }
+        Ok(sections)
+    }
+
+    /// 模拟低通 → 低通：截止角频率由 1 rad/s 移到 wo（lp2lp）
+    pub fn lp2lp(&self, wo: f64) -> Self {
+        let degree = self.poles.len() as i32 - self.zeros.len() as i32;
+        Self {
+            zeros: self.zeros.iter().map(|z| z * wo).collect(),
+            poles: self.poles.iter().map(|p| p * wo).collect(),
+            gain: self.gain * wo.powi(degree),
+        }
+    }
+
+    /// 模拟低通 → 高通：s → wo / s（lp2hp），原型在 ∞ 处的零点移到原点
+    pub fn lp2hp(&self, wo: f64) -> Self {
+        let degree = self.poles.len().saturating_sub(self.zeros.len());
+        let mut zeros: Vec<Complex<f64>> = self.zeros.iter().map(|z| wo / z).collect();
+        zeros.extend(std::iter::repeat_n(Complex::new(0.0, 0.0), degree));
+        let gain = self.gain * pairwise_ratio(&self.zeros, &self.poles, Complex::new(0.0, 0.0), 1.0).re;
+        Self {
+            zeros,
+            poles: self.poles.iter().map(|p| wo / p).collect(),
+            gain,
+        }
+    }
+
+    /// 模拟零极点分组为 s 的二阶节（scipy `zpk2sos(..., analog=True)`）
+    ///
+    /// 每节 [b0, b1, b2, a0, a1, a2] 依次为 s²、s、1 的系数。极点数为奇数时有一节是一阶的（a0 = 0），
+    /// 零点少于极点时缺少的零点位于 s = ∞（该节分子的高次系数为零）。复零点对优先配给最近的二次节，
+    /// 离虚轴最近（阻尼最小）的极点所在的节排在最后。
+    /// 增益按节数开方平均分给各节（符号放在第一节），避免高阶时第一节的系数达到 wc^N 量级
+    pub fn to_analog_sos(&self) -> Result<Vec<SosSection>, String> {
+        self.to_analog_sos_log_gain(self.gain.abs().ln())
+    }
+
+    /// 同 [`Zpk::to_analog_sos`]，但 |k| 以 ln|k| 给出，符号取自 gain 字段
+    ///
+    /// 高阶模拟滤波器的 k ≈ wc^N 超出 f64 范围时，各节分到的 |k|^{1/节数} 仍是有限值
+    pub fn to_analog_sos_log_gain(&self, log_gain: f64) -> Result<Vec<SosSection>, String> {
+        if self.zeros.len() > self.poles.len() {
+            return Err(format!(
+                "零点数 {} 多于极点数 {}，H(s) 不是真有理函数",
+                self.zeros.len(),
+                self.poles.len()
+            ));
+        }
+        if self.poles.is_empty() {
+            return Ok(vec![[0.0, 0.0, log_gain.exp().copysign(self.gain), 0.0, 0.0, 1.0]]);
+        }
+        let mut poles = split_conjugates(&self.poles).map_err(|e| format!("极点{}", e))?;
+        let zeros = split_conjugates(&self.zeros).map_err(|e| format!("零点{}", e))?;
+
+        // 先按极点分节：共轭对、两个实极点或最后剩下的一个实极点
+        let damping = |p: Complex<f64>| if p.norm() == 0.0 { 0.0 } else { p.re.abs() / p.norm() };
+        let mut groups: Vec<RootGroup> = Vec::new();
+        while !poles.is_empty() {
+            let (index, _) = poles
+                .iter()
+                .enumerate()
+                .min_by(|a, b| damping(a.1.root()).total_cmp(&damping(b.1.root())))
+                .expect("极点非空");
+            let pole_group = match poles.swap_remove(index) {
+                Root::Pair(p) => vec![p, p.conj()],
+                Root::Real(p) => match take_nearest_real(&mut poles, p) {
+                    Some(other) => vec![Complex::new(p, 0.0), other],
+                    None => vec![Complex::new(p, 0.0)],
+                },
+            };
+            groups.push(RootGroup { poles: pole_group, zeros: Vec::new() });
+        }
+
+        let nearest_group = |groups: &[RootGroup], zero: Complex<f64>, needed: usize| {
+            groups
+                .iter()
+                .enumerate()
+                .filter(|(_, g)| g.poles.len() - g.zeros.len() >= needed)
+                .min_by(|a, b| (a.1.poles[0] - zero).norm().total_cmp(&(b.1.poles[0] - zero).norm()))
+                .map(|(i, _)| i)
+        };
+        let (pairs, reals): (Vec<Root>, Vec<Root>) = zeros.into_iter().partition(|z| matches!(z, Root::Pair(_)));
+        for zero in pairs.iter().chain(&reals) {
+            let roots = match *zero {
+                Root::Pair(z) => vec![z, z.conj()],
+                Root::Real(z) => vec![Complex::new(z, 0.0)],
+            };
+            let index = nearest_group(&groups, roots[0], roots.len())
+                .ok_or_else(|| format!("零点 {:.6}{:+.6}j 无法分配到任何一节", roots[0].re, roots[0].im))?;
+            groups[index].zeros.extend(roots);
+        }
+
+        groups.reverse();
+        let scale = (log_gain / groups.len() as f64).exp();
+        let mut sections: Vec<SosSection> = groups
+            .iter()
+            .map(|group| {
+                let pad = |roots: &[Complex<f64>], scale: f64| {
+                    let coefficients = poly(roots);
+                    let mut padded = vec![0.0; 3 - coefficients.len()];
+                    padded.extend(coefficients.iter().map(|c| c.re * scale));
+                    padded
+                };
+                let (b, a) = (pad(&group.zeros, scale), pad(&group.poles, 1.0));
+                [b[0], b[1], b[2], a[0], a[1], a[2]]
+            })
+            .collect();
+        if self.gain < 0.0 {
+            for v in &mut sections[0][..3] {
+                *v = -*v;
+            }
+        }
+        Ok(sections)
+    }
+
+    /// 模拟低通 → 带通：s → (s² + wo²) / (bw·s)，wo 为中心角频率（两个边缘的几何平均），bw 为带宽（lp2bp）
+    ///
+    /// 每个根变为两个，原型的 ±1 rad/s 恰好映射到两个边缘；原型在 ∞ 处的零点一半移到原点
+    pub fn lp2bp(&self, wo: f64, bw: f64) -> Self {
+        let degree = self.poles.len().saturating_sub(self.zeros.len());
+        let split = |roots: &[Complex<f64>]| {
+            let scaled: Vec<Complex<f64>> = roots.iter().map(|r| r * (bw / 2.0)).collect();
+            let shift: Vec<Complex<f64>> = scaled.iter().map(|r| (r * r - wo * wo).sqrt()).collect();
+            scaled.iter().zip(&shift).map(|(r, d)| r + d)
+                .chain(scaled.iter().zip(&shift).map(|(r, d)| r - d))
+                .collect::<Vec<_>>()
+        };
+        let mut zeros = split(&self.zeros);
+        zeros.extend(std::iter::repeat_n(Complex::new(0.0, 0.0), degree));
+        Self {
+            zeros,
+            poles: split(&self.poles),
+            gain: self.gain * bw.powi(degree as i32),
+        }
+    }
+
+    /// 模拟低通 → 带阻：s → bw·s / (s² + wo²)（lp2bs）
+    ///
+    /// 原型在 ∞ 处的零点移到 ±j·wo，即阻带中心的陷波点
+    pub fn lp2bs(&self, wo: f64, bw: f64) -> Self {
+        let degree = self.poles.len().saturating_sub(self.zeros.len());
+        let split = |roots: &[Complex<f64>]| {
+            let inverted: Vec<Complex<f64>> = roots.iter().map(|r| (bw / 2.0) / r).collect();
+            let shift: Vec<Complex<f64>> = inverted.iter().map(|r| (r * r - wo * wo).sqrt()).collect();
+            inverted.iter().zip(&shift).map(|(r, d)| r + d)
+                .chain(inverted.iter().zip(&shift).map(|(r, d)| r - d))
+                .collect::<Vec<_>>()
+        };
+        let mut zeros = split(&self.zeros);
+        zeros.extend(std::iter::repeat_n(Complex::new(0.0, wo), degree));
+        zeros.extend(std::iter::repeat_n(Complex::new(0.0, -wo), degree));
+        let gain = self.gain * pairwise_ratio(&self.zeros, &self.poles, Complex::new(0.0, 0.0), 1.0).re;
+        Self { zeros, poles: split(&self.poles), gain }
+    }
+
+    /// 模拟滤波器 H(s) 经双线性变换 s = 2f_s·(z - 1)/(z + 1) 得到数字滤波器
+    ///
+    /// z = (2f_s + s)/(2f_s - s)，s = ∞ 处的零点映射到 z = -1（Nyquist 频率）
+    pub fn bilinear(&self, sample_rate: f64) -> Self {
+        let fs2 = 2.0 * sample_rate;
+        let map = |s: &Complex<f64>| (fs2 + s) / (fs2 - s);
+        let mut zeros: Vec<Complex<f64>> = self.zeros.iter().map(map).collect();
+        zeros.resize(self.poles.len().max(self.zeros.len()), Complex::new(-1.0, 0.0));
+        let ratio = pairwise_ratio(&self.zeros, &self.poles, Complex::new(fs2, 0.0), 1.0);
+        Self {
+            zeros,
+            poles: self.poles.iter().map(map).collect(),
+            gain: self.gain * ratio.re,
+        }
+    }
+
+    /// 在 z（或模拟滤波器的 s）处求 H 的值
+    pub fn evaluate(&self, z: Complex<f64>) -> Complex<f64> {
+        pairwise_ratio(&self.zeros, &self.poles, z, 1.0) * self.gain
+    }
+
+    /// 同 [`Zpk::evaluate`]，但 |k| 以 ln|k| 给出，符号取自 gain 字段
+    ///
+    /// |k| 按极点数开方后逐对乘入，k ≈ wc^N 超出 f64 范围的高阶模拟滤波器也能求值
+    pub fn evaluate_log_gain(&self, s: Complex<f64>, log_gain: f64) -> Complex<f64> {
+        let sign = 1.0f64.copysign(self.gain);
+        if self.poles.is_empty() {
+            return pairwise_ratio(&self.zeros, &[], s, 1.0) * log_gain.exp() * sign;
+        }
+        let scale = (log_gain / self.poles.len() as f64).exp();
+        pairwise_ratio(&self.zeros, &self.poles, s, scale) * sign
+    }
+
+    /// 保持零极点不变，重新确定增益，使 H(z) 在 z 处取给定的实数值
+    pub fn with_gain_at(&self, z: Complex<f64>, value: f64) -> Self {
+        let unit = Self { gain: 1.0, ..self.clone() }.evaluate(z);
+        Self { gain: (value / unit).re, ..self.clone() }
+    }
+}
+
+/// scale^{N_p}·Π(x - z_i) / Π(x - p_i)，N_p 为极点数
+///
+/// 零极点逐对相除再连乘：高阶（约 64 阶以上）时分子、分母各自的乘积会溢出为 ∞，相除得到 NaN。
+/// 每个极点因子另乘 scale，供增益本身超出 f64 范围时把它分摊到各对上
+fn pairwise_ratio(zeros: &[Complex<f64>], poles: &[Complex<f64>], x: Complex<f64>, scale: f64) -> Complex<f64> {
+    let mut zeros = zeros.iter();
+    let mut poles = poles.iter();
+    let mut ratio = Complex::new(1.0, 0.0);
+    loop {
+        match (zeros.next(), poles.next()) {
+            (Some(z), Some(p)) => ratio *= (x - z) / (x - p) * scale,
+            (Some(z), None) => ratio *= x - z,
+            (None, Some(p)) => ratio *= scale / (x - p),
+            (None, None) => return ratio,
+        }
+    }
+}
+
+/// 二阶节级联展开为传递函数（sos2tf）
+pub fn sos_to_tf(sections: &[SosSection]) -> (Vec<f64>, Vec<f64>) {
+    sections.iter().fold((vec![1.0], vec![1.0]), |(b, a), s| {
+        (convolve(&b, &s[..3]), convolve(&a, &s[3..]))
+    })
+}
+
+/// 传递函数分组为二阶节（tf2sos），经 [`Zpk::from_tf`] 求根
+pub fn tf_to_sos(b: &[f64], a: &[f64]) -> Result<Vec<SosSection>, String> {
+    Zpk::from_tf(b, a)?.to_sos()
+}
+
+/// 多项式乘法（系数卷积）
+pub fn convolve(p: &[f64], q: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; p.len() + q.len() - 1];
+    for (i, &x) in p.iter().enumerate() {
+        for (j, &y) in q.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
+
+/// 以给定根构造首一多项式，返回降幂系数
+pub fn poly(roots: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let mut coefficients = vec![Complex::new(1.0, 0.0)];
+    for &r in roots {
+        coefficients.push(Complex::new(0.0, 0.0));
+        for i in (1..coefficients.len()).rev() {
+            let previous = coefficients[i - 1];
+            coefficients[i] -= r * previous;
+        }
+    }
+    coefficients
+}
+
+/// 实系数多项式求根（Aberth–Ehrlich 迭代），coefficients 为降幂系数
+///
+/// 首部的零系数被忽略（次数降低），尾部的零系数给出精确的原点根
+pub fn roots(coefficients: &[f64]) -> Vec<Complex<f64>> {
+    let Some(start) = coefficients.iter().position(|&c| c != 0.0) else {
+        return Vec::new();
+    };
+    let end = coefficients.iter().rposition(|&c| c != 0.0).unwrap_or(start);
+    let mut result = vec![Complex::new(0.0, 0.0); coefficients.len() - 1 - end];
+    let c: Vec<Complex<f64>> = coefficients[start..=end]
+        .iter()
+        .map(|&v| Complex::new(v / coefficients[start], 0.0))
+        .collect();
+    let degree = c.len() - 1;
+    match degree {
+        0 => return result,
+        1 => {
+            result.push(-c[1]);
+            return result;
+        }
+        2 => {
+            result.extend(quadratic_roots(c[1].re, c[2].re));
+            return result;
+        }
+        _ => {}
+    }
+
+    // 初值取在半径为根模几何平均的圆上，起始角避开实轴
+    let radius = c[degree].norm().powf(1.0 / degree as f64).max(1e-3);
+    let mut z: Vec<Complex<f64>> = (0..degree)
+        .map(|k| Complex::from_polar(radius, 2.0 * std::f64::consts::PI * k as f64 / degree as f64 + 0.4))
+        .collect();
+    let evaluate = |x: Complex<f64>| {
+        let mut p = c[0];
+        let mut dp = Complex::new(0.0, 0.0);
+        for &coefficient in &c[1..] {
+            dp = dp * x + p;
+            p = p * x + coefficient;
+        }
+        (p, dp)
+    };
+    for _ in 0..500 {
+        let mut largest_step: f64 = 0.0;
+        for i in 0..degree {
+            let (p, dp) = evaluate(z[i]);
+            if p.norm() == 0.0 {
+                continue;
+            }
+            let ratio = p / dp;
+            let repulsion: Complex<f64> = (0..degree).filter(|&j| j != i).map(|j| (z[i] - z[j]).inv()).sum();
+            let step = ratio / (Complex::new(1.0, 0.0) - ratio * repulsion);
+            if step.is_finite() {
+                z[i] -= step;
+                largest_step = largest_step.max(step.norm() / (1.0 + z[i].norm()));
+            }
+        }
+        if largest_step < 1e-15 {
+            break;
+        }
+    }
+    result.extend(z);
+    result
+}
+
+/// z² + b·z + c = 0 的两根；实根用 q = -(b + sign(b)·√Δ)/2、c/q 的形式避免相消，重根精确
+fn quadratic_roots(b: f64, c: f64) -> [Complex<f64>; 2] {
+    let discriminant = b * b - 4.0 * c;
+    if discriminant >= 0.0 {
+        let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+        if q == 0.0 {
+            return [Complex::new(0.0, 0.0); 2];
+        }
+        [Complex::new(q, 0.0), Complex::new(c / q, 0.0)]
+    } else {
+        let im = 0.5 * (-discriminant).sqrt();
+        [Complex::new(-0.5 * b, im), Complex::new(-0.5 * b, -im)]
+    }
+}
+
+/// 求根后实根或共轭对中的一个
+#[derive(Debug, Clone, Copy)]
+enum Root {
+    Real(f64),
+    /// 虚部为正的那一个
+    Pair(Complex<f64>),
+}
+
+impl Root {
+    fn root(&self) -> Complex<f64> {
+        match *self {
+            Root::Real(r) => Complex::new(r, 0.0),
+            Root::Pair(c) => c,
+        }
+    }
+}
+
+/// 把根分为实根与共轭对，共轭对只保留虚部为正者
+fn split_conjugates(roots: &[Complex<f64>]) -> Result<Vec<Root>, String> {
+    let tol = |c: &Complex<f64>| CONJUGATE_TOL * (1.0 + c.norm());
+    let mut result = Vec::new();
+    let mut negative: Vec<Complex<f64>> = Vec::new();
+    for r in roots {
+        if r.im.abs() <= tol(r) {
+            result.push(Root::Real(r.re));
+        } else if r.im > 0.0 {
+            result.push(Root::Pair(*r));
+        } else {
+            negative.push(*r);
+        }
+    }
+    for r in result.iter().filter_map(|r| match r {
+        Root::Pair(c) => Some(*c),
+        Root::Real(_) => None,
+    }) {
+        let index = negative
+            .iter()
+            .position(|n| (n.conj() - r).norm() <= tol(&r))
+            .ok_or_else(|| format!(" {:.6}{:+.6}j 没有共轭", r.re, r.im))?;
+        negative.swap_remove(index);
+    }
+    match negative.first() {
+        Some(n) => Err(format!(" {:.6}{:+.6}j 没有共轭", n.re, n.im)),
+        None => Ok(result),
+    }
+}
+
+/// s 域分节时的一节：一个或两个极点，以及分配给它的零点
+struct RootGroup {
+    poles: Vec<Complex<f64>>,
+    zeros: Vec<Complex<f64>>,
+}
+
+fn distance_to_unit_circle(z: Complex<f64>) -> f64 {
+    (1.0 - z.norm()).abs()
+}
+
+/// 取出离 target 最近的实根
+///
+/// 实根与共轭对的根总数为偶数，取走一个实根后必然还剩奇数个实根，因此配对时总能找到
+fn take_nearest_real(roots: &mut Vec<Root>, target: f64) -> Option<Complex<f64>> {
+    let index = roots
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| matches!(r, Root::Real(_)))
+        .min_by(|a, b| (a.1.root() - target).norm().total_cmp(&(b.1.root() - target).norm()))
+        .map(|(i, _)| i)?;
+    Some(roots.swap_remove(index).root())
+}
+
+/// 取出离 target 最近的根（实根或共轭对）
+fn take_nearest_root(roots: &mut Vec<Root>, target: Complex<f64>) -> Root {
+    let (index, _) = roots
+        .iter()
+        .enumerate()
+        .min_by(|a, b| (a.1.root() - target).norm().total_cmp(&(b.1.root() - target).norm()))
+        .expect("零点个数与极点相同");
+    roots.swap_remove(index)
+}
+
+/// 由两个零点、两个极点构成一节 [1, -(z1+z2), z1·z2, 1, -(p1+p2), p1·p2]
+fn quadratic_section(zeros: [Complex<f64>; 2], poles: [Complex<f64>; 2]) -> SosSection {
+    let [z1, z2] = zeros;
+    let [p1, p2] = poles;
+    [1.0, -(z1 + z2).re, (z1 * z2).re, 1.0, -(p1 + p2).re, (p1 * p2).re]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut roots: Vec<Complex<f64>>) -> Vec<Complex<f64>> {
+        roots.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+        roots
+    }
+
+    #[test]
+    fn test_roots_and_poly() {
+        // (z - 2)(z + 0.5)(z² + 2z + 5)：根 2、-0.5、-1 ± 2j
+        let coefficients = convolve(&convolve(&[1.0, -2.0], &[1.0, 0.5]), &[1.0, 2.0, 5.0]);
+        let found = sorted(roots(&coefficients));
+        let expected = [
+            Complex::new(-1.0, -2.0),
+            Complex::new(-1.0, 2.0),
+            Complex::new(-0.5, 0.0),
+            Complex::new(2.0, 0.0),
+        ];
+        for (a, b) in found.iter().zip(&expected) {
+            assert!((a - b).norm() < 1e-12, "{} vs {}", a, b);
+        }
+        let rebuilt: Vec<f64> = poly(&found).iter().map(|c| c.re).collect();
+        for (a, b) in rebuilt.iter().zip(&coefficients) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        // 首部零系数降低次数，尾部零系数给出原点根
+        assert_eq!(roots(&[0.0, 2.0, -4.0]), vec![Complex::new(2.0, 0.0)]);
+        assert_eq!(roots(&[1.0, 0.0, 0.0]), vec![Complex::new(0.0, 0.0); 2]);
+    }
+
+    #[test]
+    fn test_tf_zpk_sos_round_trip() {
+        let zpk = Zpk::new(
+            vec![Complex::new(-1.0, 0.0), Complex::new(0.3, 0.9), Complex::new(0.3, -0.9)],
+            vec![Complex::new(0.5, 0.0), Complex::new(0.6, 0.7), Complex::new(0.6, -0.7)],
+            0.25,
+        );
+        let (b, a) = zpk.to_tf();
+        assert_eq!((b.len(), a.len()), (4, 4));
+
+        let back = Zpk::from_tf(&b, &a).unwrap();
+        assert!((back.gain - 0.25).abs() < 1e-12);
+        for (x, y) in sorted(back.poles.clone()).iter().zip(sorted(zpk.poles.clone()).iter()) {
+            assert!((x - y).norm() < 1e-10);
+        }
+
+        // 奇数阶补一个原点零极点；离单位圆最近的极点（0.6 ± 0.7j，|p| ≈ 0.92）排在最后一节
+        let sos = zpk.to_sos().unwrap();
+        assert_eq!(sos.len(), 2);
+        assert!((sos[1][4] + 1.2).abs() < 1e-12 && (sos[1][5] - 0.85).abs() < 1e-12);
+        let (b2, a2) = sos_to_tf(&sos);
+        for (x, y) in b2.iter().zip(&b).chain(a2.iter().zip(&a)) {
+            assert!((x - y).abs() < 1e-12);
+        }
+
+        let from_sos = Zpk::from_sos(&sos).unwrap();
+        assert_eq!(from_sos.poles.len(), 3);
+        let z = Complex::from_polar(1.0, 0.7);
+        assert!((from_sos.evaluate(z) - zpk.evaluate(z)).norm() < 1e-12);
+
+        // 没有共轭的复根无法分组
+        let unpaired = Zpk::new(vec![], vec![Complex::new(0.1, 0.2)], 1.0);
+        assert!(unpaired.to_sos().is_err());
+    }
+
+    #[test]
+    fn test_bilinear_maps_analog_prototype() {
+        // 一阶模拟低通 ω_c/(s + ω_c)：直流增益 1，s = ∞ 的零点映射到 z = -1
+        let (fs, wc) = (1000.0, 2.0 * 1000.0 * (std::f64::consts::PI * 100.0 / 1000.0).tan());
+        let analog = Zpk::new(vec![], vec![Complex::new(-wc, 0.0)], wc);
+        let digital = analog.bilinear(fs);
+        assert_eq!(digital.zeros, vec![Complex::new(-1.0, 0.0)]);
+        assert!((digital.evaluate(Complex::new(1.0, 0.0)).norm() - 1.0).abs() < 1e-12);
+        // 预畸变后截止频率处 |H| = 1/√2
+        let at_cutoff = digital.evaluate(Complex::from_polar(1.0, 2.0 * std::f64::consts::PI * 100.0 / fs));
+        assert!((at_cutoff.norm() - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_analog_sections() {
+        // 5 阶椭圆原型：两对纯虚零点配给两个二次节，剩下的实极点单独成一阶节
+        let family = crate::analog_prototype::FilterFamily::Elliptic { ripple_db: 1.0, attenuation_db: 40.0 };
+        let prototype = crate::analog_prototype::prototype(&family, 5).unwrap().lp2lp(2000.0);
+        let sections = prototype.to_analog_sos().unwrap();
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections.iter().filter(|s| s[3] == 0.0).count(), 1);
+        assert!(sections.iter().filter(|s| s[3] != 0.0).all(|s| s[0] != 0.0 && s[1].abs() < 1e-6 * s[2]));
+        let s = Complex::new(0.0, 1500.0);
+        let cascade: Complex<f64> = sections
+            .iter()
+            .map(|c| (c[0] * s * s + c[1] * s + c[2]) / (c[3] * s * s + c[4] * s + c[5]))
+            .product();
+        assert!((cascade - prototype.evaluate(s)).norm() < 1e-9 * prototype.evaluate(s).norm());
+
+        // lp2hp：截止角频率处的增益不变，∞ 处的零点移到原点
+        let highpass = prototype.lp2lp(1.0 / 2000.0).lp2hp(500.0);
+        assert_eq!(highpass.zeros.len(), 5);
+        let at = |zpk: &Zpk, w: f64| zpk.evaluate(Complex::new(0.0, w)).norm();
+        assert!((at(&highpass, 500.0) - at(&prototype, 2000.0)).abs() < 1e-9);
+        assert!(at(&highpass, 1e-3) < 1e-6);
+
+        assert!(Zpk::new(vec![Complex::new(1.0, 0.0); 2], vec![Complex::new(-1.0, 0.0)], 1.0).to_analog_sos().is_err());
+    }
+}